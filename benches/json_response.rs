@@ -0,0 +1,109 @@
+//! 大型嵌入响应的JSON解码基准。
+//!
+//! 对比直接从响应字节解码与先转换为`String`再解码的开销，
+//! 并通过本地模拟服务器测量`Embeddings::create`的完整耗时。
+//!
+//! 运行：`cargo bench --bench json_response`
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use openai4rs::embeddings::EmbeddingsParam;
+use openai4rs::embeddings::types::EmbeddingResponse;
+use openai4rs::*;
+use std::hint::black_box;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// 生成包含`count`个`dimensions`维浮点向量的嵌入响应。
+fn embeddings_payload(count: usize, dimensions: usize) -> Vec<u8> {
+    let data: Vec<serde_json::Value> = (0..count)
+        .map(|index| {
+            let embedding: Vec<f32> = (0..dimensions)
+                .map(|i| ((index * dimensions + i) as f32).sin())
+                .collect();
+            serde_json::json!({
+                "object": "embedding",
+                "index": index,
+                "embedding": embedding,
+            })
+        })
+        .collect();
+    serde_json::to_vec(&serde_json::json!({
+        "object": "list",
+        "model": "text-embedding-3-small",
+        "data": data,
+        "usage": {"prompt_tokens": count, "total_tokens": count},
+    }))
+    .unwrap()
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("embeddings_decode");
+    group.sample_size(20);
+
+    for count in [64, 512] {
+        let payload = embeddings_payload(count, 1536);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("from_slice", count),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    let response: EmbeddingResponse =
+                        serde_json::from_slice(black_box(payload)).unwrap();
+                    black_box(response)
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("text_then_from_str", count),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    let text = String::from_utf8(black_box(payload).clone()).unwrap();
+                    let response: EmbeddingResponse = serde_json::from_str(&text).unwrap();
+                    black_box(response)
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_create(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let payload = embeddings_payload(512, 1536);
+    let payload_len = payload.len() as u64;
+
+    let server = runtime.block_on(async {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/embeddings"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(payload, "application/json"))
+            .mount(&server)
+            .await;
+        server
+    });
+    let client = OpenAI::new("test-key", &server.uri());
+
+    let mut group = c.benchmark_group("embeddings_create");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(payload_len));
+    group.bench_function("512x1536", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(
+                    client
+                        .embeddings()
+                        .create(EmbeddingsParam::new("text-embedding-3-small", "hello")),
+                )
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode, bench_create);
+criterion_main!(benches);
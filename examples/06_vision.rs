@@ -15,10 +15,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let messages = vec![
         system!(content: "You are a helpful assistant that can describe images."),
-        user!(content: vec![
-            content!({"type": "image_url", "image_url":{"url":image_url}}) // This macro likely needs to be adapted for vision, but we'll use it for now.
-            ,content!({"type": "text", "text": "What is in this image?"})
-        ]),
+        user!(content: content!(image_url: image_url, text: "What is in this image?")),
     ];
     let request = ChatParam::new(model, &messages);
     println!("Sending request to model: {} with an image...", model);
@@ -21,6 +21,24 @@ pub fn system(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Creates a `ChatCompletionMessageParam::Developer` message.
+///
+/// Newer o-series models use the `developer` role in place of `system`.
+///
+/// This macro supports two forms:
+/// 1. Simple form: `developer!("content")`
+/// 2. Key-value form: `developer!(content: "content", name: "name")`
+///
+/// The `name` field is optional in the key-value form.
+#[proc_macro]
+pub fn developer(input: TokenStream) -> TokenStream {
+    let st = parse_macro_input!(input as proc_macro2::TokenStream);
+    match macros::developer::developer_impl(st) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 /// Creates a `ChatCompletionMessageParam::User` message.
 ///
 /// This macro supports two forms:
@@ -57,6 +75,8 @@ pub fn assistant(input: TokenStream) -> TokenStream {
 /// Creates a `ChatCompletionMessageParam::Tool` message.
 ///
 /// This macro requires key-value form with both `tool_call_id` and `content` fields.
+/// `content` may be a string, a JSON literal or any `Serialize` value; objects are
+/// serialized to a JSON string as required for tool results.
 #[proc_macro]
 pub fn tool(input: TokenStream) -> TokenStream {
     let st = parse_macro_input!(input as proc_macro2::TokenStream);
@@ -69,6 +89,10 @@ pub fn tool(input: TokenStream) -> TokenStream {
 /// Creates a `chat::Content` value.
 ///
 /// This macro supports both simple string content and complex JSON content.
+/// Multimodal content can be built from an ordered list of parts, e.g.
+/// `content!(text: "What is in this image?", image_url: "https://...")`, which produces
+/// the `[{"type": "text", ...}, {"type": "image_url", ...}]` array expected by vision models.
+/// Keys may repeat and the parts keep their order.
 #[proc_macro]
 pub fn content(input: TokenStream) -> TokenStream {
     let st = parse_macro_input!(input as proc_macro2::TokenStream);
@@ -77,3 +101,18 @@ pub fn content(input: TokenStream) -> TokenStream {
         Err(e) => e.to_compile_error().into(),
     }
 }
+
+/// Derives `ToolParameters`, generating a JSON schema `Parameters` value from a Rust type.
+///
+/// Structs with named fields become `object` schemas: field doc comments become property
+/// descriptions, and every field except `Option<T>` or `#[serde(default)]` fields is required.
+/// Enums with only unit variants become string enums. `#[serde(rename)]`, `#[serde(rename_all)]`
+/// and `#[serde(skip)]` are honored so the schema matches what serde deserializes.
+#[proc_macro_derive(ToolParameters, attributes(serde))]
+pub fn tool_parameters(input: TokenStream) -> TokenStream {
+    let st = parse_macro_input!(input as proc_macro2::TokenStream);
+    match macros::tool_parameters::tool_parameters_impl(st) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -1,8 +1,115 @@
+use crate::parser::KeyValue;
 use crate::utils::{expand_content, get_crate_path};
-use proc_macro2::TokenStream as TokenStream2;
-use syn::Result;
+use proc_macro2::{Spacing, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Result, Token, parse2};
+
+/// Part kinds accepted by the `content!(text: ..., image_url: ...)` form.
+const PART_KINDS: [&str; 2] = ["text", "image_url"];
 
 pub fn content_impl(input: TokenStream2) -> Result<TokenStream2> {
     let root = get_crate_path();
-    Ok(expand_content(&root, input))
+    if !is_parts_syntax(&input) {
+        return Ok(expand_content(&root, input));
+    }
+
+    let ContentParts(parts) = parse2(input)?;
+    let parts = parts.iter().map(|part| {
+        let value = &part.value;
+        if part.key == "text" {
+            quote! { #root::serde_json::json!({"type": "text", "text": (#value)}) }
+        } else {
+            quote! { #root::serde_json::json!({"type": "image_url", "image_url": {"url": (#value)}}) }
+        }
+    });
+
+    Ok(quote! {
+        #root::modules::chat::types::Content::Object(
+            #root::serde_json::Value::Array(::std::vec![#(#parts),*])
+        )
+    })
+}
+
+/// Returns `true` when the input starts with `ident:` (but not a `::` path), e.g.
+/// `content!(text: "describe", image_url: url)`. Anything else is treated as a JSON expression.
+fn is_parts_syntax(input: &TokenStream2) -> bool {
+    let mut tokens = input.clone().into_iter();
+    matches!(
+        (tokens.next(), tokens.next()),
+        (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(punct)))
+            if punct.as_char() == ':' && punct.spacing() == Spacing::Alone
+    )
+}
+
+/// An ordered list of content parts, e.g. `text: "describe", image_url: url, text: "briefly"`.
+/// Keys may repeat; each pair becomes one part in the given order.
+struct ContentParts(Punctuated<KeyValue, Token![,]>);
+
+impl Parse for ContentParts {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let parts = Punctuated::<KeyValue, Token![,]>::parse_terminated(input)?;
+        for part in &parts {
+            if !PART_KINDS.contains(&part.key.to_string().as_str()) {
+                return Err(syn::Error::new(
+                    part.key.span(),
+                    format!(
+                        "The content part '{}' must be one of the following: {:?}",
+                        part.key, PART_KINDS
+                    ),
+                ));
+            }
+        }
+        Ok(Self(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::parse_str;
+
+    fn keys(parts: &ContentParts) -> Vec<String> {
+        parts.0.iter().map(|part| part.key.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_parts_in_order() {
+        let parts: ContentParts =
+            parse_str(r#"text: "describe", image_url: url, text: "briefly","#).unwrap();
+        assert_eq!(keys(&parts), ["text", "image_url", "text"]);
+        assert_eq!(parts.0[1].value.to_token_stream().to_string(), "url");
+    }
+
+    #[test]
+    fn rejects_unknown_part_kind() {
+        let error = parse_str::<ContentParts>(r#"text: "hi", audio: clip"#)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"The content part 'audio' must be one of the following: ["text", "image_url"]"#
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_parts() {
+        // Missing value
+        assert!(parse_str::<ContentParts>("text:").is_err());
+        // Missing separator between parts
+        assert!(parse_str::<ContentParts>(r#"text: "a" image_url: b"#).is_err());
+        // Key without `:` or `=`
+        assert!(parse_str::<ContentParts>(r#"text "a""#).is_err());
+    }
+
+    #[test]
+    fn detects_parts_syntax() {
+        let detect = |input: &str| is_parts_syntax(&parse_str(input).unwrap());
+        assert!(detect(r#"text: "describe", image_url: url"#));
+        assert!(!detect(r#"{"type": "text", "text": "hi"}"#));
+        assert!(!detect(r#""plain text""#));
+        assert!(!detect("serde_json::json!([])"));
+    }
 }
@@ -1,5 +1,7 @@
 pub mod assistant;
 pub mod content;
+pub mod developer;
 pub mod system;
 pub mod tool;
+pub mod tool_parameters;
 pub mod user;
@@ -1,5 +1,5 @@
 use crate::parser::MacroInput;
-use crate::utils::{FieldValidator, expand_content, get_crate_path};
+use crate::utils::{FieldValidator, get_crate_path};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::spanned::Spanned;
@@ -24,7 +24,17 @@ pub fn tool_impl(input: TokenStream2) -> Result<TokenStream2> {
         }
     };
 
-    let content = expand_content(&root, quote! {#content});
+    // Tool results must be a string or an array of content parts, so JSON objects
+    // (including serialized structs) are sent as their JSON text.
+    let content = quote! {
+        {
+            let value = #root::serde_json::json!(#content);
+            match value {
+                #root::serde_json::Value::Object(_) => #root::modules::chat::types::Content::Text(value.to_string()),
+                other => #root::modules::chat::types::Content::from(other),
+            }
+        }
+    };
 
     Ok(quote! {
         #root::modules::chat::types::ChatCompletionMessageParam::Tool(
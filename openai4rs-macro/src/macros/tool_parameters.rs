@@ -0,0 +1,243 @@
+use crate::utils::get_crate_path;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, Meta, Result, parse2};
+
+pub fn tool_parameters_impl(input: TokenStream2) -> Result<TokenStream2> {
+    let input: DeriveInput = parse2(input)?;
+    let root = get_crate_path();
+    let params = quote!(#root::modules::chat::tool_parameters);
+
+    let container = SerdeAttrs::parse(&input.attrs)?;
+    let description = describe(&doc_comment(&input.attrs));
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let Fields::Named(fields) = &data.fields else {
+                return Err(syn::Error::new(
+                    data.fields.span(),
+                    "`ToolParameters` can only be derived for structs with named fields",
+                ));
+            };
+
+            let mut properties = Vec::new();
+            for field in &fields.named {
+                let attrs = SerdeAttrs::parse(&field.attrs)?;
+                if attrs.skip {
+                    continue;
+                }
+
+                let ident = field.ident.as_ref().unwrap().to_string();
+                let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+                let name = match attrs.rename {
+                    Some(name) => name,
+                    None => rename_field(ident, container.rename_all.as_deref(), field.span())?,
+                };
+
+                let ty = &field.ty;
+                let schema = match doc_comment(&field.attrs) {
+                    Some(doc) => quote! {
+                        <#ty as #params::ToolParameters>::parameters().with_description(#doc)
+                    },
+                    None => quote!(<#ty as #params::ToolParameters>::parameters()),
+                };
+                let has_default = attrs.default;
+                properties.push(quote! {
+                    builder = builder.property(#name, #schema);
+                    if !#has_default && !<#ty as #params::ToolParameters>::OPTIONAL {
+                        required.push(#name.to_string());
+                    }
+                });
+            }
+
+            quote! {
+                let mut builder = #params::Parameters::object() #description;
+                let mut required: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(#properties)*
+                if !required.is_empty() {
+                    builder = builder.required(required);
+                }
+                builder
+                    .build()
+                    .expect("every required property is defined")
+            }
+        }
+        Data::Enum(data) => {
+            let mut values = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        "`ToolParameters` can only be derived for enums with unit variants",
+                    ));
+                }
+                let attrs = SerdeAttrs::parse(&variant.attrs)?;
+                if attrs.skip {
+                    continue;
+                }
+                let name = match attrs.rename {
+                    Some(name) => name,
+                    None => rename_variant(
+                        &variant.ident.to_string(),
+                        container.rename_all.as_deref(),
+                        variant.span(),
+                    )?,
+                };
+                values.push(name);
+            }
+
+            quote! {
+                #params::Parameters::string() #description
+                    #(.enum_str(#values))*
+                    .build()
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "`ToolParameters` cannot be derived for unions",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #params::ToolParameters for #ident #ty_generics #where_clause {
+            fn parameters() -> #params::Parameters {
+                #body
+            }
+        }
+    })
+}
+
+/// Emits a `.description(...)` builder call when a doc comment is present.
+fn describe(doc: &Option<String>) -> TokenStream2 {
+    match doc {
+        Some(doc) => quote!(.description(#doc)),
+        None => quote!(),
+    }
+}
+
+/// Collects `///` doc comments into a single string, one line per comment line.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// The subset of `#[serde(...)]` attributes that affect the generated schema.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+    default: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = SerdeAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("rename_all") {
+                    parsed.rename_all = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("default") {
+                    parsed.default = true;
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<Expr>()?;
+                    }
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Ignore attributes that do not affect the schema, e.g. `with = "..."`.
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    content.parse::<TokenStream2>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Applies a serde `rename_all` rule to a `snake_case` field name.
+fn rename_field(name: &str, rule: Option<&str>, span: proc_macro2::Span) -> Result<String> {
+    let words: Vec<&str> = name.split('_').filter(|w| !w.is_empty()).collect();
+    apply_rule(name, &words, rule, span)
+}
+
+/// Applies a serde `rename_all` rule to a `PascalCase` variant name.
+fn rename_variant(name: &str, rule: Option<&str>, span: proc_macro2::Span) -> Result<String> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, c) in name.char_indices().skip(1) {
+        if c.is_uppercase() {
+            words.push(&name[start..i]);
+            start = i;
+        }
+    }
+    words.push(&name[start..]);
+    apply_rule(name, &words, rule, span)
+}
+
+fn apply_rule(
+    name: &str,
+    words: &[&str],
+    rule: Option<&str>,
+    span: proc_macro2::Span,
+) -> Result<String> {
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect(),
+            None => String::new(),
+        }
+    };
+    let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+
+    Ok(match rule {
+        None => name.to_string(),
+        Some("lowercase") => lower.concat(),
+        Some("UPPERCASE") => lower.concat().to_uppercase(),
+        Some("PascalCase") => words.iter().map(|w| capitalize(w)).collect::<String>(),
+        Some("camelCase") => {
+            let mut renamed = lower.first().cloned().unwrap_or_default();
+            renamed.extend(words.iter().skip(1).map(|w| capitalize(w)));
+            renamed
+        }
+        Some("snake_case") => lower.join("_"),
+        Some("SCREAMING_SNAKE_CASE") => lower.join("_").to_uppercase(),
+        Some("kebab-case") => lower.join("-"),
+        Some("SCREAMING-KEBAB-CASE") => lower.join("-").to_uppercase(),
+        Some(other) => {
+            return Err(syn::Error::new(
+                span,
+                format!("unsupported `rename_all` rule `{other}`"),
+            ));
+        }
+    })
+}
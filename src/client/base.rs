@@ -1,6 +1,14 @@
+use crate::common::rate_limit::RateLimitSnapshot;
+use crate::config::{AzureConfig, Config};
+use crate::error::{ConfigError, OpenAIError, RequestError};
 use crate::modules::{Chat, Completions, Embeddings, Models};
-use crate::{config::Config, service::client::HttpClient};
+use crate::service::client::HttpClient;
+use crate::service::interceptor::Interceptor;
+use crate::service::request::{RequestBuilder, RequestSpec};
 use http::HeaderValue;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
 use std::time::Duration;
 
 #[doc = include_str!("../docs/openai.md")]
@@ -18,16 +26,31 @@ impl OpenAI {
     /// 如果需要更精细的控制请使用`Config::builder()`来构建配置后并使用`build_openai`方法创建客户端。
     #[must_use]
     pub fn new(api_key: &str, base_url: &str) -> OpenAI {
-        let config = Config::new(api_key.to_string(), base_url.to_string());
-        let http_client = HttpClient::new(config);
+        Self::with_config(Config::new(api_key.to_string(), base_url.to_string()))
+    }
 
-        OpenAI {
-            chat: Chat::new(http_client.clone()),
-            completions: Completions::new(http_client.clone()),
-            models: Models::new(http_client.clone()),
-            embeddings: Embeddings::new(http_client.clone()),
-            http_client,
-        }
+    /// 创建连接Azure OpenAI服务的客户端
+    ///
+    /// 请求将发送到 `{endpoint}/openai/deployments/{deployment}/...?api-version={api_version}`，
+    /// 并使用 `api-key` 请求头进行认证。需要更多设置时请使用[`ConfigBuilder::azure`](crate::ConfigBuilder::azure)。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::OpenAI;
+    ///
+    /// let client = OpenAI::azure(
+    ///     "https://my-resource.openai.azure.com",
+    ///     "azure-key",
+    ///     "gpt-4o-deployment",
+    ///     "2024-10-21",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn azure(endpoint: &str, api_key: &str, deployment: &str, api_version: &str) -> OpenAI {
+        let mut config = Config::new(api_key, endpoint.trim_end_matches('/'));
+        config.with_azure(AzureConfig::new(deployment, api_version));
+        Self::with_config(config)
     }
 
     /// 根据配置创建客户端
@@ -45,47 +68,103 @@ impl OpenAI {
     }
 
     #[doc = include_str!("../docs/from_env.md")]
-    pub fn from_env() -> Result<Self, String> {
-        let api_key = std::env::var("OPENAI_API_KEY")
-            .map_err(|_| "The `OPENAI_API_KEY` environment variable is not set.")?;
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_env_prefixed("OPENAI")
+    }
+
+    /// 从带有指定前缀的环境变量创建新的OpenAI客户端。
+    ///
+    /// 与[`OpenAI::from_env`]读取相同的变量集合，但将`OPENAI`前缀替换为`prefix`，
+    /// 例如`from_env_prefixed("FOO")`会读取`FOO_API_KEY`、`FOO_BASE_URL`、`FOO_TIMEOUT`等。
+    /// 这便于在同一进程中为多个提供商分别配置客户端。
+    ///
+    /// # 错误
+    ///
+    /// - 如果环境变量中未设置`{prefix}_API_KEY`，返回[`ConfigError::MissingEnvVar`]。
+    /// - 如果可选变量的值无法解析，返回[`ConfigError::InvalidEnvVar`]。所有无法解析的变量都会通过`tracing::warn!`记录，
+    ///   返回的错误对应其中第一个。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::OpenAI;
+    ///
+    /// // 读取 DEEPSEEK_API_KEY、DEEPSEEK_BASE_URL 等
+    /// let deepseek = OpenAI::from_env_prefixed("DEEPSEEK").unwrap();
+    /// // 读取 OPENROUTER_API_KEY、OPENROUTER_BASE_URL 等
+    /// let openrouter = OpenAI::from_env_prefixed("OPENROUTER").unwrap();
+    /// ```
+    pub fn from_env_prefixed(prefix: &str) -> Result<Self, ConfigError> {
+        let name = |key: &str| format!("{prefix}_{key}");
+
+        let api_key = std::env::var(name("API_KEY")).map_err(|_| ConfigError::MissingEnvVar {
+            name: name("API_KEY"),
+        })?;
         let base_url =
-            std::env::var("OPENAI_BASE_URL").unwrap_or("https://api.openai.com/v1".to_string());
+            std::env::var(name("BASE_URL")).unwrap_or("https://api.openai.com/v1".to_string());
 
         let mut config = Config::new(api_key, base_url);
 
         // Read optional environment variables
-        if let Ok(timeout) = std::env::var("OPENAI_TIMEOUT") {
-            if let Ok(timeout) = timeout.parse::<u64>() {
-                config.with_timeout(Duration::from_secs(timeout));
-            }
+        // 解析失败不会回退到默认值：所有错误都会被收集并记录，随后返回第一个错误
+        let mut errors = Vec::new();
+
+        if let Some(timeout) = parse_env_var::<u64>(&name("TIMEOUT"), &mut errors) {
+            config.with_timeout(Duration::from_secs(timeout));
         }
 
-        if let Ok(connect_timeout) = std::env::var("OPENAI_CONNECT_TIMEOUT") {
-            if let Ok(connect_timeout) = connect_timeout.parse::<u64>() {
-                config.with_connect_timeout(Duration::from_secs(connect_timeout));
-            }
+        if let Some(connect_timeout) = parse_env_var::<u64>(&name("CONNECT_TIMEOUT"), &mut errors) {
+            config.with_connect_timeout(Duration::from_secs(connect_timeout));
         }
 
-        if let Ok(retry_count) = std::env::var("OPENAI_RETRY_COUNT") {
-            if let Ok(retry_count) = retry_count.parse::<usize>() {
-                config.with_retry_count(retry_count);
-            }
+        if let Some(retry_count) = parse_env_var::<usize>(&name("RETRY_COUNT"), &mut errors) {
+            config.with_retry_count(retry_count);
         }
 
-        if let Ok(proxy) = std::env::var("OPENAI_PROXY") {
+        if let Ok(proxy) = std::env::var(name("PROXY")) {
             config.with_proxy(proxy);
         }
 
-        if let Ok(user_agent) = std::env::var("OPENAI_USER_AGENT") {
-            config.with_user_agent(HeaderValue::from_str(&user_agent).unwrap_or_else(|_| {
-                panic!("Cannot convert the value `{user_agent}` of environment variable `OPENAI_USER_AGENT` to HeaderValue, please check if the value is valid.")
-            }));
+        if let Some(user_agent) = parse_env_var::<HeaderValue>(&name("USER_AGENT"), &mut errors) {
+            config.with_user_agent(user_agent);
+        }
+
+        if let Some(error) = errors.into_iter().next() {
+            return Err(error);
         }
 
         Ok(Self::with_config(config))
     }
 }
 
+/// 读取并解析可选的环境变量，未设置或解析失败时返回`None`。
+///
+/// 解析失败时会通过`tracing::warn!`记录，并将错误追加到`errors`中。
+fn parse_env_var<T>(name: &str, errors: &mut Vec<ConfigError>) -> Option<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = std::env::var(name).ok()?;
+    match value.trim().parse::<T>() {
+        Ok(parsed) => Some(parsed),
+        Err(e) => {
+            tracing::warn!(
+                env_var = name,
+                value = %value,
+                error = %e,
+                "malformed environment variable"
+            );
+            errors.push(ConfigError::InvalidEnvVar {
+                name: name.to_string(),
+                value,
+                reason: e.to_string(),
+            });
+            None
+        }
+    }
+}
+
 impl OpenAI {
     #[doc = include_str!("../docs/chat.md")]
     #[inline]
@@ -156,6 +235,137 @@ impl OpenAI {
         self.http_client.config_write().with_api_key(api_key);
     }
 
+    /// 向未被本库建模的端点发送POST请求，并返回原始响应。
+    ///
+    /// 请求会复用客户端的认证、全局请求头与请求体字段、拦截器以及重试逻辑。
+    /// `route`是相对于基础URL的路径（例如`"/moderations"`），对于Azure OpenAI会被映射到
+    /// `{endpoint}/openai{route}?api-version=...`。
+    ///
+    /// # 错误
+    ///
+    /// 如果`body`不能序列化为JSON对象，返回`RequestError::InvalidParameter`；
+    /// 非2xx响应与其他请求一样返回`OpenAIError::Api`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenAI::from_env()?;
+    ///     let response = client
+    ///         .raw_post("/moderations", serde_json::json!({ "input": "hello" }))
+    ///         .await?;
+    ///     let body: serde_json::Value = response.json().await?;
+    ///     println!("{body:#?}");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn raw_post<B: Serialize>(
+        &self,
+        route: &str,
+        body: B,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        let body = match serde_json::to_value(body) {
+            Ok(serde_json::Value::Object(body)) => body,
+            Ok(other) => {
+                return Err(RequestError::InvalidParameter(format!(
+                    "raw request body must be a JSON object, got `{other}`"
+                ))
+                .into());
+            }
+            Err(e) => return Err(RequestError::InvalidParameter(e.to_string()).into()),
+        };
+
+        let http_params = RequestSpec::new(
+            |config| config.resource_url(route),
+            move |_, request| {
+                let mut builder = RequestBuilder::new(request);
+                builder.body_fields(body);
+                builder.take()
+            },
+        );
+        self.http_client.post_raw(http_params).await
+    }
+
+    /// 向未被本库建模的端点发送GET请求，并返回原始响应。
+    ///
+    /// 与[`OpenAI::raw_post`]相同，请求会复用客户端的认证、全局请求头、拦截器以及重试逻辑。
+    pub async fn raw_get(&self, route: &str) -> Result<reqwest::Response, OpenAIError> {
+        let http_params =
+            RequestSpec::new(|config| config.resource_url(route), |_, request| request);
+        self.http_client.get_raw(http_params).await
+    }
+
+    /// 为客户端添加一个拦截器，对之后发出的所有请求生效。
+    ///
+    /// 请求拦截器按添加顺序执行，响应和错误拦截器按添加的逆序执行。
+    pub fn add_interceptor<I: Interceptor + 'static>(&self, interceptor: I) {
+        self.http_client
+            .config_write()
+            .with_interceptor(interceptor);
+    }
+
+    /// 添加一个拦截器并返回客户端，便于在创建客户端时以链式调用注册拦截器。
+    ///
+    /// 与[`OpenAI::add_interceptor`]相同。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// struct TraceId;
+    ///
+    /// impl Interceptor for TraceId {
+    ///     fn on_request(&self, request: &mut Request) -> Result<(), OpenAIError> {
+    ///         request
+    ///             .headers_mut()
+    ///             .insert("x-trace-id", HeaderValue::from_static("trace-123"));
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let client = OpenAI::new("sk-...", "https://api.openai.com/v1").with_interceptor(TraceId);
+    /// ```
+    #[must_use]
+    pub fn with_interceptor<I: Interceptor + 'static>(self, interceptor: I) -> Self {
+        self.add_interceptor(interceptor);
+        self
+    }
+
+    /// 使用外部构建的reqwest客户端发送请求并返回客户端，便于复用已配置好TLS、连接池等设置的客户端。
+    ///
+    /// 参见[`ConfigBuilder::http_client`](crate::ConfigBuilder::http_client)。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// let http_client = reqwest::Client::builder()
+    ///     .pool_max_idle_per_host(32)
+    ///     .build()
+    ///     .unwrap();
+    /// let client = OpenAI::new("sk-...", "https://api.openai.com/v1").with_http_client(http_client);
+    /// ```
+    #[must_use]
+    pub fn with_http_client(self, http_client: reqwest::Client) -> Self {
+        self.update_config(|config| {
+            config.with_http_client(http_client);
+        });
+        self
+    }
+
+    /// 返回最近一次成功响应中的速率限制信息（来自`x-ratelimit-*`响应头）。
+    ///
+    /// 尚未收到带有速率限制响应头的响应时返回`None`。信息由客户端内部记录，不会向配置的拦截器链添加拦截器；
+    /// 其中的重置时长相对于收到该响应的时刻，可以通过[`RateLimitSnapshot::age`]得知快照已经过去多久。
+    pub fn rate_limit_status(&self) -> Option<RateLimitSnapshot> {
+        self.http_client.rate_limit_status()
+    }
+
     /// 更新客户端配置并重新创建HTTP客户端。
     ///
     /// 此方法允许您修改现有客户端的配置，并使用新设置自动重新创建内部HTTP客户端。
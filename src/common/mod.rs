@@ -7,6 +7,12 @@
 //!
 //! - [`CompletionGeneric`]: 一个泛型结构体，表示补全响应的通用字段。
 //! - [`CompletionUsage`]: 表示补全的令牌使用统计信息。
+//! - [`UsageTracker`]: 在多次请求之间累计令牌使用统计。
 //! - [`ServiceTier`]: 表示模型的服务层级。
+//! - [`Temperature`]、[`Penalty`]: 带取值范围检查的采样参数。
+//! - [`ApiResponse`]: 解析后的响应体及其状态码与响应头。
+//! - [`RateLimitInfo`]: 从`x-ratelimit-*`响应头解析出的速率限制信息。
 
+pub(crate) mod params;
+pub mod rate_limit;
 pub mod types;
@@ -0,0 +1,165 @@
+//! 各参数构建器共享的setter。
+//!
+//! 请求级别的设置（超时、请求头、重试次数）以及`user`字段在所有参数类型上的签名与行为必须一致，
+//! 因此统一由此处的宏生成，而不是在每个参数类型中各自实现。
+//! 使用这些宏的类型需要包含一个`inner: InParam`字段。
+
+/// 生成请求级别的setter：`timeout`、`connect_timeout`、`user_agent`、`header`、`retry_count`与`no_retry`。
+///
+/// 这些设置只影响本次请求的发送方式，不会在请求体中序列化。
+macro_rules! impl_request_options {
+    ($param:ty) => {
+        impl $param {
+            /// 超时时间。HTTP请求超时时间，覆盖客户端的全局设置。
+            ///
+            /// 此字段不会在请求体中序列化。
+            pub fn timeout(mut self, timeout: ::std::time::Duration) -> Self {
+                self.inner
+                    .extensions
+                    .insert($crate::common::types::Timeout(timeout));
+                self
+            }
+
+            /// 连接超时时间。建立TCP连接的超时时间，覆盖客户端的全局设置。
+            ///
+            /// 与全局设置不同时，本次请求会使用按该连接超时缓存的HTTP客户端发送，相同连接超时的请求共享同一连接池。
+            /// `wasm32`下连接由浏览器管理，此设置会被忽略。此字段不会在请求体中序列化。
+            pub fn connect_timeout(mut self, connect_timeout: ::std::time::Duration) -> Self {
+                self.inner
+                    .extensions
+                    .insert($crate::common::types::ConnectTimeout(connect_timeout));
+                self
+            }
+
+            /// 用户代理。HTTP请求User-Agent，覆盖客户端的全局设置。
+            pub fn user_agent(mut self, user_agent: ::http::HeaderValue) -> Self {
+                self.inner
+                    .headers
+                    .insert(::http::header::USER_AGENT, user_agent);
+                self
+            }
+
+            /// 设置HTTP请求头信息。
+            pub fn header<K: ::http::header::IntoHeaderName>(
+                mut self,
+                key: K,
+                val: ::http::HeaderValue,
+            ) -> Self {
+                self.inner.headers.insert(key, val);
+                self
+            }
+
+            /// 重试次数。本次请求的最大尝试次数，覆盖客户端的全局设置。
+            ///
+            /// 只要调用了此方法，无论传入何值都优先于客户端的[`Config::retry_count`](crate::Config::retry_count)；
+            /// `0`和`1`均表示只尝试一次、不重试。
+            ///
+            /// 此字段不会在请求体中序列化。
+            pub fn retry_count(mut self, retry_count: usize) -> Self {
+                self.inner
+                    .extensions
+                    .insert($crate::common::types::RetryCount(retry_count));
+                self
+            }
+
+            /// 禁用本次请求的重试，等价于`retry_count(1)`。
+            ///
+            /// 无论客户端的全局重试设置如何，本次请求都只会发送一次。
+            pub fn no_retry(self) -> Self {
+                self.retry_count(1)
+            }
+        }
+    };
+}
+
+/// 生成写入请求体`user`字段的setter，用于带请求体的参数类型。
+macro_rules! impl_user_param {
+    ($param:ty) => {
+        impl $param {
+            /// 终端用户标识符。代表您的终端用户的唯一标识符，这可以帮助OpenAI监控和检测滥用行为。
+            pub fn user(mut self, user: impl Into<String>) -> Self {
+                self.inner
+                    .body
+                    .get_or_insert_with($crate::common::types::JsonBody::new)
+                    .insert("user".to_string(), ::serde_json::Value::String(user.into()));
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use {impl_request_options, impl_user_param};
+
+#[cfg(test)]
+mod tests {
+    use crate::common::types::{ConnectTimeout, InParam, RetryCount, Timeout};
+    use crate::{ChatParam, CompletionsParam, EmbeddingsParam, ModelsParam, user};
+    use http::HeaderValue;
+    use http::header::USER_AGENT;
+    use std::time::Duration;
+
+    macro_rules! with_request_options {
+        ($param:expr) => {
+            $param
+                .timeout(Duration::from_secs(30))
+                .connect_timeout(Duration::from_secs(5))
+                .user_agent(HeaderValue::from_static("agent/1.0"))
+                .header("x-trace-id", HeaderValue::from_static("trace-1"))
+                .retry_count(3)
+                .take()
+        };
+    }
+
+    fn request_options(inner: &InParam) -> (Duration, Duration, usize, String, String) {
+        (
+            inner.extensions.get::<Timeout>().unwrap().0,
+            inner.extensions.get::<ConnectTimeout>().unwrap().0,
+            inner.extensions.get::<RetryCount>().unwrap().0,
+            inner.headers[USER_AGENT].to_str().unwrap().to_string(),
+            inner.headers["x-trace-id"].to_str().unwrap().to_string(),
+        )
+    }
+
+    #[test]
+    fn test_request_options_are_identical_across_params() {
+        let messages = vec![user!("hi")];
+        let params = [
+            with_request_options!(ChatParam::new("gpt-4o", &messages)),
+            with_request_options!(CompletionsParam::new("gpt-3.5-turbo-instruct", "hi")),
+            with_request_options!(EmbeddingsParam::new("text-embedding-3-small", "hi")),
+            with_request_options!(ModelsParam::new()),
+        ];
+
+        let expected = (
+            Duration::from_secs(30),
+            Duration::from_secs(5),
+            3,
+            "agent/1.0".to_string(),
+            "trace-1".to_string(),
+        );
+        for inner in &params {
+            assert_eq!(request_options(inner), expected);
+        }
+    }
+
+    #[test]
+    fn test_user_is_identical_across_params() {
+        let messages = vec![user!("hi")];
+        let owned = "user-1".to_string();
+        let users = [
+            ChatParam::new("gpt-4o", &messages)
+                .user(owned.clone())
+                .take(),
+            CompletionsParam::new("gpt-3.5-turbo-instruct", "hi")
+                .user("user-1")
+                .take(),
+            EmbeddingsParam::new("text-embedding-3-small", "hi")
+                .user(&owned)
+                .take(),
+        ];
+
+        for inner in users {
+            assert_eq!(inner.body.unwrap()["user"], serde_json::json!("user-1"));
+        }
+    }
+}
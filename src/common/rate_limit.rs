@@ -0,0 +1,217 @@
+use crate::utils::runtime::Instant;
+use http::HeaderMap;
+use std::time::Duration;
+
+/// 从`x-ratelimit-*`响应头解析出的速率限制信息。
+///
+/// 每个字段对应一个响应头，服务端未返回或无法解析时为`None`：
+///
+/// | 字段 | 响应头 |
+/// | --- | --- |
+/// | `limit_requests` | `x-ratelimit-limit-requests` |
+/// | `remaining_requests` | `x-ratelimit-remaining-requests` |
+/// | `reset_requests` | `x-ratelimit-reset-requests` |
+/// | `limit_tokens` | `x-ratelimit-limit-tokens` |
+/// | `remaining_tokens` | `x-ratelimit-remaining-tokens` |
+/// | `reset_tokens` | `x-ratelimit-reset-tokens` |
+///
+/// 重置时间使用OpenAI的时长格式（例如`6m0s`、`1.5s`、`20ms`），表示距离配额恢复的剩余时长。
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use openai4rs::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = OpenAI::from_env()?;
+///     let messages = vec![user!("Hello")];
+///     let response = client
+///         .chat()
+///         .create_with_response(ChatParam::new("gpt-4o-mini", &messages))
+///         .await?;
+///
+///     if let Some(rate_limit) = response.rate_limit()
+///         && rate_limit.remaining_requests == Some(0)
+///     {
+///         let wait = rate_limit.reset_requests.unwrap_or_default();
+///         tokio::time::sleep(wait).await;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit_requests: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    pub reset_requests: Option<Duration>,
+    pub limit_tokens: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    pub reset_tokens: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// 从响应头解析速率限制信息，不包含任何可解析的`x-ratelimit-*`响应头时返回`None`。
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let count = |name: &str| header(name).and_then(|value| value.trim().parse().ok());
+        let reset = |name: &str| header(name).and_then(parse_reset_duration);
+
+        let info = Self {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        };
+        (info != Self::default()).then_some(info)
+    }
+}
+
+/// 带有接收时刻的速率限制信息快照。
+///
+/// [`RateLimitInfo`]中的重置时长相对于收到响应的时刻，
+/// 结合[`RateLimitSnapshot::age`]可以判断快照是否已经过时，或估算距离配额恢复的剩余时间。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitSnapshot {
+    /// 响应头中的速率限制信息
+    pub info: RateLimitInfo,
+    received_at: Instant,
+}
+
+impl RateLimitSnapshot {
+    /// 以当前时刻作为接收时刻创建快照。
+    pub(crate) fn new(info: RateLimitInfo) -> Self {
+        Self {
+            info,
+            received_at: Instant::now(),
+        }
+    }
+
+    /// 收到该响应的时刻。
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// 距离收到该响应已经过去的时长。
+    pub fn age(&self) -> Duration {
+        self.received_at.elapsed()
+    }
+}
+
+/// 解析OpenAI格式的时长，例如`6m0s`、`1h30m`、`1.5s`、`20ms`。
+///
+/// 由若干“数值+单位”组成，单位支持`h`、`m`、`s`、`ms`、`us`（`µs`）与`ns`；
+/// 不带单位的数值按秒处理。格式无效时返回`None`。
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds_per_unit = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 1e-3,
+            "us" | "µs" => 1e-6,
+            "ns" => 1e-9,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+
+        total += Duration::try_from_secs_f64(number * seconds_per_unit).ok()?;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn test_parse_reset_duration() {
+        assert_eq!(parse_reset_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(
+            parse_reset_duration("1h2m3s"),
+            Some(Duration::from_secs(3723))
+        );
+        assert_eq!(
+            parse_reset_duration("1.5s"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse_reset_duration("20ms"),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(parse_reset_duration("17"), Some(Duration::from_secs(17)));
+        assert_eq!(parse_reset_duration(""), None);
+        assert_eq!(parse_reset_duration("6x"), None);
+        assert_eq!(parse_reset_duration("soon"), None);
+        assert_eq!(parse_reset_duration("-1s"), None);
+    }
+
+    #[test]
+    fn test_rate_limit_from_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-ratelimit-limit-requests", "60"),
+            ("x-ratelimit-remaining-requests", "59"),
+            ("x-ratelimit-reset-requests", "1s"),
+            ("x-ratelimit-limit-tokens", "150000"),
+            ("x-ratelimit-remaining-tokens", "149984"),
+            ("x-ratelimit-reset-tokens", "6m0s"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        assert_eq!(
+            RateLimitInfo::from_headers(&headers),
+            Some(RateLimitInfo {
+                limit_requests: Some(60),
+                remaining_requests: Some(59),
+                reset_requests: Some(Duration::from_secs(1)),
+                limit_tokens: Some(150000),
+                remaining_tokens: Some(149984),
+                reset_tokens: Some(Duration::from_secs(360)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_from_partial_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            HeaderValue::from_static("42"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-tokens",
+            HeaderValue::from_static("invalid"),
+        );
+
+        let info = RateLimitInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.remaining_tokens, Some(42));
+        assert_eq!(info.reset_tokens, None);
+        assert_eq!(info.limit_requests, None);
+
+        assert_eq!(RateLimitInfo::from_headers(&HeaderMap::new()), None);
+    }
+}
@@ -1,4 +1,6 @@
-use http::{Extensions, HeaderMap};
+use super::rate_limit::RateLimitInfo;
+use crate::error::RequestError;
+use http::{Extensions, HeaderMap, StatusCode};
 use serde::{Deserialize, Serialize, de::MapAccess};
 use std::collections::HashMap;
 use std::fmt;
@@ -17,13 +19,114 @@ pub struct CompletionGeneric<T> {
     pub extra_fields: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct CompletionUsage {
     pub completion_tokens: i64,
     pub prompt_tokens: i64,
     pub total_tokens: i64,
     pub completion_tokens_details: Option<CompletionTokensDetails>,
     pub prompt_tokens_details: Option<PromptTokensDetails>,
+    pub extra_fields: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl CompletionUsage {
+    /// 推理模型用于推理的令牌数（`completion_tokens_details.reasoning_tokens`）。
+    pub fn reasoning_tokens(&self) -> Option<i64> {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.reasoning_tokens)
+    }
+
+    /// 命中提示缓存的令牌数（`prompt_tokens_details.cached_tokens`）。
+    pub fn cached_tokens(&self) -> Option<i64> {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.cached_tokens)
+    }
+
+    /// 令牌数全部为0。部分服务会在流式响应的每个数据块中携带这样的占位`usage`。
+    pub(crate) fn is_empty(&self) -> bool {
+        self.prompt_tokens == 0 && self.completion_tokens == 0 && self.total_tokens == 0
+    }
+}
+
+/// 在多次请求之间累计令牌使用统计，便于统计整个多轮对话的开销。
+///
+/// 对于非流式响应，使用[`record`](Self::record)记录每次返回的补全；对于流式响应，
+/// 在流结束后将[`StreamUsage`](crate::StreamUsage)中的`usage`传给[`add`](Self::add)，
+/// 避免逐块记录导致重复计数。
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use openai4rs::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = OpenAI::from_env()?;
+///     let mut messages = vec![user!("hello")];
+///     let mut tracker = UsageTracker::new();
+///
+///     let completion = client
+///         .chat()
+///         .create(ChatParam::new("gpt-4o", &messages))
+///         .await?;
+///     tracker.record(&completion);
+///     messages.push(completion.choices[0].message.clone().into());
+///
+///     println!("total tokens so far: {}", tracker.total_tokens());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UsageTracker {
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    total_tokens: i64,
+    requests: usize,
+}
+
+impl UsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 累加一次请求的令牌使用统计。
+    pub fn add(&mut self, usage: &CompletionUsage) {
+        self.prompt_tokens += usage.prompt_tokens;
+        self.completion_tokens += usage.completion_tokens;
+        self.total_tokens += usage.total_tokens;
+        self.requests += 1;
+    }
+
+    /// 累加响应中的`usage`，响应不包含`usage`时忽略。
+    pub fn record<T>(&mut self, completion: &CompletionGeneric<T>) {
+        if let Some(usage) = &completion.usage {
+            self.add(usage);
+        }
+    }
+
+    pub fn prompt_tokens(&self) -> i64 {
+        self.prompt_tokens
+    }
+
+    pub fn completion_tokens(&self) -> i64 {
+        self.completion_tokens
+    }
+
+    pub fn total_tokens(&self) -> i64 {
+        self.total_tokens
+    }
+
+    /// 已累加的请求数量。
+    pub fn requests(&self) -> usize {
+        self.requests
+    }
+
+    /// 清空累计的统计。
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -47,17 +150,125 @@ pub enum ServiceTier {
     Default,
 }
 
+/// 采样温度，取值范围为`0.0..=2.0`。
+///
+/// 使用[`Temperature::try_new`]构造时会在本地检查取值范围。参数设置方法同时接受`f32`，
+/// 直接转换不做检查，超出范围的值由[`ChatParam::validate`](crate::ChatParam::validate)在发送前拒绝。
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Temperature(f32);
+
+impl Temperature {
+    pub const MIN: f32 = 0.0;
+    pub const MAX: f32 = 2.0;
+
+    /// 创建采样温度，超出`0.0..=2.0`（包括`NaN`）时返回[`RequestError::Validation`]。
+    pub fn try_new(value: f32) -> Result<Self, RequestError> {
+        check_range("temperature", value, Self::MIN, Self::MAX).map(Self)
+    }
+
+    #[inline]
+    pub fn get(self) -> f32 {
+        self.0
+    }
+}
+
+impl From<f32> for Temperature {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
+
+/// 频率惩罚或存在惩罚，取值范围为`-2.0..=2.0`。
+///
+/// 与[`Temperature`]相同，[`Penalty::try_new`]会在本地检查取值范围，直接从`f32`转换则不做检查。
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Penalty(f32);
+
+impl Penalty {
+    pub const MIN: f32 = -2.0;
+    pub const MAX: f32 = 2.0;
+
+    /// 创建惩罚值，超出`-2.0..=2.0`（包括`NaN`）时返回[`RequestError::Validation`]。
+    pub fn try_new(value: f32) -> Result<Self, RequestError> {
+        check_range("penalty", value, Self::MIN, Self::MAX).map(Self)
+    }
+
+    #[inline]
+    pub fn get(self) -> f32 {
+        self.0
+    }
+}
+
+impl From<f32> for Penalty {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
+
+/// 解析后的响应体及其HTTP元数据。
+///
+/// 由`create_with_response`等方法返回，便于读取速率限制（`x-ratelimit-*`）、
+/// 请求ID（`x-request-id`）等只出现在响应头中的信息。
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: T,
+}
+
+impl<T> ApiResponse<T> {
+    /// 返回指定响应头的值，不存在或不是有效的可见ASCII字符串时返回`None`。
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// 服务端分配的请求ID（`x-request-id`响应头），反馈问题时可提供给服务商。
+    pub fn request_id(&self) -> Option<&str> {
+        self.header("x-request-id")
+    }
+
+    /// 从`x-ratelimit-*`响应头解析速率限制信息，参见[`RateLimitInfo`]。
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        RateLimitInfo::from_headers(&self.headers)
+    }
+
+    /// 取出响应体，丢弃HTTP元数据。
+    pub fn into_body(self) -> T {
+        self.body
+    }
+}
+
+fn check_range(name: &str, value: f32, min: f32, max: f32) -> Result<f32, RequestError> {
+    if (min..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(RequestError::validation(
+            name,
+            format!("a value between {min} and {max}"),
+            value,
+        ))
+    }
+}
+
 pub(crate) type JsonBody = serde_json::Map<String, serde_json::Value>;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Timeout(pub std::time::Duration);
 
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectTimeout(pub std::time::Duration);
+
 #[derive(Debug, Clone)]
 pub(crate) struct RetryCount(pub usize);
 
+#[derive(Debug, Clone)]
+pub(crate) struct BaseUrl(pub String);
+
+#[derive(Clone)]
 pub(crate) struct InParam {
     pub body: Option<JsonBody>,
     pub headers: HeaderMap,
+    pub query: Vec<(String, String)>,
     pub extensions: Extensions,
 }
 
@@ -66,6 +277,7 @@ impl InParam {
         Self {
             body: None,
             headers: HeaderMap::new(),
+            query: Vec::new(),
             extensions: Extensions::new(),
         }
     }
@@ -171,7 +383,7 @@ where
                             if usage.is_some() {
                                 return Err(serde::de::Error::duplicate_field("usage"));
                             }
-                            usage = Some(map.next_value()?);
+                            usage = map.next_value()?;
                         }
                         _ => {
                             let value = map.next_value()?;
@@ -211,3 +423,169 @@ where
         })
     }
 }
+
+impl<'de> serde::Deserialize<'de> for CompletionUsage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct CompletionUsageVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CompletionUsageVisitor {
+            type Value = CompletionUsage;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct CompletionUsage")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut completion_tokens = None;
+                let mut prompt_tokens = None;
+                let mut total_tokens = None;
+                let mut completion_tokens_details = None;
+                let mut prompt_tokens_details = None;
+                let mut extra_fields = HashMap::new();
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "completion_tokens" => {
+                            if completion_tokens.is_some() {
+                                return Err(serde::de::Error::duplicate_field("completion_tokens"));
+                            }
+                            completion_tokens = Some(map.next_value()?);
+                        }
+                        "prompt_tokens" => {
+                            if prompt_tokens.is_some() {
+                                return Err(serde::de::Error::duplicate_field("prompt_tokens"));
+                            }
+                            prompt_tokens = Some(map.next_value()?);
+                        }
+                        "total_tokens" => {
+                            if total_tokens.is_some() {
+                                return Err(serde::de::Error::duplicate_field("total_tokens"));
+                            }
+                            total_tokens = Some(map.next_value()?);
+                        }
+                        "completion_tokens_details" => {
+                            if completion_tokens_details.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "completion_tokens_details",
+                                ));
+                            }
+                            completion_tokens_details = Some(try_deserialize_or_skip(&mut map)?);
+                        }
+                        "prompt_tokens_details" => {
+                            if prompt_tokens_details.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "prompt_tokens_details",
+                                ));
+                            }
+                            prompt_tokens_details = Some(try_deserialize_or_skip(&mut map)?);
+                        }
+                        other => {
+                            let value: serde_json::Value = map.next_value()?;
+                            extra_fields.insert(other.to_string(), value);
+                        }
+                    }
+                }
+
+                let completion_tokens = completion_tokens
+                    .ok_or_else(|| serde::de::Error::missing_field("completion_tokens"))?;
+                let prompt_tokens = prompt_tokens
+                    .ok_or_else(|| serde::de::Error::missing_field("prompt_tokens"))?;
+                let total_tokens =
+                    total_tokens.ok_or_else(|| serde::de::Error::missing_field("total_tokens"))?;
+                let extra_fields = if extra_fields.is_empty() {
+                    None
+                } else {
+                    Some(extra_fields)
+                };
+
+                Ok(CompletionUsage {
+                    completion_tokens,
+                    prompt_tokens,
+                    total_tokens,
+                    completion_tokens_details: completion_tokens_details.flatten(),
+                    prompt_tokens_details: prompt_tokens_details.flatten(),
+                    extra_fields,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(CompletionUsageVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChatCompletion, ChatCompletionChunk, StreamUsage};
+
+    #[test]
+    fn test_usage_tracker_accumulates() {
+        let mut tracker = UsageTracker::new();
+        tracker.record(&ChatCompletion::mock("Hello").with_usage(10, 5));
+        tracker.record(&ChatCompletion::mock("World").with_usage(20, 7));
+        tracker.record(&ChatCompletion::mock("No usage"));
+
+        let mut stream_usage = StreamUsage::new();
+        stream_usage.record(&ChatCompletionChunk::mock("Hi"));
+        stream_usage.record(&ChatCompletionChunk::mock_usage(30, 3));
+        tracker.add(stream_usage.usage().unwrap());
+
+        assert_eq!(tracker.prompt_tokens(), 60);
+        assert_eq!(tracker.completion_tokens(), 15);
+        assert_eq!(tracker.total_tokens(), 75);
+        assert_eq!(tracker.requests(), 3);
+
+        tracker.reset();
+        assert_eq!(tracker.total_tokens(), 0);
+        assert_eq!(tracker.requests(), 0);
+    }
+
+    #[test]
+    fn test_temperature_range() {
+        assert_eq!(Temperature::try_new(0.0).unwrap().get(), 0.0);
+        assert_eq!(Temperature::try_new(2.0).unwrap().get(), 2.0);
+        assert!(Temperature::try_new(-0.01).is_err());
+        assert!(Temperature::try_new(2.01).is_err());
+        assert!(Temperature::try_new(f32::NAN).is_err());
+
+        let error = Temperature::try_new(3.0).unwrap_err();
+        assert!(matches!(error, RequestError::Validation { .. }));
+        assert!(
+            error
+                .to_string()
+                .contains("Invalid value for `temperature`: expected a value between 0 and 2")
+        );
+    }
+
+    #[test]
+    fn test_penalty_range() {
+        assert_eq!(Penalty::try_new(-2.0).unwrap().get(), -2.0);
+        assert_eq!(Penalty::try_new(2.0).unwrap().get(), 2.0);
+        assert!(Penalty::try_new(-2.01).is_err());
+        assert!(Penalty::try_new(2.01).is_err());
+        assert!(Penalty::try_new(f32::INFINITY).is_err());
+        assert!(Penalty::try_new(f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_sampling_setters_accept_newtypes_and_f32() {
+        let messages = vec![crate::user!("hello")];
+        let param = crate::ChatParam::new("gpt-4o", &messages)
+            .temperature(Temperature::try_new(0.5).unwrap())
+            .frequency_penalty(Penalty::try_new(-1.5).unwrap())
+            .presence_penalty(1.0);
+
+        assert_eq!(param.get("temperature"), Some(&serde_json::json!(0.5)));
+        assert_eq!(
+            param.get("frequency_penalty"),
+            Some(&serde_json::json!(-1.5))
+        );
+        assert_eq!(param.get("presence_penalty"), Some(&serde_json::json!(1.0)));
+    }
+}
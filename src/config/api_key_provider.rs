@@ -0,0 +1,112 @@
+use crate::error::OpenAIError;
+use crate::utils::runtime::Instant;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+type ApiKeyFuture = Pin<Box<dyn Future<Output = Result<String, OpenAIError>> + Send>>;
+type FetchFn = dyn Fn() -> ApiKeyFuture + Send + Sync;
+
+/// 动态获取API密钥的异步回调。
+///
+/// 适用于令牌会过期的网关（如OAuth或STS）。每次发送请求前以及每次重试前都会通过提供者获取密钥，
+/// 获取到的密钥会被缓存`ttl`时长，过期后再次调用回调刷新。并发请求共享同一次刷新。
+///
+/// 设置提供者后，[`Config::with_api_key`](super::Config::with_api_key)设置的静态密钥将被忽略，
+/// 直到调用[`Config::clear_api_key_provider`](super::Config::clear_api_key_provider)。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::{ApiKeyProvider, Config};
+/// use std::time::Duration;
+///
+/// let provider = ApiKeyProvider::new(Duration::from_secs(300), || async {
+///     // 从认证服务获取新的令牌
+///     Ok("sk-refreshed".to_string())
+/// });
+///
+/// let config = Config::builder()
+///     .base_url("https://gateway.example.com/v1")
+///     .api_key_provider(provider)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct ApiKeyProvider {
+    inner: Arc<ApiKeyProviderInner>,
+}
+
+struct ApiKeyProviderInner {
+    fetch: Box<FetchFn>,
+    ttl: Duration,
+    cache: Mutex<Option<(String, Instant)>>,
+}
+
+impl ApiKeyProvider {
+    /// 创建一个新的提供者，获取到的密钥在`ttl`时长内有效。
+    pub fn new<F, Fut>(ttl: Duration, fetch: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, OpenAIError>> + Send + 'static,
+    {
+        Self {
+            inner: Arc::new(ApiKeyProviderInner {
+                fetch: Box::new(move || Box::pin(fetch())),
+                ttl,
+                cache: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// 创建一个不缓存密钥的提供者，每次请求（包括重试）都会调用回调。
+    ///
+    /// 适用于外部服务已经负责令牌刷新与缓存的场景，等价于`ApiKeyProvider::new(Duration::ZERO, fetch)`。
+    pub fn uncached<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, OpenAIError>> + Send + 'static,
+    {
+        Self::new(Duration::ZERO, fetch)
+    }
+
+    #[inline]
+    pub fn ttl(&self) -> Duration {
+        self.inner.ttl
+    }
+
+    /// 返回缓存的密钥，如果缓存为空或已过期则调用回调刷新。
+    ///
+    /// # 错误
+    ///
+    /// 返回回调产生的错误，此时缓存保持为空。
+    pub async fn api_key(&self) -> Result<String, OpenAIError> {
+        let mut cache = self.inner.cache.lock().await;
+        if let Some((key, fetched_at)) = cache.as_ref()
+            && fetched_at.elapsed() < self.inner.ttl
+        {
+            return Ok(key.clone());
+        }
+
+        *cache = None;
+        let key = (self.inner.fetch)().await?;
+        *cache = Some((key.clone(), Instant::now()));
+        Ok(key)
+    }
+
+    /// 清除缓存的密钥，下一次请求将重新调用回调。
+    pub async fn invalidate(&self) {
+        *self.inner.cache.lock().await = None;
+    }
+}
+
+impl fmt::Debug for ApiKeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyProvider")
+            .field("ttl", &self.inner.ttl)
+            .finish_non_exhaustive()
+    }
+}
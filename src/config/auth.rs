@@ -0,0 +1,32 @@
+use http::HeaderName;
+
+/// API密钥在请求中的携带方式。
+///
+/// 大多数OpenAI兼容服务使用`Authorization: Bearer {api_key}`，但部分服务需要不同的认证头。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::{AuthScheme, Config, HeaderName};
+///
+/// let config = Config::builder()
+///     .api_key("sk-...")
+///     .base_url("https://api.example.com/v1")
+///     .auth_scheme(AuthScheme::Header {
+///         name: HeaderName::from_static("x-api-key"),
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer {api_key}`（默认）
+    #[default]
+    Bearer,
+    /// `Authorization: {api_key}`，不带任何前缀
+    Raw,
+    /// `{name}: {api_key}`，例如Azure OpenAI使用的`api-key`
+    Header { name: HeaderName },
+    /// 不发送任何认证头
+    None,
+}
@@ -0,0 +1,33 @@
+/// Azure OpenAI 服务的连接配置。
+///
+/// Azure OpenAI 使用与 OpenAI 不同的URL结构和认证方式：
+/// - 部署级端点为 `{endpoint}/openai/deployments/{deployment}/chat/completions?api-version={api_version}`
+/// - 使用 `api-key` 请求头而不是 `Authorization: Bearer`
+///
+/// 其中 `endpoint` 对应 [`Config`](super::Config) 的基础URL。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureConfig {
+    /// 模型部署名称
+    deployment: String,
+    /// API版本，例如 "2024-10-21"
+    api_version: String,
+}
+
+impl AzureConfig {
+    pub fn new<D: Into<String>, V: Into<String>>(deployment: D, api_version: V) -> Self {
+        Self {
+            deployment: deployment.into(),
+            api_version: api_version.into(),
+        }
+    }
+
+    #[inline]
+    pub fn deployment(&self) -> &str {
+        &self.deployment
+    }
+
+    #[inline]
+    pub fn api_version(&self) -> &str {
+        &self.api_version
+    }
+}
@@ -1,13 +1,28 @@
 use super::http::{HttpConfig, HttpConfigBuilder};
-use super::{Credentials, CredentialsBuilder};
+use super::{
+    ApiKeyProvider, AuthScheme, AzureConfig, Credentials, CredentialsBuilder, ExponentialBackoff,
+    RetryCallback, RetryPolicy,
+};
 use crate::OpenAI;
 use crate::common::types::JsonBody;
 use crate::config::CredentialsBuilderError;
+use crate::error::OpenAIError;
+use crate::service::interceptor::{Interceptor, InterceptorChain};
+use crate::service::transport::Transport;
 use http::header::IntoHeaderName;
-use http::{HeaderMap, HeaderValue};
+use http::{HeaderMap, HeaderName, HeaderValue};
 use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// 流式响应中收到保活事件时调用的回调。
+///
+/// 参数为事件类型（例如`ping`），未命名的空事件为`message`。回调在读取流的后台任务中同步调用，
+/// 可用于监控长时间无数据的连接是否仍然存活，不应执行耗时操作。
+///
+/// 以`:`开头的SSE注释行在解析阶段即被丢弃，不会触发此回调。
+pub type KeepaliveCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
 #[derive(Debug)]
 pub enum ConfigBuildError {
     /// 必需字段缺失错误
@@ -52,6 +67,26 @@ pub struct Config {
     http: HttpConfig,
     /// 失败请求的重试次数
     retry_count: usize,
+    /// 重试策略
+    retry_policy: Arc<dyn RetryPolicy>,
+    /// Azure OpenAI 配置，设置后将使用Azure的URL结构
+    azure: Option<AzureConfig>,
+    /// API密钥在请求中的携带方式
+    auth_scheme: AuthScheme,
+    /// 动态API密钥提供者，设置后优先于静态API密钥
+    api_key_provider: Option<ApiKeyProvider>,
+    /// 请求拦截器链
+    interceptors: InterceptorChain,
+    /// 每次重试前调用的回调
+    on_retry: Option<RetryCallback>,
+    /// 流式响应中收到保活事件时调用的回调
+    on_keepalive: Option<KeepaliveCallback>,
+    /// 是否在`tracing`日志中记录请求体与响应体
+    log_bodies: bool,
+    /// 自定义传输层，未设置时使用内部的reqwest客户端
+    transport: Option<Arc<dyn Transport>>,
+    /// 外部构建的reqwest客户端，设置后不再根据HTTP配置构建客户端
+    http_client: Option<reqwest::Client>,
 }
 impl Config {
     pub fn new(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
@@ -59,12 +94,32 @@ impl Config {
             credentials: Credentials::new(api_key.into(), base_url.into()),
             http: HttpConfig::default(),
             retry_count: 5,
+            retry_policy: Arc::new(ExponentialBackoff::default()),
+            azure: None,
+            auth_scheme: AuthScheme::default(),
+            api_key_provider: None,
+            interceptors: InterceptorChain::new(),
+            on_retry: None,
+            on_keepalive: None,
+            log_bodies: false,
+            transport: None,
+            http_client: None,
         }
     }
 
     pub fn builder() -> ConfigBuilder {
         ConfigBuilder {
             retry_count: 5,
+            retry_policy: Arc::new(ExponentialBackoff::default()),
+            azure: None,
+            auth_scheme: AuthScheme::default(),
+            api_key_provider: None,
+            interceptors: InterceptorChain::new(),
+            on_retry: None,
+            on_keepalive: None,
+            log_bodies: false,
+            transport: None,
+            http_client: None,
             credentials_builder: CredentialsBuilder::default(),
             http_builder: HttpConfigBuilder::default(),
         }
@@ -85,6 +140,11 @@ impl Config {
         self.retry_count
     }
 
+    #[inline]
+    pub fn retry_policy(&self) -> &Arc<dyn RetryPolicy> {
+        &self.retry_policy
+    }
+
     #[inline]
     pub fn timeout(&self) -> Duration {
         self.http.timeout()
@@ -115,21 +175,192 @@ impl Config {
         &self.credentials
     }
 
+    #[inline]
+    pub fn azure(&self) -> Option<&AzureConfig> {
+        self.azure.as_ref()
+    }
+
+    #[inline]
+    pub fn auth_scheme(&self) -> &AuthScheme {
+        &self.auth_scheme
+    }
+
+    #[inline]
+    pub fn api_key_provider(&self) -> Option<&ApiKeyProvider> {
+        self.api_key_provider.as_ref()
+    }
+
+    #[inline]
+    pub fn interceptors(&self) -> &InterceptorChain {
+        &self.interceptors
+    }
+
+    #[inline]
+    pub fn on_retry(&self) -> Option<&RetryCallback> {
+        self.on_retry.as_ref()
+    }
+
+    #[inline]
+    pub fn on_keepalive(&self) -> Option<&KeepaliveCallback> {
+        self.on_keepalive.as_ref()
+    }
+
+    #[inline]
+    pub fn log_bodies(&self) -> bool {
+        self.log_bodies
+    }
+
+    #[inline]
+    pub fn transport(&self) -> Option<&Arc<dyn Transport>> {
+        self.transport.as_ref()
+    }
+
+    #[inline]
+    pub fn http_client(&self) -> Option<&reqwest::Client> {
+        self.http_client.as_ref()
+    }
+
+    /// 构建部署级API端点（聊天、补全、嵌入）的完整URL。
+    ///
+    /// 对于Azure OpenAI，返回 `{base_url}/openai/deployments/{deployment}{path}?api-version={api_version}`，
+    /// 否则返回 `{base_url}{path}`。
+    pub fn endpoint_url(&self, path: &str) -> String {
+        self.endpoint_url_with_base(self.base_url(), path)
+    }
+
+    /// 与[`Config::endpoint_url`]相同，但使用指定的`base_url`代替配置中的基础URL。
+    pub fn endpoint_url_with_base(&self, base_url: &str, path: &str) -> String {
+        match &self.azure {
+            Some(azure) => format!(
+                "{}/openai/deployments/{}{}?api-version={}",
+                base_url,
+                azure.deployment(),
+                path,
+                azure.api_version()
+            ),
+            None => format!("{base_url}{path}"),
+        }
+    }
+
+    /// 构建资源级API端点（如模型列表）的完整URL。
+    ///
+    /// 对于Azure OpenAI，返回 `{base_url}/openai{path}?api-version={api_version}`，
+    /// 否则返回 `{base_url}{path}`。
+    pub fn resource_url(&self, path: &str) -> String {
+        match &self.azure {
+            Some(azure) => format!(
+                "{}/openai{}?api-version={}",
+                self.base_url(),
+                path,
+                azure.api_version()
+            ),
+            None => format!("{}{}", self.base_url(), path),
+        }
+    }
+
     pub fn with_base_url<T: Into<String>>(&mut self, base_url: T) -> &mut Self {
         self.credentials.with_base_url(base_url);
         self
     }
 
+    /// 设置静态API密钥。
+    ///
+    /// 如果设置了[`ApiKeyProvider`]，请求将使用提供者返回的密钥，此处设置的密钥不会生效。
     pub fn with_api_key<T: Into<String>>(&mut self, api_key: T) -> &mut Self {
         self.credentials.with_api_key(api_key);
         self
     }
 
+    /// 设置动态API密钥提供者，每次请求前都会通过它获取密钥。
+    pub fn with_api_key_provider(&mut self, provider: ApiKeyProvider) -> &mut Self {
+        self.api_key_provider = Some(provider);
+        self
+    }
+
+    /// 在拦截器链末尾添加一个拦截器。
+    pub fn with_interceptor<I: Interceptor + 'static>(&mut self, interceptor: I) -> &mut Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// 移除动态API密钥提供者，恢复使用静态API密钥。
+    pub fn clear_api_key_provider(&mut self) -> &mut Self {
+        self.api_key_provider = None;
+        self
+    }
+
     pub fn with_retry_count(&mut self, retry_count: usize) -> &mut Self {
         self.retry_count = retry_count;
         self
     }
 
+    pub fn with_retry_policy<P: RetryPolicy + 'static>(&mut self, retry_policy: P) -> &mut Self {
+        self.retry_policy = Arc::new(retry_policy);
+        self
+    }
+
+    /// 设置每次重试前调用的回调，参见[`RetryCallback`]。
+    pub fn with_on_retry<F>(&mut self, on_retry: F) -> &mut Self
+    where
+        F: Fn(u32, u32, &OpenAIError, Duration) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(on_retry));
+        self
+    }
+
+    /// 设置流式响应中收到保活事件时调用的回调，参见[`KeepaliveCallback`]。
+    pub fn with_on_keepalive<F>(&mut self, on_keepalive: F) -> &mut Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_keepalive = Some(Arc::new(on_keepalive));
+        self
+    }
+
+    /// 启用Azure OpenAI，同时将认证方式设置为`api-key`请求头。
+    pub fn with_azure(&mut self, azure: AzureConfig) -> &mut Self {
+        self.azure = Some(azure);
+        self.auth_scheme = AuthScheme::Header {
+            name: HeaderName::from_static("api-key"),
+        };
+        self
+    }
+
+    /// 设置是否在`tracing`日志中记录请求体与响应体，参见[`ConfigBuilder::log_bodies`]。
+    pub fn with_log_bodies(&mut self, log_bodies: bool) -> &mut Self {
+        self.log_bodies = log_bodies;
+        self
+    }
+
+    /// 设置自定义传输层，参见[`Transport`]。
+    pub fn with_transport<T: Transport + 'static>(&mut self, transport: T) -> &mut Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// 移除自定义传输层，恢复使用内部的reqwest客户端。
+    pub fn clear_transport(&mut self) -> &mut Self {
+        self.transport = None;
+        self
+    }
+
+    /// 使用外部构建的reqwest客户端发送请求，参见[`ConfigBuilder::http_client`]。
+    pub fn with_http_client(&mut self, http_client: reqwest::Client) -> &mut Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// 移除外部构建的reqwest客户端，恢复根据HTTP配置构建客户端。
+    pub fn clear_http_client(&mut self) -> &mut Self {
+        self.http_client = None;
+        self
+    }
+
+    pub fn with_auth_scheme(&mut self, auth_scheme: AuthScheme) -> &mut Self {
+        self.auth_scheme = auth_scheme;
+        self
+    }
+
     pub fn with_timeout(&mut self, timeout: Duration) -> &mut Self {
         self.http.with_timeout(timeout);
         self
@@ -155,6 +386,26 @@ impl Config {
 pub struct ConfigBuilder {
     /// 失败请求的重试次数
     retry_count: usize,
+    /// 重试策略
+    retry_policy: Arc<dyn RetryPolicy>,
+    /// Azure OpenAI 配置
+    azure: Option<AzureConfig>,
+    /// API密钥在请求中的携带方式
+    auth_scheme: AuthScheme,
+    /// 动态API密钥提供者
+    api_key_provider: Option<ApiKeyProvider>,
+    /// 请求拦截器链
+    interceptors: InterceptorChain,
+    /// 每次重试前调用的回调
+    on_retry: Option<RetryCallback>,
+    /// 收到保活事件时调用的回调
+    on_keepalive: Option<KeepaliveCallback>,
+    /// 是否记录请求体与响应体
+    log_bodies: bool,
+    /// 自定义传输层
+    transport: Option<Arc<dyn Transport>>,
+    /// 外部构建的reqwest客户端
+    http_client: Option<reqwest::Client>,
     /// BaseConfig的构建器
     credentials_builder: CredentialsBuilder,
     /// HttpConfig的构建器
@@ -168,10 +419,25 @@ impl ConfigBuilder {
     ///
     /// 包含Config实例或ConfigBuildError的Result
     pub fn build(self) -> Result<Config, ConfigBuildError> {
+        let mut credentials = self.credentials_builder.clone().build();
+        if credentials.is_err() && self.api_key_provider.is_some() {
+            // 使用动态密钥时不会读取静态密钥，填充一个空值以通过必需字段检查
+            credentials = self.credentials_builder.api_key(String::new()).build();
+        }
         Ok(Config {
-            credentials: self.credentials_builder.build()?,
+            credentials: credentials?,
             http: self.http_builder.build()?,
             retry_count: self.retry_count,
+            retry_policy: self.retry_policy,
+            azure: self.azure,
+            auth_scheme: self.auth_scheme,
+            api_key_provider: self.api_key_provider,
+            interceptors: self.interceptors,
+            on_retry: self.on_retry,
+            on_keepalive: self.on_keepalive,
+            log_bodies: self.log_bodies,
+            transport: self.transport,
+            http_client: self.http_client,
         })
     }
 
@@ -212,6 +478,83 @@ impl ConfigBuilder {
         self
     }
 
+    /// 将客户端配置为使用Azure OpenAI服务。
+    ///
+    /// 请求将发送到 `{endpoint}/openai/deployments/{deployment}/...?api-version={api_version}`，
+    /// 并使用 `api-key` 请求头进行认证。
+    ///
+    /// # 参数
+    ///
+    /// * `endpoint` - Azure资源端点，例如 "https://my-resource.openai.azure.com"
+    /// * `deployment` - 模型部署名称
+    /// * `api_version` - API版本，例如 "2024-10-21"
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn azure<E: Into<String>, D: Into<String>, V: Into<String>>(
+        mut self,
+        endpoint: E,
+        deployment: D,
+        api_version: V,
+    ) -> Self {
+        let endpoint: String = endpoint.into();
+        self.credentials_builder = self
+            .credentials_builder
+            .base_url(endpoint.trim_end_matches('/').to_string());
+        self.azure = Some(AzureConfig::new(deployment, api_version));
+        self.auth_scheme = AuthScheme::Header {
+            name: HeaderName::from_static("api-key"),
+        };
+        self
+    }
+
+    /// 设置API密钥在请求中的携带方式，默认为[`AuthScheme::Bearer`]
+    ///
+    /// # 参数
+    ///
+    /// * `auth_scheme` - 认证方式
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn auth_scheme(mut self, auth_scheme: AuthScheme) -> Self {
+        self.auth_scheme = auth_scheme;
+        self
+    }
+
+    /// 在拦截器链末尾添加一个拦截器
+    ///
+    /// 请求拦截器按添加顺序执行，响应和错误拦截器按添加的逆序执行。
+    ///
+    /// # 参数
+    ///
+    /// * `interceptor` - 要添加的拦截器
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// 设置动态API密钥提供者，每次请求前都会通过它获取密钥
+    ///
+    /// 设置提供者后`api_key`不再是必需字段，即使设置了也不会被使用。
+    ///
+    /// # 参数
+    ///
+    /// * `provider` - API密钥提供者
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn api_key_provider(mut self, provider: ApiKeyProvider) -> Self {
+        self.api_key_provider = Some(provider);
+        self
+    }
+
     /// 设置配置的重试次数
     ///
     /// # 参数
@@ -226,6 +569,115 @@ impl ConfigBuilder {
         self
     }
 
+    /// 设置重试策略，默认为[`ExponentialBackoff::default`]
+    ///
+    /// # 参数
+    ///
+    /// * `retry_policy` - 决定是否重试以及重试延迟的策略
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn retry_policy<P: RetryPolicy + 'static>(mut self, retry_policy: P) -> Self {
+        self.retry_policy = Arc::new(retry_policy);
+        self
+    }
+
+    /// 设置每次重试前调用的回调
+    ///
+    /// 回调接收刚失败的尝试次数、最大尝试次数、导致重试的错误以及下一次重试前的等待时间，
+    /// 可用于监控速率限制退避等情况。
+    ///
+    /// # 参数
+    ///
+    /// * `on_retry` - 重试回调
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn on_retry<F>(mut self, on_retry: F) -> Self
+    where
+        F: Fn(u32, u32, &OpenAIError, Duration) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(on_retry));
+        self
+    }
+
+    /// 设置流式响应中收到保活事件时调用的回调
+    ///
+    /// 部分服务在长时间生成时会发送`event: ping`等保活事件以维持连接，这些事件不会出现在流中。
+    /// 回调接收事件类型，可用于监控连接健康状况。
+    ///
+    /// # 参数
+    ///
+    /// * `on_keepalive` - 保活回调
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn on_keepalive<F>(mut self, on_keepalive: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_keepalive = Some(Arc::new(on_keepalive));
+        self
+    }
+
+    /// 设置是否在`tracing`日志中记录请求体与响应体
+    ///
+    /// 每个请求都会在`openai4rs_request`跨度内以`DEBUG`级别记录方法、URL、请求头和响应状态。
+    /// 启用此选项后还会记录JSON请求体与非流式响应体，响应体会被完整读入内存。
+    /// 携带API密钥的请求头始终会被遮蔽。
+    ///
+    /// # 参数
+    ///
+    /// * `log_bodies` - 是否记录请求体与响应体
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn log_bodies(mut self, log_bodies: bool) -> Self {
+        self.log_bodies = log_bodies;
+        self
+    }
+
+    /// 设置自定义传输层，每次尝试都会通过它发送请求
+    ///
+    /// 认证、拦截器、重试与响应解析仍照常执行，只有发送请求这一步被替换，
+    /// 适合在单元测试中配合[`MockTransport`](crate::MockTransport)使用。
+    /// 设置后`proxy`、`connect_timeout`等HTTP客户端设置不再生效。
+    ///
+    /// # 参数
+    ///
+    /// * `transport` - 自定义传输层
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// 使用外部构建的reqwest客户端发送请求，而不是根据HTTP配置构建新的客户端
+    ///
+    /// 适合复用已配置好TLS、连接池等设置的客户端。客户端级别的HTTP配置（`timeout`、`connect_timeout`、
+    /// `proxy`、连接池与TLS设置，以及请求级别的`connect_timeout`）不再生效，以传入的客户端为准；
+    /// 请求级别的`timeout`、全局请求头、认证、拦截器与重试仍照常应用到每个请求。
+    /// 同时设置了[`transport`](Self::transport)时以自定义传输层为准。
+    ///
+    /// # 参数
+    ///
+    /// * `http_client` - 已构建的reqwest客户端
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
     /// 设置配置的请求超时时间
     ///
     /// # 参数
@@ -240,6 +692,150 @@ impl ConfigBuilder {
         self
     }
 
+    /// 设置每个主机保留的最大空闲连接数，默认不限制
+    ///
+    /// # 参数
+    ///
+    /// * `max` - 最大空闲连接数
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.http_builder = self.http_builder.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// 设置空闲连接在连接池中保留的时间，默认90秒
+    ///
+    /// # 参数
+    ///
+    /// * `timeout` - 空闲超时时间
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.http_builder = self.http_builder.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// 设置是否直接使用HTTP/2（不经协商），仅在服务端确定支持HTTP/2时启用
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否启用
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http_builder = self.http_builder.http2_prior_knowledge(enabled);
+        self
+    }
+
+    /// 设置TCP keepalive探测间隔，默认15秒
+    ///
+    /// # 参数
+    ///
+    /// * `interval` - 探测间隔
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.http_builder = self.http_builder.tcp_keepalive(interval);
+        self
+    }
+
+    /// 添加一个额外信任的根证书，例如内部网关使用的私有CA，可多次调用
+    ///
+    /// 系统证书仍然有效。`wasm32`下不可用，证书由浏览器管理。
+    ///
+    /// # 参数
+    ///
+    /// * `certificate` - PEM或DER格式的证书，参见[`Certificate`](super::http::Certificate)
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, certificate: super::http::Certificate) -> Self {
+        self.http_builder = self.http_builder.add_root_certificate(certificate);
+        self
+    }
+
+    /// 设置是否接受无效的TLS证书，默认不接受
+    ///
+    /// **危险**：启用后HTTPS连接不再验证服务端身份，容易受到中间人攻击，仅应在测试环境中使用。
+    /// 信任私有CA请使用[`ConfigBuilder::add_root_certificate`]。`wasm32`下不可用。
+    ///
+    /// # 参数
+    ///
+    /// * `accept` - 是否接受无效证书
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.http_builder = self.http_builder.danger_accept_invalid_certs(accept);
+        self
+    }
+
+    /// 设置是否接受压缩的响应并自动解压，默认启用
+    ///
+    /// 需要启用crate特性`gzip`、`deflate`或`brotli`中的至少一个，
+    /// 只有已启用特性对应的算法会出现在`Accept-Encoding`请求头中。
+    ///
+    /// ```toml
+    /// openai4rs = { version = "0.1.9", features = ["gzip", "brotli"] }
+    /// ```
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否启用
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn accept_compression(mut self, enabled: bool) -> Self {
+        self.http_builder = self.http_builder.accept_compression(enabled);
+        self
+    }
+
+    /// 单独设置是否接受gzip压缩的响应，覆盖[`accept_compression`](Self::accept_compression)
+    ///
+    /// 需要启用crate特性`gzip`，否则此选项不起作用。
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否启用
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.http_builder = self.http_builder.gzip(enabled);
+        self
+    }
+
+    /// 单独设置是否接受brotli压缩的响应，覆盖[`accept_compression`](Self::accept_compression)
+    ///
+    /// 需要启用crate特性`brotli`，否则此选项不起作用。
+    ///
+    /// # 参数
+    ///
+    /// * `enabled` - 是否启用
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn brotli(mut self, enabled: bool) -> Self {
+        self.http_builder = self.http_builder.brotli(enabled);
+        self
+    }
+
     /// 设置配置的连接超时时间
     ///
     /// # 参数
@@ -297,6 +893,24 @@ impl ConfigBuilder {
         self
     }
 
+    /// 向HTTP配置添加全局查询参数，附加到每个请求的URL上。
+    ///
+    /// 适用于每个请求都需要携带的参数，例如网关要求的`api-version`。
+    /// 请求级别设置的同名参数（例如[`ChatParam::query`](crate::ChatParam::query)）优先。
+    ///
+    /// # 参数
+    ///
+    /// * `key` - 参数名
+    /// * `value` - 参数值
+    ///
+    /// # 返回
+    ///
+    /// 用于方法链的构建器实例
+    pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.http_builder = self.http_builder.query(key, value);
+        self
+    }
+
     /// 向HTTP配置添加全局主体字段。
     ///
     /// # 参数
@@ -6,6 +6,10 @@ use http::{
 };
 use std::time::Duration;
 
+/// TLS根证书，参见[`HttpConfigBuilder::add_root_certificate`]。
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::Certificate;
+
 /// 连接到API服务的HTTP客户端配置。
 ///
 /// 该结构体保存与底层HTTP传输层相关的设置，
@@ -39,12 +43,71 @@ pub struct HttpConfig {
     #[builder(default = None)]
     proxy: Option<String>,
 
+    /// 每个主机保留的最大空闲连接数。默认值：不限制
+    #[builder(default = usize::MAX)]
+    pool_max_idle_per_host: usize,
+
+    /// 空闲连接在连接池中保留的时间。默认值：90秒
+    #[builder(default = Some(Duration::from_secs(90)))]
+    pool_idle_timeout: Option<Duration>,
+
+    /// 是否直接使用HTTP/2（不经协商）。默认值：`false`
+    ///
+    /// 仅当服务端确定支持HTTP/2时才应启用，否则请求会失败。
+    #[builder(default = false)]
+    http2_prior_knowledge: bool,
+
+    /// TCP keepalive探测间隔。默认值：15秒
+    #[builder(default = Some(Duration::from_secs(15)))]
+    tcp_keepalive: Option<Duration>,
+
+    /// 是否接受压缩的响应并自动解压。默认值：`true`
+    ///
+    /// 仅对启用了的crate特性（`gzip`、`deflate`、`brotli`）对应的算法生效：
+    /// 启用时会发送相应的`Accept-Encoding`请求头，并按`Content-Encoding`解压响应体。
+    /// 未启用任何特性时此选项不起作用。
+    #[builder(default = true)]
+    accept_compression: bool,
+
+    /// 是否接受gzip压缩的响应，覆盖`accept_compression`。默认值：跟随`accept_compression`
+    ///
+    /// 需要启用crate特性`gzip`，否则此选项不起作用。
+    #[builder(default = None)]
+    gzip: Option<bool>,
+
+    /// 是否接受brotli压缩的响应，覆盖`accept_compression`。默认值：跟随`accept_compression`
+    ///
+    /// 需要启用crate特性`brotli`，否则此选项不起作用。
+    #[builder(default = None)]
+    brotli: Option<bool>,
+
+    /// 额外信任的根证书，例如内部网关使用的私有CA。默认值：空
+    ///
+    /// 通过[`HttpConfigBuilder::add_root_certificate`]添加，系统证书仍然有效。
+    #[cfg(not(target_arch = "wasm32"))]
+    #[builder(default, setter(custom))]
+    root_certificates: Vec<Certificate>,
+
+    /// 是否接受无效的TLS证书（包括过期、自签名或主机名不匹配的证书）。默认值：`false`
+    ///
+    /// **危险**：启用后HTTPS连接不再验证服务端身份，容易受到中间人攻击，
+    /// 仅应在测试环境中使用。信任私有CA请使用`root_certificates`。
+    #[cfg(not(target_arch = "wasm32"))]
+    #[builder(default = false)]
+    danger_accept_invalid_certs: bool,
+
     /// 要包含在所有请求中的全局头
     ///
     /// 这些头将自动添加到使用此配置发出的每个HTTP请求中。
     #[builder(default = HeaderMap::new())]
     headers: HeaderMap,
 
+    /// 要附加到所有请求URL上的全局查询参数
+    ///
+    /// 请求本身设置了同名参数时，以请求的值为准。
+    #[builder(default, setter(custom))]
+    query: Vec<(String, String)>,
+
     /// 要包含在所有有请求体的请求中的全局请求体字段
     ///
     /// 这些字段将自动合并到每个包含请求体的请求的请求体中。
@@ -72,6 +135,55 @@ impl HttpConfig {
         self.proxy.as_ref()
     }
 
+    #[inline]
+    pub fn pool_max_idle_per_host(&self) -> usize {
+        self.pool_max_idle_per_host
+    }
+
+    #[inline]
+    pub fn pool_idle_timeout(&self) -> Option<Duration> {
+        self.pool_idle_timeout
+    }
+
+    #[inline]
+    pub fn http2_prior_knowledge(&self) -> bool {
+        self.http2_prior_knowledge
+    }
+
+    #[inline]
+    pub fn tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive
+    }
+
+    #[inline]
+    pub fn accept_compression(&self) -> bool {
+        self.accept_compression
+    }
+
+    /// 是否接受gzip压缩的响应，未单独设置时与`accept_compression`一致。
+    #[inline]
+    pub fn gzip(&self) -> bool {
+        self.gzip.unwrap_or(self.accept_compression)
+    }
+
+    /// 是否接受brotli压缩的响应，未单独设置时与`accept_compression`一致。
+    #[inline]
+    pub fn brotli(&self) -> bool {
+        self.brotli.unwrap_or(self.accept_compression)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn root_certificates(&self) -> &[Certificate] {
+        &self.root_certificates
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn danger_accept_invalid_certs(&self) -> bool {
+        self.danger_accept_invalid_certs
+    }
+
     #[inline]
     pub fn user_agent(&self) -> Option<&HeaderValue> {
         self.headers.get(USER_AGENT)
@@ -82,6 +194,11 @@ impl HttpConfig {
         &self.headers
     }
 
+    #[inline]
+    pub fn query(&self) -> &[(String, String)] {
+        &self.query
+    }
+
     #[inline]
     pub fn bodys(&self) -> &JsonBody {
         &self.bodys
@@ -139,13 +256,49 @@ impl HttpConfig {
         self
     }
 
+    /// 构建reqwest客户端。
+    ///
+    /// `wasm32`下由浏览器负责连接管理，`connect_timeout`、`proxy`与连接池、HTTP/2、
+    /// TCP keepalive相关设置会被忽略，`timeout`改为在每个请求上设置，TLS相关设置不可用。
     pub fn build_reqwest_client(&self) -> reqwest::Client {
-        let mut client_builder = reqwest::ClientBuilder::new()
-            .timeout(self.timeout)
-            .connect_timeout(self.connect_timeout);
+        let mut client_builder = reqwest::ClientBuilder::new();
 
-        if let Some(ref proxy_url) = self.proxy {
-            if let Ok(proxy) = reqwest::Proxy::all(proxy_url) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            client_builder = client_builder
+                .timeout(self.timeout)
+                .connect_timeout(self.connect_timeout)
+                .pool_max_idle_per_host(self.pool_max_idle_per_host)
+                .pool_idle_timeout(self.pool_idle_timeout)
+                .tcp_keepalive(self.tcp_keepalive);
+
+            if self.http2_prior_knowledge {
+                client_builder = client_builder.http2_prior_knowledge();
+            }
+
+            #[cfg(feature = "gzip")]
+            {
+                client_builder = client_builder.gzip(self.gzip());
+            }
+            #[cfg(feature = "deflate")]
+            {
+                client_builder = client_builder.deflate(self.accept_compression);
+            }
+            #[cfg(feature = "brotli")]
+            {
+                client_builder = client_builder.brotli(self.brotli());
+            }
+
+            for certificate in &self.root_certificates {
+                client_builder = client_builder.add_root_certificate(certificate.clone());
+            }
+            if self.danger_accept_invalid_certs {
+                client_builder = client_builder.danger_accept_invalid_certs(true);
+            }
+
+            if let Some(ref proxy_url) = self.proxy
+                && let Ok(proxy) = reqwest::Proxy::all(proxy_url)
+            {
                 client_builder = client_builder.proxy(proxy);
             }
         }
@@ -166,8 +319,20 @@ impl Default for HttpConfig {
             timeout: Duration::from_secs(300),
             connect_timeout: Duration::from_secs(10),
             proxy: None,
+            pool_max_idle_per_host: usize::MAX,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            http2_prior_knowledge: false,
+            tcp_keepalive: Some(Duration::from_secs(15)),
+            accept_compression: true,
+            gzip: None,
+            brotli: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            root_certificates: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            danger_accept_invalid_certs: false,
             bodys: JsonBody::new(),
             headers: HeaderMap::new(),
+            query: Vec::new(),
         }
     }
 }
@@ -179,6 +344,17 @@ impl HttpConfigBuilder {
         self
     }
 
+    /// 添加一个全局查询参数，同名参数会被替换。
+    pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        let (key, value) = (key.into(), value.into());
+        let query = self.query.get_or_insert_with(Vec::new);
+        match query.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = value,
+            None => query.push((key, value)),
+        }
+        self
+    }
+
     pub fn body<T: Into<String>, U: Into<serde_json::Value>>(mut self, key: T, value: U) -> Self {
         let body_map = self.bodys.get_or_insert_with(JsonBody::new);
         body_map.insert(key.into(), value.into());
@@ -191,4 +367,13 @@ impl HttpConfigBuilder {
             .insert(USER_AGENT, user_agent);
         self
     }
+
+    /// 添加一个额外信任的根证书，可多次调用。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates
+            .get_or_insert_with(Vec::new)
+            .push(certificate);
+        self
+    }
 }
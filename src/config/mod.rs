@@ -7,20 +7,45 @@
 //!
 //! - [`Credentials`]: 包含API密钥和基础URL等基本API连接参数
 //! - [`HttpConfig`]: 处理HTTP特定设置，如超时、代理和用户代理
+//! - [`ApiKeyProvider`]: 动态获取并缓存API密钥的异步回调
+//! - [`AuthScheme`]: API密钥在请求中的携带方式
+//! - [`AzureConfig`]: Azure OpenAI 部署的URL和认证配置
+//! - [`RetryPolicy`]: 决定是否重试以及重试延迟的策略
+//! - [`ExponentialBackoff`]: 默认的指数退避重试策略
+//! - [`RetryRng`]: 指数退避抖动使用的随机数来源，可在测试中替换
+//! - [`RetryCallback`]: 每次重试前调用的观测回调
+//! - [`KeepaliveCallback`]: 流式响应中收到保活事件时调用的观测回调
 //! - [`Config`]: 结合基础和HTTP配置以及额外的客户端特定选项
 //! - [`ConfigBuilder`]: 提供流畅的API来构建配置
 //!
+/// 动态API密钥提供者
+pub mod api_key_provider;
+/// API认证方式
+pub mod auth;
+/// Azure OpenAI 端点配置
+pub mod azure;
 /// 客户端配置，结合基础和HTTP设置以及额外选项
 pub mod client;
 /// 用于连接API服务的HTTP客户端配置
 pub mod http;
+/// 重试相关配置
+pub mod retry;
 
-pub use client::{Config, ConfigBuilder};
+pub use api_key_provider::ApiKeyProvider;
+pub use auth::AuthScheme;
+pub use azure::AzureConfig;
+pub use client::{Config, ConfigBuilder, KeepaliveCallback};
 use derive_builder::Builder;
 pub use http::{HttpConfig, HttpConfigBuilder};
+pub use retry::{ExponentialBackoff, RetryCallback, RetryPolicy, RetryRng};
 
 #[derive(Debug, Clone, Builder)]
-#[builder(name = "CredentialsBuilder", pattern = "owned", setter(strip_option))]
+#[builder(
+    name = "CredentialsBuilder",
+    pattern = "owned",
+    setter(strip_option),
+    derive(Clone)
+)]
 pub struct Credentials {
     /// 用于服务身份验证的API密钥
     api_key: String,
@@ -0,0 +1,443 @@
+use crate::error::{ApiErrorKind, OpenAIError, RequestError};
+use rand::Rng;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 每次重试前调用的回调。
+///
+/// 参数依次为：刚失败的尝试次数（从1开始）、最大尝试次数、导致重试的错误、下一次重试前的等待时间。
+/// 回调在等待开始前同步调用，适合用于上报指标或记录日志，不应执行耗时操作。
+pub type RetryCallback = Arc<dyn Fn(u32, u32, &OpenAIError, Duration) + Send + Sync>;
+
+/// 决定失败的请求是否重试以及重试前等待多久。
+///
+/// 执行器在每次可重试的尝试失败后调用[`RetryPolicy::delay`]：返回`Some(delay)`时等待`delay`后重试，
+/// 返回`None`时停止重试并返回错误。无论策略如何，总尝试次数都不会超过`retry_count`。
+///
+/// 默认策略为[`ExponentialBackoff`]。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::{Config, OpenAIError, RetryPolicy};
+/// use std::time::Duration;
+///
+/// /// 固定间隔重试速率限制错误，最多重试两次
+/// struct FixedDelay;
+///
+/// impl RetryPolicy for FixedDelay {
+///     fn delay(
+///         &self,
+///         attempt: u32,
+///         error: &OpenAIError,
+///         retry_after: Option<Duration>,
+///     ) -> Option<Duration> {
+///         (attempt <= 2 && error.is_rate_limit())
+///             .then(|| retry_after.unwrap_or(Duration::from_millis(250)))
+///     }
+/// }
+///
+/// let config = Config::builder()
+///     .api_key("sk-...")
+///     .base_url("http://localhost:8000/v1")
+///     .retry_policy(FixedDelay)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait RetryPolicy: Send + Sync {
+    /// 计算下一次重试前的等待时间。
+    ///
+    /// # 参数
+    /// * `attempt` - 刚失败的尝试次数（从1开始）
+    /// * `error` - 导致失败的错误
+    /// * `retry_after` - 服务器通过`Retry-After`头指定的延迟
+    ///
+    /// # 返回值
+    /// 重试前等待的时间，返回`None`表示不再重试
+    fn delay(
+        &self,
+        attempt: u32,
+        error: &OpenAIError,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration>;
+}
+
+/// 为[`ExponentialBackoff`]的重试抖动提供随机数。
+///
+/// 默认使用线程本地的随机数生成器。在测试中可以注入固定或可预测的随机数，从而断言精确的退避延迟。
+/// 闭包`Fn() -> f64`自动实现此trait。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::ExponentialBackoff;
+/// use std::time::Duration;
+///
+/// // 始终取抖动范围的中点
+/// let policy = ExponentialBackoff::default().with_rng(|| 0.5);
+/// ```
+pub trait RetryRng: Send + Sync {
+    /// 返回`[0, 1)`范围内的随机数，超出范围的值会被截断到`[0, 1]`。
+    fn next_f64(&self) -> f64;
+}
+
+impl<F> RetryRng for F
+where
+    F: Fn() -> f64 + Send + Sync,
+{
+    fn next_f64(&self) -> f64 {
+        self()
+    }
+}
+
+/// 带抖动的指数退避重试策略，也是默认的[`RetryPolicy`]。
+///
+/// 仅重试[`OpenAIError::is_retryable`]为`true`的错误。
+/// 重试延迟按指数退避计算：`base_delay * 2^(attempt-1)`，并限制在对应的最大延迟内，
+/// 随后添加`[0, jitter_fraction)`比例的随机抖动。服务器返回`Retry-After`时直接使用该值
+/// （同样不超过`api_error_max_delay`），并添加最多`retry_after_jitter`的抖动。
+/// 抖动使用的随机数可以通过[`ExponentialBackoff::with_rng`]替换。
+///
+/// 此策略只决定重试延迟，不限制重试次数：对每个可重试的错误都会返回延迟，
+/// 最大尝试次数始终由[`Config::retry_count`](super::Config::retry_count)或请求级别的`retry_count`决定。
+/// 需要按错误类型限制重试次数时，请实现自定义的[`RetryPolicy`]。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::{Config, ExponentialBackoff};
+/// use std::time::Duration;
+///
+/// let config = Config::builder()
+///     .api_key("sk-...")
+///     .base_url("http://localhost:8000/v1")
+///     .retry_policy(ExponentialBackoff {
+///         rate_limit_base_delay: Duration::from_millis(200),
+///         api_error_max_delay: Duration::from_secs(2),
+///         ..Default::default()
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct ExponentialBackoff {
+    /// 一般API错误的基础延迟
+    pub api_error_base_delay: Duration,
+    /// 服务器内部错误（HTTP 500）的基础延迟
+    pub internal_server_error_base_delay: Duration,
+    /// 速率限制错误（HTTP 429）的基础延迟
+    pub rate_limit_base_delay: Duration,
+    /// API错误的最大延迟（不含抖动）
+    pub api_error_max_delay: Duration,
+    /// 一般网络请求错误的基础延迟
+    pub request_error_base_delay: Duration,
+    /// 连接错误的基础延迟
+    pub connection_error_base_delay: Duration,
+    /// 网络请求错误的最大延迟（不含抖动）
+    pub request_error_max_delay: Duration,
+    /// 在`Retry-After`指定的延迟上额外添加的最大抖动
+    pub retry_after_jitter: Duration,
+    /// 指数退避延迟的最大抖动比例，例如`0.1`表示最多增加10%，`0.0`表示不添加抖动
+    pub jitter_fraction: f64,
+    /// 抖动使用的随机数来源，为`None`时使用线程本地的随机数生成器
+    pub rng: Option<Arc<dyn RetryRng>>,
+}
+
+impl fmt::Debug for ExponentialBackoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExponentialBackoff")
+            .field("api_error_base_delay", &self.api_error_base_delay)
+            .field(
+                "internal_server_error_base_delay",
+                &self.internal_server_error_base_delay,
+            )
+            .field("rate_limit_base_delay", &self.rate_limit_base_delay)
+            .field("api_error_max_delay", &self.api_error_max_delay)
+            .field("request_error_base_delay", &self.request_error_base_delay)
+            .field(
+                "connection_error_base_delay",
+                &self.connection_error_base_delay,
+            )
+            .field("request_error_max_delay", &self.request_error_max_delay)
+            .field("retry_after_jitter", &self.retry_after_jitter)
+            .field("jitter_fraction", &self.jitter_fraction)
+            .field("rng", &self.rng.as_ref().map(|_| "<custom>"))
+            .finish()
+    }
+}
+
+/// 随机数来源按指针比较：只有共享同一个[`RetryRng`]实例（或都未设置）时才视为相等。
+impl PartialEq for ExponentialBackoff {
+    fn eq(&self, other: &Self) -> bool {
+        let same_rng = match (&self.rng, &other.rng) {
+            (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+            (None, None) => true,
+            _ => false,
+        };
+        same_rng
+            && self.api_error_base_delay == other.api_error_base_delay
+            && self.internal_server_error_base_delay == other.internal_server_error_base_delay
+            && self.rate_limit_base_delay == other.rate_limit_base_delay
+            && self.api_error_max_delay == other.api_error_max_delay
+            && self.request_error_base_delay == other.request_error_base_delay
+            && self.connection_error_base_delay == other.connection_error_base_delay
+            && self.request_error_max_delay == other.request_error_max_delay
+            && self.retry_after_jitter == other.retry_after_jitter
+            && self.jitter_fraction == other.jitter_fraction
+    }
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self {
+            api_error_base_delay: Duration::from_millis(500),
+            internal_server_error_base_delay: Duration::from_millis(1000),
+            rate_limit_base_delay: Duration::from_millis(5000),
+            api_error_max_delay: Duration::from_millis(30_000),
+            request_error_base_delay: Duration::from_millis(100),
+            connection_error_base_delay: Duration::from_millis(200),
+            request_error_max_delay: Duration::from_millis(10_000),
+            retry_after_jitter: Duration::from_millis(1000),
+            jitter_fraction: 0.1,
+            rng: None,
+        }
+    }
+}
+
+impl ExponentialBackoff {
+    /// 创建不带任何随机抖动的默认策略。
+    ///
+    /// 计算出的延迟完全确定，适合在测试中断言精确的退避序列。
+    ///
+    /// ```rust
+    /// use openai4rs::ExponentialBackoff;
+    /// use std::time::Duration;
+    ///
+    /// let policy = ExponentialBackoff {
+    ///     rate_limit_base_delay: Duration::from_millis(10),
+    ///     ..ExponentialBackoff::without_jitter()
+    /// };
+    /// assert_eq!(policy.jitter_fraction, 0.0);
+    /// ```
+    pub fn without_jitter() -> Self {
+        Self {
+            retry_after_jitter: Duration::ZERO,
+            jitter_fraction: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// 使用指定的随机数来源计算抖动，参见[`RetryRng`]。
+    #[must_use]
+    pub fn with_rng<R: RetryRng + 'static>(mut self, rng: R) -> Self {
+        self.rng = Some(Arc::new(rng));
+        self
+    }
+
+    /// 返回`[0, 1]`范围内的随机数。
+    fn sample(&self) -> f64 {
+        match &self.rng {
+            Some(rng) => rng.next_f64().clamp(0.0, 1.0),
+            None => rand::thread_rng().gen_range(0.0..1.0),
+        }
+    }
+
+    /// 返回`[0, max)`范围内的随机时长（毫秒精度），`max`小于1毫秒时返回0。
+    fn random_duration(&self, max: Duration) -> Duration {
+        let max_ms = max.as_millis() as u64;
+        if max_ms == 0 {
+            return Duration::ZERO;
+        }
+        let millis = (self.sample() * max_ms as f64) as u64;
+        Duration::from_millis(millis.min(max_ms - 1))
+    }
+
+    /// 根据API错误类型计算重试延迟。
+    fn api_error_delay(
+        &self,
+        attempt: u32,
+        error_kind: &ApiErrorKind,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        // 如果服务器指定了重试延迟，使用该延迟（不超过最大延迟）并添加抖动
+        if let Some(duration) = retry_after {
+            return duration.min(self.api_error_max_delay)
+                + self.random_duration(self.retry_after_jitter);
+        }
+
+        // 基础延迟因错误类型而异
+        let base_delay = match error_kind {
+            ApiErrorKind::RateLimit => self.rate_limit_base_delay,
+            ApiErrorKind::InternalServer => self.internal_server_error_base_delay,
+            _ => self.api_error_base_delay,
+        };
+
+        self.backoff(attempt, base_delay, self.api_error_max_delay)
+    }
+
+    /// 根据网络级请求错误计算重试延迟。
+    fn request_error_delay(&self, attempt: u32, error: &RequestError) -> Duration {
+        // 基础延迟因错误类型而异
+        let base_delay = match error {
+            RequestError::Connection(_) => self.connection_error_base_delay,
+            _ => self.request_error_base_delay,
+        };
+
+        self.backoff(attempt, base_delay, self.request_error_max_delay)
+    }
+
+    /// 指数退避：`base_delay * 2^(attempt-1)`，限制在`max_delay`内，并添加按比例的抖动。
+    fn backoff(&self, attempt: u32, base_delay: Duration, max_delay: Duration) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = base_delay.saturating_mul(factor).min(max_delay);
+
+        // 添加抖动以防止雷鸣般涌入
+        if self.jitter_fraction > 0.0 {
+            let jitter = self.sample() * self.jitter_fraction;
+            delay + delay.mul_f64(jitter)
+        } else {
+            delay
+        }
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn delay(
+        &self,
+        attempt: u32,
+        error: &OpenAIError,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if !error.is_retryable() {
+            return None;
+        }
+
+        match error {
+            OpenAIError::Api(api_error) => {
+                Some(self.api_error_delay(attempt, &api_error.kind, retry_after))
+            }
+            OpenAIError::Request(request_error) => {
+                Some(self.request_error_delay(attempt, request_error))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+
+    fn api_error(status: u16) -> OpenAIError {
+        ApiError {
+            status,
+            kind: ApiErrorKind::from(status),
+            message: String::new(),
+            r#type: None,
+            code: None,
+            param: None,
+        }
+        .into()
+    }
+
+    fn tight_policy() -> ExponentialBackoff {
+        ExponentialBackoff {
+            api_error_base_delay: Duration::from_millis(10),
+            internal_server_error_base_delay: Duration::from_millis(20),
+            rate_limit_base_delay: Duration::from_millis(50),
+            api_error_max_delay: Duration::from_millis(150),
+            request_error_base_delay: Duration::from_millis(1),
+            connection_error_base_delay: Duration::from_millis(2),
+            request_error_max_delay: Duration::from_millis(5),
+            ..ExponentialBackoff::without_jitter()
+        }
+    }
+
+    #[test]
+    fn test_exponential_backoff_delays() {
+        let policy = tight_policy();
+        let rate_limit = api_error(429);
+        let delays: Vec<_> = (1..=4)
+            .map(|attempt| policy.delay(attempt, &rate_limit, None).unwrap())
+            .collect();
+
+        assert_eq!(
+            delays,
+            [50, 100, 150, 150].map(Duration::from_millis).to_vec()
+        );
+        assert_eq!(
+            policy.delay(1, &api_error(500), None),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(
+            policy.delay(3, &rate_limit, Some(Duration::from_secs(2))),
+            Some(Duration::from_millis(150))
+        );
+        assert_eq!(
+            policy.delay(3, &rate_limit, Some(Duration::from_millis(40))),
+            Some(Duration::from_millis(40))
+        );
+    }
+
+    #[test]
+    fn test_exponential_backoff_stops_on_non_retryable() {
+        let policy = tight_policy();
+        assert_eq!(policy.delay(1, &api_error(400), None), None);
+        assert_eq!(policy.delay(1, &api_error(401), None), None);
+    }
+
+    #[test]
+    fn test_exponential_backoff_jitter_bounds() {
+        let policy = ExponentialBackoff::default();
+        let rate_limit = api_error(429);
+        for attempt in 1..=3 {
+            let delay = policy.delay(attempt, &rate_limit, None).unwrap();
+            let base = Duration::from_millis(5000 * 2u64.pow(attempt - 1));
+            assert!(delay >= base && delay < base.mul_f64(1.1));
+        }
+    }
+
+    #[test]
+    fn test_exponential_backoff_with_rng() {
+        let rate_limit = api_error(429);
+        let policy = ExponentialBackoff {
+            jitter_fraction: 0.5,
+            retry_after_jitter: Duration::from_millis(100),
+            ..tight_policy()
+        };
+
+        let midpoint = policy.clone().with_rng(|| 0.5);
+        let delays: Vec<_> = (1..=3)
+            .map(|attempt| midpoint.delay(attempt, &rate_limit, None).unwrap())
+            .collect();
+        assert_eq!(
+            delays,
+            [62_500, 125_000, 187_500]
+                .map(Duration::from_micros)
+                .to_vec()
+        );
+        assert_eq!(
+            midpoint.delay(1, &rate_limit, Some(Duration::from_millis(40))),
+            Some(Duration::from_millis(90))
+        );
+
+        let lowest = policy.clone().with_rng(|| 0.0);
+        assert_eq!(
+            lowest.delay(2, &api_error(500), None),
+            Some(Duration::from_millis(40))
+        );
+
+        // 超出范围的随机数会被截断，抖动不会超过上限
+        let highest = policy.with_rng(|| 7.0);
+        assert_eq!(
+            highest.delay(1, &rate_limit, None),
+            Some(Duration::from_millis(75))
+        );
+        assert_eq!(
+            highest.delay(1, &rate_limit, Some(Duration::from_millis(40))),
+            Some(Duration::from_millis(139))
+        );
+    }
+}
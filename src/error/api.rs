@@ -11,8 +11,12 @@ pub struct ApiError {
     pub status: u16,
     pub kind: ApiErrorKind,
     pub message: String,
+    /// 错误代码，例如`context_length_exceeded`、`invalid_api_key`
     pub code: Option<String>,
+    /// 错误类型，例如`invalid_request_error`
     pub r#type: Option<String>,
+    /// 导致错误的请求参数名称，例如`messages`
+    pub param: Option<String>,
 }
 
 /// 基于 HTTP 状态码的 API 错误分类。
@@ -20,6 +24,8 @@ pub struct ApiError {
 pub enum ApiErrorKind {
     BadRequest,
     Authentication,
+    /// 需要付费，通常表示额度或余额不足（HTTP 402）。
+    PaymentRequired,
     PermissionDenied,
     NotFound,
     Conflict,
@@ -35,6 +41,7 @@ impl From<u16> for ApiErrorKind {
         match code {
             400 => Self::BadRequest,
             401 => Self::Authentication,
+            402 => Self::PaymentRequired,
             403 => Self::PermissionDenied,
             404 => Self::NotFound,
             409 => Self::Conflict,
@@ -52,6 +59,21 @@ impl ApiError {
         self.kind == ApiErrorKind::Authentication
     }
 
+    /// 如果需要付费（HTTP 402），例如账户余额或额度不足，则返回 `true`。
+    pub fn is_payment_required(&self) -> bool {
+        self.kind == ApiErrorKind::PaymentRequired
+    }
+
+    /// 如果没有访问权限（HTTP 403），则返回 `true`。
+    pub fn is_forbidden(&self) -> bool {
+        self.kind == ApiErrorKind::PermissionDenied
+    }
+
+    /// 如果请求的资源不存在（HTTP 404），例如模型名称错误，则返回 `true`。
+    pub fn is_not_found(&self) -> bool {
+        self.kind == ApiErrorKind::NotFound
+    }
+
     /// 如果错误是速率限制错误（HTTP 429），则返回 `true`。
     pub fn is_rate_limit(&self) -> bool {
         self.kind == ApiErrorKind::RateLimit
@@ -72,6 +94,48 @@ impl ApiError {
         self.kind == ApiErrorKind::Conflict
     }
 
+    /// 返回错误类型（`error.type`），例如`invalid_request_error`。
+    pub fn error_type(&self) -> Option<&str> {
+        self.r#type.as_deref()
+    }
+
+    /// 返回错误代码（`error.code`），例如`context_length_exceeded`。
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// 返回导致错误的请求参数名称（`error.param`）。
+    pub fn param(&self) -> Option<&str> {
+        self.param.as_deref()
+    }
+
+    /// 如果提示超出了模型的上下文窗口，则返回 `true`。
+    ///
+    /// 优先根据错误代码`context_length_exceeded`判断；部分兼容服务不返回代码，
+    /// 此时根据错误消息中的常见措辞进行启发式匹配。
+    pub fn is_context_length_exceeded(&self) -> bool {
+        if self.code() == Some("context_length_exceeded") {
+            return true;
+        }
+        if !matches!(
+            self.kind,
+            ApiErrorKind::BadRequest | ApiErrorKind::UnprocessableEntity | ApiErrorKind::Other
+        ) {
+            return false;
+        }
+
+        const PHRASES: &[&str] = &[
+            "context_length_exceeded",
+            "maximum context length",
+            "context window",
+            "context length",
+            "prompt is too long",
+            "too many tokens",
+        ];
+        let message = self.message.to_lowercase();
+        PHRASES.iter().any(|phrase| message.contains(phrase))
+    }
+
     /// 如果导致错误的请求在重试时可能成功，则返回 `true`。
     pub fn is_retryable(&self) -> bool {
         // 速率限制、服务器端错误和冲突值得重试。
@@ -84,21 +148,24 @@ impl AsyncFrom<Response> for ApiError {
         let status = response.status();
         let status_code = status.as_u16();
 
-        let (message, code, r#type) = if let Ok(json) = response.json::<Value>().await {
+        let (message, code, r#type, param) = if let Ok(json) = response.json::<Value>().await {
             let error = &json["error"];
             let message = error["message"]
                 .as_str()
                 .unwrap_or("No error message provided")
                 .to_string();
-            let code = error["code"].as_str().map(String::from);
-            let r#type = error["type"].as_str().map(String::from);
-            (message, code, r#type)
+            (
+                message,
+                error_field(error, "code"),
+                error_field(error, "type"),
+                error_field(error, "param"),
+            )
         } else {
             let msg = status
                 .canonical_reason()
                 .unwrap_or("Unknown status")
                 .to_string();
-            (msg, None, None)
+            (msg, None, None, None)
         };
 
         ApiError {
@@ -107,10 +174,72 @@ impl AsyncFrom<Response> for ApiError {
             message,
             code,
             r#type,
+            param,
         }
     }
 }
 
+impl ApiError {
+    /// 从流式响应的事件数据中解析错误，例如`{"error": {"message": "...", "type": "rate_limit_error"}}`。
+    ///
+    /// 部分服务在返回200并开始发送SSE之后，才以错误对象作为事件数据报告失败。
+    /// 由于HTTP状态码已经是200，状态码依次取自错误对象中数字形式的`code`或`status`、
+    /// 根据`type`或`code`推断的状态码，都无法确定时视为服务器端错误（500）。
+    /// 数据不是包含`error`对象的JSON时返回`None`。
+    pub(crate) fn from_stream_event(data: &str) -> Option<Self> {
+        let json: Value = serde_json::from_str(data).ok()?;
+        let error = json.get("error").filter(|error| error.is_object())?;
+
+        let status_code = ["code", "status"]
+            .iter()
+            .filter_map(|key| error[key].as_u64())
+            .find(|code| (400..=599).contains(code))
+            .map(|code| code as u16)
+            .or_else(|| {
+                ["type", "code"]
+                    .iter()
+                    .filter_map(|key| error[key].as_str())
+                    .find_map(status_for_error_type)
+            })
+            .unwrap_or(500);
+
+        Some(ApiError {
+            status: status_code,
+            kind: ApiErrorKind::from(status_code),
+            message: error["message"]
+                .as_str()
+                .unwrap_or("No error message provided")
+                .to_string(),
+            code: error_field(error, "code"),
+            r#type: error_field(error, "type"),
+            param: error_field(error, "param"),
+        })
+    }
+}
+
+/// 根据常见的错误类型或错误代码推断HTTP状态码。
+fn status_for_error_type(value: &str) -> Option<u16> {
+    match value {
+        "invalid_request_error" | "context_length_exceeded" => Some(400),
+        "authentication_error" | "invalid_api_key" => Some(401),
+        "permission_error" | "permission_denied" => Some(403),
+        "not_found_error" | "model_not_found" => Some(404),
+        "rate_limit_error" | "rate_limit_exceeded" | "insufficient_quota" => Some(429),
+        "server_error" | "api_error" => Some(500),
+        "overloaded_error" => Some(503),
+        _ => None,
+    }
+}
+
+/// 读取错误对象中的字符串字段，部分服务使用数字作为错误代码，同样转换为字符串。
+fn error_field(error: &Value, key: &str) -> Option<String> {
+    match &error[key] {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,6 +249,7 @@ mod tests {
         // 测试所有定义的状态码
         assert_eq!(ApiErrorKind::from(400), ApiErrorKind::BadRequest);
         assert_eq!(ApiErrorKind::from(401), ApiErrorKind::Authentication);
+        assert_eq!(ApiErrorKind::from(402), ApiErrorKind::PaymentRequired);
         assert_eq!(ApiErrorKind::from(403), ApiErrorKind::PermissionDenied);
         assert_eq!(ApiErrorKind::from(404), ApiErrorKind::NotFound);
         assert_eq!(ApiErrorKind::from(409), ApiErrorKind::Conflict);
@@ -142,6 +272,7 @@ mod tests {
             message: "Invalid API key".to_string(),
             code: Some("invalid_key".to_string()),
             r#type: Some("authentication_error".to_string()),
+            param: None,
         };
 
         let rate_limit_error = ApiError {
@@ -150,6 +281,7 @@ mod tests {
             message: "Rate limit exceeded".to_string(),
             code: Some("rate_limit_exceeded".to_string()),
             r#type: Some("rate_limit_error".to_string()),
+            param: None,
         };
 
         let server_error = ApiError {
@@ -158,6 +290,7 @@ mod tests {
             message: "Internal server error".to_string(),
             code: Some("internal_error".to_string()),
             r#type: Some("server_error".to_string()),
+            param: None,
         };
 
         let bad_request_error = ApiError {
@@ -166,6 +299,7 @@ mod tests {
             message: "Bad request".to_string(),
             code: Some("bad_request".to_string()),
             r#type: Some("invalid_request_error".to_string()),
+            param: None,
         };
 
         let conflict_error = ApiError {
@@ -174,6 +308,7 @@ mod tests {
             message: "Conflict".to_string(),
             code: Some("conflict".to_string()),
             r#type: Some("conflict_error".to_string()),
+            param: None,
         };
 
         // 测试辅助方法
@@ -216,6 +351,7 @@ mod tests {
             message: "Invalid API key".to_string(),
             code: Some("invalid_key".to_string()),
             r#type: Some("authentication_error".to_string()),
+            param: None,
         };
 
         let error_string = format!("{}", error);
@@ -223,4 +359,111 @@ mod tests {
         assert!(error_string.contains("401"));
         assert!(error_string.contains("Invalid API key"));
     }
+
+    fn api_error(status: u16) -> ApiError {
+        ApiError {
+            status,
+            kind: ApiErrorKind::from(status),
+            message: "error".to_string(),
+            code: None,
+            r#type: None,
+            param: None,
+        }
+    }
+
+    #[test]
+    fn test_api_error_payment_required() {
+        let error = api_error(402);
+        assert!(error.is_payment_required());
+        assert!(!error.is_forbidden());
+        assert!(!error.is_not_found());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_api_error_forbidden() {
+        let error = api_error(403);
+        assert!(error.is_forbidden());
+        assert!(!error.is_payment_required());
+        assert!(!error.is_not_found());
+        assert!(!error.is_authentication());
+    }
+
+    #[test]
+    fn test_api_error_not_found() {
+        let error = api_error(404);
+        assert!(error.is_not_found());
+        assert!(!error.is_forbidden());
+        assert!(!error.is_payment_required());
+        assert!(!error.is_bad_request());
+    }
+
+    #[test]
+    fn test_api_error_from_stream_event() {
+        let error = ApiError::from_stream_event(
+            r#"{"error": {"message": "Rate limit reached", "type": "rate_limit_error", "param": null, "code": "rate_limit_exceeded"}}"#,
+        )
+        .unwrap();
+        assert_eq!(error.status, 429);
+        assert!(error.is_rate_limit());
+        assert_eq!(error.message, "Rate limit reached");
+        assert_eq!(error.code(), Some("rate_limit_exceeded"));
+
+        // 数字形式的错误代码优先
+        let error =
+            ApiError::from_stream_event(r#"{"error": {"message": "No credits", "code": 402}}"#)
+                .unwrap();
+        assert!(error.is_payment_required());
+
+        let error =
+            ApiError::from_stream_event(r#"{"error": {"message": "Upstream failed"}}"#).unwrap();
+        assert!(error.is_server_error());
+
+        assert!(ApiError::from_stream_event(r#"{"id": "chatcmpl-1", "choices": []}"#).is_none());
+        assert!(ApiError::from_stream_event(r#"{"error": null}"#).is_none());
+        assert!(ApiError::from_stream_event("not json").is_none());
+    }
+
+    fn bad_request(message: &str, code: Option<&str>) -> ApiError {
+        ApiError {
+            status: 400,
+            kind: ApiErrorKind::BadRequest,
+            message: message.to_string(),
+            code: code.map(String::from),
+            r#type: Some("invalid_request_error".to_string()),
+            param: None,
+        }
+    }
+
+    #[test]
+    fn test_api_error_is_context_length_exceeded() {
+        assert!(
+            bad_request("anything", Some("context_length_exceeded")).is_context_length_exceeded()
+        );
+
+        // 不返回错误代码的兼容服务
+        for message in [
+            "This model's maximum context length is 8192 tokens. However, your messages resulted in 9000 tokens.",
+            "This model's maximum context length is 32768 tokens. However, you requested 40000 tokens (39000 in the messages, 1000 in the completion).",
+            "prompt is too long: 210000 tokens > 200000 maximum",
+            "The input exceeds the context window of this model",
+        ] {
+            assert!(
+                bad_request(message, None).is_context_length_exceeded(),
+                "{message}"
+            );
+        }
+
+        assert!(!bad_request("Invalid value for 'temperature'", None).is_context_length_exceeded());
+
+        let rate_limit = ApiError {
+            status: 429,
+            kind: ApiErrorKind::RateLimit,
+            message: "Too many tokens per minute".to_string(),
+            code: None,
+            r#type: None,
+            param: None,
+        };
+        assert!(!rate_limit.is_context_length_exceeded());
+    }
 }
@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+/// 从环境变量加载客户端配置时发生的错误。
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigError {
+    /// 必需的环境变量未设置
+    #[error("The `{name}` environment variable is not set")]
+    MissingEnvVar { name: String },
+
+    /// 环境变量的值无法解析
+    #[error("The `{name}` environment variable has an invalid value `{value}`: {reason}")]
+    InvalidEnvVar {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl ConfigError {
+    /// 返回导致错误的环境变量名称。
+    pub fn env_var(&self) -> &str {
+        match self {
+            Self::MissingEnvVar { name } | Self::InvalidEnvVar { name, .. } => name,
+        }
+    }
+}
@@ -11,6 +11,8 @@
 //!   速率限制，无效请求)。
 //! - [`ProcessingError`]: 在处理 API 响应期间发生的错误
 //!   (例如，反序列化失败)。
+//! - [`ConfigError`]: 从环境变量加载配置时发生的错误
+//!   (例如，缺少 API 密钥，超时时间格式错误)。
 //!
 //! # 示例
 //!
@@ -48,6 +50,9 @@
 //!             eprintln!("Response processing error: {}", proc_error);
 //!             // 处理响应处理期间的错误
 //!         }
+//!         Err(OpenAIError::Config(config_error)) => {
+//!             eprintln!("Configuration error: {}", config_error);
+//!         }
 //!     }
 //!
 //!     Ok(())
@@ -91,6 +96,7 @@
 //! ```
 
 pub use api::{ApiError, ApiErrorKind};
+pub use config::ConfigError;
 use eventsource_stream::EventStreamError;
 pub use processing::ProcessingError;
 pub use request::RequestError;
@@ -99,6 +105,7 @@ use thiserror::Error;
 use crate::error::sse::SseError;
 
 pub mod api;
+pub mod config;
 pub mod processing;
 pub mod request;
 pub mod sse;
@@ -119,6 +126,10 @@ pub enum OpenAIError {
     /// 在处理 API 响应期间发生的错误。
     #[error("Response processing error: {0}")]
     Processing(#[from] ProcessingError),
+
+    /// 加载客户端配置时发生的错误。
+    #[error("Configuration error: {0}")]
+    Config(#[from] ConfigError),
 }
 
 impl OpenAIError {
@@ -137,6 +148,11 @@ impl OpenAIError {
         matches!(self, Self::Processing(_))
     }
 
+    /// 如果错误是配置错误，则返回 `true`。
+    pub fn is_config_error(&self) -> bool {
+        matches!(self, Self::Config(_))
+    }
+
     /// 如果错误是超时错误，则返回 `true`。
     pub fn is_timeout(&self) -> bool {
         matches!(self, Self::Request(err) if err.is_timeout())
@@ -152,6 +168,21 @@ impl OpenAIError {
         matches!(self, Self::Api(err) if err.is_authentication())
     }
 
+    /// 如果需要付费 (HTTP 402)，例如账户余额或额度不足，则返回 `true`。
+    pub fn is_payment_required(&self) -> bool {
+        matches!(self, Self::Api(err) if err.is_payment_required())
+    }
+
+    /// 如果没有访问权限 (HTTP 403)，则返回 `true`。
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, Self::Api(err) if err.is_forbidden())
+    }
+
+    /// 如果请求的资源不存在 (HTTP 404)，则返回 `true`。
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::Api(err) if err.is_not_found())
+    }
+
     /// 如果错误是速率限制错误 (HTTP 429)，则返回 `true`。
     pub fn is_rate_limit(&self) -> bool {
         matches!(self, Self::Api(err) if err.is_rate_limit())
@@ -167,11 +198,20 @@ impl OpenAIError {
         matches!(self, Self::Api(err) if err.is_bad_request())
     }
 
+    /// 如果提示超出了模型的上下文窗口，则返回 `true`。
+    ///
+    /// 参见[`ApiError::is_context_length_exceeded`]。
+    pub fn is_context_length_exceeded(&self) -> bool {
+        matches!(self, Self::Api(err) if err.is_context_length_exceeded())
+    }
+
     /// 如果错误是由于反序列化问题，则返回 `true`。
     pub fn is_deserialization(&self) -> bool {
         matches!(
             self,
-            Self::Processing(ProcessingError::JsonDeserialization { .. })
+            Self::Processing(
+                ProcessingError::JsonDeserialization { .. } | ProcessingError::JsonBody { .. }
+            )
         )
     }
 
@@ -190,8 +230,10 @@ impl OpenAIError {
             Self::Api(err) => Some(err.status),
             Self::Processing(err) => match err {
                 ProcessingError::JsonDeserialization { status_code, .. } => *status_code,
+                ProcessingError::JsonBody { status_code, .. } => Some(*status_code),
                 _ => None,
             },
+            Self::Config(_) => None,
         }
     }
 
@@ -212,6 +254,7 @@ impl OpenAIError {
             Self::Request(err) => err.to_string(),
             Self::Api(err) => err.message.clone(),
             Self::Processing(err) => err.to_string(),
+            Self::Config(err) => err.to_string(),
         }
     }
 }
@@ -15,10 +15,33 @@ pub enum ProcessingError {
         url: Option<String>,
     },
 
+    /// 响应体不是目标类型的有效JSON，`error`包含`serde_json`报告的原因与出错位置
+    #[error(
+        "Failed to deserialize JSON response from {url} (status {status_code}) to type '{target_type}': {error}"
+    )]
+    JsonBody {
+        #[source]
+        error: serde_json::Error,
+        /// 按UTF-8有损解码的原始响应体
+        raw: String,
+        target_type: String,
+        status_code: u16,
+        url: String,
+    },
+
     /// 无法将一个值从一种类型转换为另一种类型（用于SSE流处理）
     #[error("Failed to convert value '{raw}' to type '{target_type}'")]
     Conversion { raw: String, target_type: String },
 
+    /// 工具调用的参数无法反序列化为目标类型，例如模型生成了不完整或不符合结构的JSON
+    #[error("Failed to parse arguments of tool call '{name}' into type '{target_type}': {error}")]
+    ToolArguments {
+        #[source]
+        error: serde_json::Error,
+        name: String,
+        target_type: String,
+    },
+
     /// 处理服务器发送事件流时发生错误。
     #[error("Failed to process SSE stream: {0}")]
     Sse(#[from] SseError),
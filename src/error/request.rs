@@ -22,13 +22,40 @@ pub enum RequestError {
     /// 事件流中发生错误。
     #[error("Event stream error: {0}")]
     EventSource(String),
+
+    /// 请求参数无效，请求未被发送。
+    #[error("Invalid request parameter: {0}")]
+    InvalidParameter(String),
+
+    /// 请求参数未通过客户端检查（例如超出文档规定的取值范围），请求未被发送。
+    #[error("Invalid value for `{field}`: expected {expected}, got {actual}")]
+    Validation {
+        /// 未通过检查的参数名
+        field: String,
+        /// 允许的取值，例如`a value between 0 and 2`
+        expected: String,
+        /// 实际设置的值
+        actual: String,
+    },
+
+    /// API密钥包含换行符等不能出现在请求头中的字符，请求未被发送。
+    #[error("The API key is not a valid HTTP header value: {0}")]
+    InvalidApiKey(#[source] http::header::InvalidHeaderValue),
+
+    /// 请求被拦截器中止。
+    #[error("Request aborted by interceptor: {0}")]
+    Interceptor(String),
+
+    /// 请求在收到响应前被取消令牌取消。
+    #[error("Request was cancelled")]
+    Cancelled,
 }
 
 impl From<reqwest::Error> for RequestError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
             Self::Timeout(err)
-        } else if err.is_connect() {
+        } else if is_connect(&err) {
             Self::Connection(err)
         } else if err.is_builder() {
             Self::Build(err)
@@ -38,6 +65,17 @@ impl From<reqwest::Error> for RequestError {
     }
 }
 
+/// `wasm32`下reqwest不区分连接错误。
+fn is_connect(err: &reqwest::Error) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    return err.is_connect();
+    #[cfg(target_arch = "wasm32")]
+    return {
+        let _ = err;
+        false
+    };
+}
+
 impl RequestError {
     /// 如果错误是超时则返回 `true`。
     pub fn is_timeout(&self) -> bool {
@@ -49,13 +87,31 @@ impl RequestError {
         matches!(self, Self::Connection(_))
     }
 
+    /// 创建参数检查错误。
+    pub(crate) fn validation(
+        field: impl Into<String>,
+        expected: impl Into<String>,
+        actual: impl ToString,
+    ) -> Self {
+        Self::Validation {
+            field: field.into(),
+            expected: expected.into(),
+            actual: actual.to_string(),
+        }
+    }
+
     /// 如果错误是从响应生成的，则返回 `StatusCode`。
     pub fn status(&self) -> Option<reqwest::StatusCode> {
         match self {
             Self::Connection(e) | Self::Timeout(e) | Self::Transport(e) | Self::Build(e) => {
                 e.status()
             }
-            Self::EventSource(_) => None,
+            Self::EventSource(_)
+            | Self::InvalidParameter(_)
+            | Self::Validation { .. }
+            | Self::InvalidApiKey(_)
+            | Self::Interceptor(_)
+            | Self::Cancelled => None,
         }
     }
 
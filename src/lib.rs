@@ -16,6 +16,9 @@
 //! - **模型管理**: 列出和检索可用模型的信息。
 //! - **可配置的 HTTP 客户端**: 自定义超时、重试、代理和用户代理。
 //! - **线程安全**: 客户端可以在多个线程间安全共享。
+//! - **WebAssembly**: 可编译到`wasm32-unknown-unknown`，此时通过浏览器的`fetch`发送请求，
+//!   `proxy`与`connect_timeout`（包括请求级别）会被忽略，TLS根证书与`danger_accept_invalid_certs`不可用，
+//!   `OpenAI::from_env`不可用。
 //! - **推理支持**: 对基于推理的模型提供特殊支持。
 //!
 //! ## 快速开始
@@ -96,15 +99,27 @@ pub mod utils;
 
 // 重新导出核心类型和函数
 pub use client::OpenAI;
-pub use config::{Config, ConfigBuilder};
+pub use common::rate_limit::{RateLimitInfo, RateLimitSnapshot};
+pub use common::types::{ApiResponse, Penalty, Temperature, UsageTracker};
+pub use config::{
+    ApiKeyProvider, AuthScheme, AzureConfig, Config, ConfigBuilder, ExponentialBackoff,
+    KeepaliveCallback, RetryCallback, RetryPolicy, RetryRng,
+};
 pub use error::OpenAIError;
 pub use http::header;
 pub use http::header::{HeaderName, HeaderValue};
 pub use modules::*;
 pub use serde_json;
-pub use service::{Request, RequestBuilder};
+#[cfg(feature = "debug-logging")]
+pub use service::DebugLoggingInterceptor;
+#[cfg(any(test, feature = "test-util"))]
+pub use service::MockTransport;
+pub use service::{
+    Interceptor, InterceptorChain, RateLimitInterceptor, Request, RequestBuilder, Transport,
+};
+pub use tokio_util::sync::CancellationToken;
 // 导入并重新导出新的过程宏
 pub mod macros {
-    pub use openai4rs_macro::{assistant, content, system, tool, user};
+    pub use openai4rs_macro::{ToolParameters, assistant, content, developer, system, tool, user};
 }
 pub use macros::*;
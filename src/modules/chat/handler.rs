@@ -2,11 +2,13 @@ use core::panic;
 
 use super::params::ChatParam;
 use super::types::{ChatCompletion, ChatCompletionChunk};
-use crate::common::types::{InParam, RetryCount, Timeout};
-use crate::error::OpenAIError;
+use crate::common::types::{ApiResponse, BaseUrl, ConnectTimeout, InParam, RetryCount, Timeout};
+use crate::config::Config;
+use crate::error::{OpenAIError, RequestError};
 use crate::service::client::HttpClient;
-use crate::service::request::{RequestBuilder, RequestSpec};
+use crate::service::request::{Request, RequestBuilder, RequestSpec};
 use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
 
 /// 处理聊天完成请求，包括流式和非流式模式。
 pub struct Chat {
@@ -22,6 +24,9 @@ impl Chat {
     ///
     /// 此方法向API发送请求，并在单个响应中返回完整的完成结果。
     ///
+    /// 通过`ChatParam::cancellation_token`传入的`CancellationToken`被取消时，
+    /// 会立即中止等待中的请求并返回[`RequestError::Cancelled`]。
+    ///
     /// # 参数
     ///
     /// * `param` - 聊天完成的一组参数，例如模型和消息。
@@ -44,30 +49,83 @@ impl Chat {
     /// }
     /// ```
     pub async fn create(&self, param: ChatParam) -> Result<ChatCompletion, OpenAIError> {
+        self.create_with_response(param)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// 创建一个聊天完成，并返回响应的状态码与响应头。
+    ///
+    /// 与[`Chat::create`]相同，但返回的[`ApiResponse`]还包含HTTP元数据，
+    /// 可用于读取`x-ratelimit-remaining-requests`、`x-request-id`等响应头。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// use dotenvy::dotenv;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     dotenv().ok();
+    ///     let client = OpenAI::from_env()?;
+    ///     let messages = vec![user!("What is Rust?")];
+    ///     let request = ChatParam::new("Qwen/Qwen3-235B-A22B-Instruct-2507", &messages);
+    ///     let response = client.chat().create_with_response(request).await?;
+    ///     println!("remaining requests: {:?}", response.header("x-ratelimit-remaining-requests"));
+    ///     println!("{:?}", response.body.content());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_with_response(
+        &self,
+        param: ChatParam,
+    ) -> Result<ApiResponse<ChatCompletion>, OpenAIError> {
+        param.validate()?;
         let mut inner = param.take();
-        inner
-            .body
-            .as_mut()
-            .unwrap()
-            .insert("stream".to_string(), serde_json::to_value(false).unwrap());
+        let body = inner.body.as_mut().unwrap();
+        // 无论用户如何设置，非流式请求都不能开启流式响应，否则SSE响应会被当作JSON解析
+        body.insert("stream".to_string(), serde_json::to_value(false).unwrap());
+        // 服务端只允许在流式请求中携带`stream_options`
+        body.remove("stream_options");
+
+        let base_url = inner.extensions.get::<BaseUrl>().cloned();
+        let cancel_token = inner.extensions.get::<CancellationToken>().cloned();
+
+        #[cfg(debug_assertions)]
+        super::lint::lint_request_body(inner.body.as_ref().unwrap());
 
         let http_params = RequestSpec::new(
-            |config| format!("{}/chat/completions", config.base_url()),
-            move |config, request| {
+            move |config| match base_url {
+                Some(BaseUrl(base_url)) => {
+                    config.endpoint_url_with_base(&base_url, "/chat/completions")
+                }
+                None => config.endpoint_url("/chat/completions"),
+            },
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
         );
 
-        self.http_client.post_json(http_params).await
+        let response = self.http_client.post_json_with_response(http_params);
+        match cancel_token {
+            // 取消时丢弃进行中的请求，底层连接随之关闭
+            Some(cancel_token) => tokio::select! {
+                response = response => response,
+                _ = cancel_token.cancelled() => Err(RequestError::Cancelled.into()),
+            },
+            None => response.await,
+        }
     }
 
     /// 创建一个流式聊天完成。
     ///
     /// 此方法返回 `ChatCompletionChunk` 事件流。这对于实时显示生成的完成结果非常有用。
     ///
+    /// 丢弃返回的流即可终止读取并关闭底层连接。如果流被其他位置持有，
+    /// 可以通过 `ChatParam::cancellation_token` 传入 `CancellationToken` 并在需要时调用 `cancel()`。
+    ///
     /// # 参数
     ///
     /// * `param` - 聊天完成的一组参数，例如模型和消息。
@@ -102,27 +160,90 @@ impl Chat {
         &self,
         param: ChatParam,
     ) -> Result<ReceiverStream<Result<ChatCompletionChunk, OpenAIError>>, OpenAIError> {
-        let mut inner = param.take();
+        param.validate()?;
+        let inner = param.take();
+        let cancel_token = inner.extensions.get::<CancellationToken>().cloned();
+        let http_params = Self::stream_request(inner);
+        self.http_client
+            .post_json_sse(http_params, cancel_token)
+            .await
+    }
+
+    /// 创建流式聊天完成，并原样返回每个服务器发送事件的数据。
+    ///
+    /// 与[`Chat::create_stream`]发送相同的请求，但不会将事件反序列化为[`ChatCompletionChunk`]，
+    /// 适用于调试、记录原始流或将其原封不动地转发给下游客户端。
+    /// 空事件会被跳过，`[DONE]`会结束流且不会被返回。
+    ///
+    /// # 参数
+    ///
+    /// * `param` - 聊天完成的一组参数，例如模型和消息。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// use futures::StreamExt;
+    /// use dotenvy::dotenv;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     dotenv().ok();
+    ///     let client = OpenAI::from_env()?;
+    ///     let messages = vec![user!("Tell me a short story.")];
+    ///     let request = ChatParam::new("Qwen/Qwen3-235B-A22B-Instruct-2507", &messages);
+    ///     let mut stream = client.chat().create_stream_raw(request).await?;
+    ///
+    ///     while let Some(data) = stream.next().await {
+    ///         println!("data: {}", data?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_stream_raw(
+        &self,
+        param: ChatParam,
+    ) -> Result<ReceiverStream<Result<String, OpenAIError>>, OpenAIError> {
+        param.validate()?;
+        let inner = param.take();
+        let cancel_token = inner.extensions.get::<CancellationToken>().cloned();
+        let http_params = Self::stream_request(inner);
+        self.http_client
+            .post_raw_sse(http_params, cancel_token)
+            .await
+    }
+}
+
+impl Chat {
+    /// 构建流式聊天完成请求。
+    fn stream_request(
+        mut inner: InParam,
+    ) -> RequestSpec<impl FnOnce(&Config) -> String, impl FnOnce(&Config, Request) -> Request> {
         inner
             .body
             .as_mut()
             .unwrap()
             .insert("stream".to_string(), serde_json::to_value(true).unwrap());
 
-        let http_params = RequestSpec::new(
-            |config| format!("{}/chat/completions", config.base_url()),
-            move |config, request| {
+        let base_url = inner.extensions.get::<BaseUrl>().cloned();
+
+        #[cfg(debug_assertions)]
+        super::lint::lint_request_body(inner.body.as_ref().unwrap());
+
+        RequestSpec::new(
+            move |config| match base_url {
+                Some(BaseUrl(base_url)) => {
+                    config.endpoint_url_with_base(&base_url, "/chat/completions")
+                }
+                None => config.endpoint_url("/chat/completions"),
+            },
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
-        );
-        self.http_client.post_json_sse(http_params).await
+        )
     }
-}
 
-impl Chat {
     fn apply_request_settings(builder: &mut RequestBuilder, params: InParam) {
         let body = params
             .body
@@ -132,12 +253,82 @@ impl Chat {
 
         *builder.request_mut().headers_mut() = params.headers;
 
+        for (key, value) in params.query {
+            builder.query(key, value);
+        }
+
         if let Some(time) = params.extensions.get::<Timeout>() {
             builder.timeout(time.0);
         }
 
+        if let Some(time) = params.extensions.get::<ConnectTimeout>() {
+            builder.connect_timeout(time.0);
+        }
+
         if let Some(retry) = params.extensions.get::<RetryCount>() {
             builder.request_mut().extensions_mut().insert(retry.clone());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::request::Request;
+    use crate::user;
+    use std::time::Duration;
+
+    #[test]
+    fn test_apply_request_settings_query() {
+        let messages = vec![user!("hello")];
+        let param = ChatParam::new("gpt-4o", &messages).query("api-version", "2024-10-21");
+
+        let mut builder = RequestBuilder::new(Request::new(
+            reqwest::Method::POST,
+            "https://example.openai.azure.com/chat/completions".to_string(),
+        ));
+        Chat::apply_request_settings(&mut builder, param.take());
+
+        let request = builder
+            .take()
+            .to_reqwest(&reqwest::Client::new())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://example.openai.azure.com/chat/completions?api-version=2024-10-21"
+        );
+    }
+
+    #[test]
+    fn test_apply_request_settings_timeouts() {
+        let messages = vec![user!("hello")];
+        let param = ChatParam::new("gpt-4o", &messages)
+            .timeout(Duration::from_secs(3))
+            .connect_timeout(Duration::from_millis(500));
+
+        let mut builder = RequestBuilder::new(Request::new(
+            reqwest::Method::POST,
+            "https://api.openai.com/v1/chat/completions".to_string(),
+        ));
+        Chat::apply_request_settings(&mut builder, param.take());
+        let request = builder.take();
+
+        assert_eq!(
+            request
+                .extensions()
+                .get::<ConnectTimeout>()
+                .map(|timeout| timeout.0),
+            Some(Duration::from_millis(500))
+        );
+
+        // 请求级别的超时会覆盖客户端构建时设置的全局超时
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let request = request.to_reqwest(&client).build().unwrap();
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(3)));
+    }
+}
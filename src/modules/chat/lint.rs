@@ -0,0 +1,87 @@
+//! 仅在调试构建中启用的聊天请求体检查。
+//!
+//! 在发送请求之前检查请求体中常见的可疑配置，并通过`tracing`输出警告。
+//! 该检查只用于在开发阶段尽早发现问题，不会阻止请求发送，并且在release构建中会被完全移除。
+
+use crate::common::types::JsonBody;
+use serde_json::Value;
+
+/// 检查聊天请求体并为每个可疑配置输出一条`tracing`警告。
+pub(crate) fn lint_request_body(body: &JsonBody) {
+    for warning in request_body_warnings(body) {
+        tracing::warn!("Suspicious chat request: {}", warning);
+    }
+}
+
+/// 返回聊天请求体中所有可疑配置对应的警告信息。
+fn request_body_warnings(body: &JsonBody) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    // 超出范围的采样参数与`top_logprobs`等问题由`ChatParam::validate`直接拒绝，这里只检查不会被拒绝的配置
+    if body
+        .get("messages")
+        .and_then(Value::as_array)
+        .is_some_and(|messages| messages.is_empty())
+    {
+        warnings.push("`messages` is empty, the API will reject this request".to_string());
+    }
+
+    let has_tools = body
+        .get("tools")
+        .and_then(Value::as_array)
+        .is_some_and(|tools| !tools.is_empty());
+
+    if has_tools && body.get("tool_choice").and_then(Value::as_str) == Some("none") {
+        warnings.push(
+            "`tools` are provided but `tool_choice` is `none`, the tools will never be called"
+                .to_string(),
+        );
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_lint_empty_messages() {
+        let messages = vec![];
+        let inner = ChatParam::new("gpt-4o", &messages).take();
+        let warnings = request_body_warnings(inner.body.as_ref().unwrap());
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`messages` is empty"));
+    }
+
+    #[test]
+    fn test_lint_suspicious_fields() {
+        let messages = vec![user!("hello")];
+        let tool = ChatCompletionToolParam::function(
+            "function_name",
+            "function description",
+            Parameters::object().build().unwrap(),
+        );
+        let inner = ChatParam::new("gpt-4o", &messages)
+            .tools(vec![tool])
+            .tool_choice(ToolChoice::None)
+            .take();
+        let warnings = request_body_warnings(inner.body.as_ref().unwrap());
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`tool_choice`"));
+    }
+
+    #[test]
+    fn test_lint_valid_request() {
+        let messages = vec![user!("hello")];
+        let inner = ChatParam::new("gpt-4o", &messages)
+            .temperature(0.7)
+            .top_p(0.9)
+            .take();
+
+        assert!(request_body_warnings(inner.body.as_ref().unwrap()).is_empty());
+    }
+}
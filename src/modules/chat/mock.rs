@@ -0,0 +1,305 @@
+//! 用于测试的`ChatCompletion`与`ChatCompletionChunk`构造器。
+//!
+//! 仅在启用`test-util`特性（或在本crate的测试中）时可用，用于在不访问真实服务的情况下
+//! 快速构造响应对象。
+
+use super::types::{
+    ChatCompletion, ChatCompletionChunk, ChatCompletionMessage, ChatCompletionToolCall,
+    ChoiceDelta, FinalChoice, FinishReason, Function, StreamChoice,
+};
+use crate::common::types::{CompletionGeneric, CompletionUsage};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
+const MOCK_ID: &str = "chatcmpl-mock";
+const MOCK_MODEL: &str = "mock-model";
+const MOCK_CREATED: i64 = 1_700_000_000;
+
+fn mock_generic<T>(object: &str, choices: Vec<T>) -> CompletionGeneric<T> {
+    CompletionGeneric {
+        created: MOCK_CREATED,
+        id: MOCK_ID.to_string(),
+        model: MOCK_MODEL.to_string(),
+        object: object.to_string(),
+        choices,
+        service_tier: None,
+        system_fingerprint: None,
+        usage: None,
+        extra_fields: None,
+    }
+}
+
+fn mock_usage(prompt_tokens: i64, completion_tokens: i64) -> CompletionUsage {
+    CompletionUsage {
+        completion_tokens,
+        prompt_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+        completion_tokens_details: None,
+        prompt_tokens_details: None,
+        extra_fields: None,
+    }
+}
+
+fn mock_tool_call(index: usize, id: &str, name: &str, arguments: &str) -> ChatCompletionToolCall {
+    ChatCompletionToolCall {
+        index,
+        function: Function::new(id, name, arguments),
+        r#type: "function".to_string(),
+    }
+}
+
+impl<T> CompletionGeneric<T> {
+    /// 设置模型名称。
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+
+    /// 设置令牌使用统计，`total_tokens`为两者之和。
+    pub fn with_usage(mut self, prompt_tokens: i64, completion_tokens: i64) -> Self {
+        self.usage = Some(mock_usage(prompt_tokens, completion_tokens));
+        self
+    }
+}
+
+impl ChatCompletion {
+    /// 构造一个只包含一条助手文本回复的`ChatCompletion`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::ChatCompletion;
+    ///
+    /// let completion = ChatCompletion::mock("Hello!").with_usage(5, 2);
+    /// assert_eq!(completion.content(), Some("Hello!"));
+    /// assert_eq!(completion.usage.unwrap().total_tokens, 7);
+    /// ```
+    pub fn mock(content: &str) -> Self {
+        let message = ChatCompletionMessage {
+            role: "assistant".to_string(),
+            content: Some(content.to_string()),
+            refusal: None,
+            reasoning: None,
+            annotations: None,
+            tool_calls: None,
+            extra_fields: None,
+        };
+        mock_generic(
+            "chat.completion",
+            vec![FinalChoice {
+                index: 0,
+                finish_reason: FinishReason::Stop,
+                message,
+                logprobs: None,
+            }],
+        )
+    }
+
+    /// 为第一个选项追加一个函数调用，并将结束原因设置为`tool_calls`。
+    pub fn with_tool_call(mut self, id: &str, name: &str, arguments: &str) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            let tool_calls = choice.message.tool_calls.get_or_insert_with(Vec::new);
+            tool_calls.push(mock_tool_call(tool_calls.len(), id, name, arguments));
+            choice.finish_reason = FinishReason::ToolCalls;
+        }
+        self
+    }
+
+    /// 设置第一个选项的推理内容。
+    pub fn with_reasoning(mut self, reasoning: &str) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            choice.message.reasoning = Some(reasoning.to_string());
+        }
+        self
+    }
+
+    /// 设置第一个选项的结束原因。
+    pub fn with_finish_reason(mut self, finish_reason: FinishReason) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            choice.finish_reason = finish_reason;
+        }
+        self
+    }
+}
+
+impl ChatCompletionChunk {
+    /// 构造一个只包含一段文本增量的`ChatCompletionChunk`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::ChatCompletionChunk;
+    ///
+    /// let chunk = ChatCompletionChunk::mock("Hel");
+    /// assert_eq!(chunk.content(), Some("Hel"));
+    /// ```
+    pub fn mock(content: &str) -> Self {
+        let delta = ChoiceDelta {
+            content: Some(content.to_string()),
+            refusal: None,
+            reasoning: None,
+            role: Some("assistant".to_string()),
+            tool_calls: None,
+            extra_fields: None,
+        };
+        mock_generic(
+            "chat.completion.chunk",
+            vec![StreamChoice {
+                index: 0,
+                delta,
+                finish_reason: None,
+                logprobs: None,
+            }],
+        )
+    }
+
+    /// 构造一个没有选项、只包含令牌使用统计的数据块，与启用`include_usage`时流的最后一个数据块相同。
+    pub fn mock_usage(prompt_tokens: i64, completion_tokens: i64) -> Self {
+        mock_generic("chat.completion.chunk", Vec::new())
+            .with_usage(prompt_tokens, completion_tokens)
+    }
+
+    /// 为第一个选项追加一个函数调用增量。
+    pub fn with_tool_call(mut self, id: &str, name: &str, arguments: &str) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            let tool_calls = choice.delta.tool_calls.get_or_insert_with(Vec::new);
+            tool_calls.push(mock_tool_call(tool_calls.len(), id, name, arguments));
+        }
+        self
+    }
+
+    /// 设置第一个选项的推理内容增量。
+    pub fn with_reasoning(mut self, reasoning: &str) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            choice.delta.reasoning = Some(reasoning.to_string());
+        }
+        self
+    }
+
+    /// 设置第一个选项的结束原因。
+    pub fn with_finish_reason(mut self, finish_reason: FinishReason) -> Self {
+        if let Some(choice) = self.choices.first_mut() {
+            choice.finish_reason = Some(finish_reason);
+        }
+        self
+    }
+}
+
+impl ChatCompletion {
+    /// 转换为服务端返回的JSON格式，供[`MockTransport`](crate::MockTransport)或模拟HTTP服务器使用。
+    ///
+    /// 只包含本模块的构造器会设置的字段，`logprobs`、`annotations`与令牌明细不会输出。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// let body = ChatCompletion::mock("Hello!").to_mock_json();
+    /// assert_eq!(body["choices"][0]["message"]["content"], "Hello!");
+    /// ```
+    pub fn to_mock_json(&self) -> Value {
+        generic_json(self, |choice| {
+            let message = &choice.message;
+            let mut message_json = Map::new();
+            message_json.insert("role".to_string(), json!(message.role));
+            message_json.insert("content".to_string(), json!(message.content));
+            insert_some(&mut message_json, "refusal", message.refusal.as_deref());
+            insert_some(&mut message_json, "reasoning", message.reasoning.as_deref());
+            if let Some(tool_calls) = &message.tool_calls {
+                message_json.insert("tool_calls".to_string(), tool_calls_json(tool_calls));
+            }
+            extend_extra_fields(&mut message_json, message.extra_fields.as_ref());
+
+            json!({
+                "index": choice.index,
+                "message": message_json,
+                "finish_reason": choice.finish_reason.as_str(),
+                "logprobs": null,
+            })
+        })
+    }
+}
+
+impl ChatCompletionChunk {
+    /// 转换为服务端在SSE事件中发送的JSON格式，供[`MockTransport`](crate::MockTransport)或模拟HTTP服务器使用。
+    pub fn to_mock_json(&self) -> Value {
+        generic_json(self, |choice| {
+            let delta = &choice.delta;
+            let mut delta_json = Map::new();
+            insert_some(&mut delta_json, "role", delta.role.as_deref());
+            insert_some(&mut delta_json, "content", delta.content.as_deref());
+            insert_some(&mut delta_json, "refusal", delta.refusal.as_deref());
+            insert_some(&mut delta_json, "reasoning", delta.reasoning.as_deref());
+            if let Some(tool_calls) = &delta.tool_calls {
+                delta_json.insert("tool_calls".to_string(), tool_calls_json(tool_calls));
+            }
+            extend_extra_fields(&mut delta_json, delta.extra_fields.as_ref());
+
+            json!({
+                "index": choice.index,
+                "delta": delta_json,
+                "finish_reason": choice.finish_reason.as_ref().map(FinishReason::as_str),
+                "logprobs": null,
+            })
+        })
+    }
+}
+
+fn generic_json<T>(completion: &CompletionGeneric<T>, choice_json: impl Fn(&T) -> Value) -> Value {
+    let mut value = Map::new();
+    value.insert("id".to_string(), json!(completion.id));
+    value.insert("object".to_string(), json!(completion.object));
+    value.insert("created".to_string(), json!(completion.created));
+    value.insert("model".to_string(), json!(completion.model));
+    value.insert(
+        "choices".to_string(),
+        completion.choices.iter().map(choice_json).collect(),
+    );
+    insert_some(
+        &mut value,
+        "system_fingerprint",
+        completion.system_fingerprint.as_deref(),
+    );
+    if let Some(usage) = &completion.usage {
+        value.insert(
+            "usage".to_string(),
+            json!({
+                "prompt_tokens": usage.prompt_tokens,
+                "completion_tokens": usage.completion_tokens,
+                "total_tokens": usage.total_tokens,
+            }),
+        );
+    }
+    extend_extra_fields(&mut value, completion.extra_fields.as_ref());
+    Value::Object(value)
+}
+
+fn tool_calls_json(tool_calls: &[ChatCompletionToolCall]) -> Value {
+    tool_calls
+        .iter()
+        .map(|tool_call| {
+            json!({
+                "index": tool_call.index,
+                "id": tool_call.function.id,
+                "type": tool_call.r#type,
+                "function": tool_call.function,
+            })
+        })
+        .collect()
+}
+
+fn insert_some(map: &mut Map<String, Value>, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        map.insert(key.to_string(), json!(value));
+    }
+}
+
+fn extend_extra_fields(
+    map: &mut Map<String, Value>,
+    extra_fields: Option<&HashMap<String, Value>>,
+) {
+    if let Some(extra_fields) = extra_fields {
+        map.extend(extra_fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
@@ -1,9 +1,15 @@
 pub mod handler;
+#[cfg(debug_assertions)]
+mod lint;
+#[cfg(any(test, feature = "test-util"))]
+mod mock;
 pub mod params;
+pub mod stream;
 pub mod tool_parameters;
 pub mod types;
 
 pub use handler::Chat;
 pub use params::ChatParam;
-pub use tool_parameters::Parameters;
+pub use stream::{ChatCompletionAccumulator, ChatCompletionStreamExt, ReasoningEvent, StreamUsage};
+pub use tool_parameters::{Parameters, ToolParameters};
 pub use types::*;
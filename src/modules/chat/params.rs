@@ -2,38 +2,83 @@ use super::types::{
     ChatCompletionMessageParam, ChatCompletionPredictionContentParam, ChatCompletionToolParam,
     Modality, ReasoningEffort, ToolChoice,
 };
-use crate::common::types::{InParam, JsonBody, RetryCount, ServiceTier, Timeout};
-use http::{
-    HeaderValue,
-    header::{IntoHeaderName, USER_AGENT},
-};
+use crate::common::params::{impl_request_options, impl_user_param};
+use crate::common::types::{BaseUrl, InParam, JsonBody, Penalty, ServiceTier, Temperature};
+use crate::error::{OpenAIError, RequestError};
+use serde::Serialize;
 use serde_json::Value;
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
+use tokio_util::sync::CancellationToken;
 
+#[derive(Clone)]
 pub struct ChatParam {
     inner: InParam,
 }
 
+impl_request_options!(ChatParam);
+impl_user_param!(ChatParam);
+
 impl ChatParam {
     #[doc = include_str!("../../docs/chat_param.md")]
     pub fn new(model: &str, messages: &Vec<ChatCompletionMessageParam>) -> Self {
+        Self::with_messages(model, messages).expect(MESSAGES_SERIALIZE_INFALLIBLE)
+    }
+
+    /// 与[`ChatParam::new`]相同，但会立即检查参数，而不是等到发送请求时才报错。
+    ///
+    /// 除[`ChatParam::validate`]的检查外，`model`与`messages`均不能为空。
+    /// 之后通过builder方法设置的参数仍会在发送前再次检查。
+    ///
+    /// # 错误
+    ///
+    /// 参数无效时返回[`RequestError::Validation`]，其中包含参数名、允许的取值与实际的值。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// fn main() {
+    ///     let messages = vec![user!("hello")];
+    ///     assert!(ChatParam::try_new("gpt-4o", &messages).is_ok());
+    ///     assert!(ChatParam::try_new("", &messages).is_err());
+    ///     assert!(ChatParam::try_new("gpt-4o", &vec![]).is_err());
+    /// }
+    /// ```
+    pub fn try_new(
+        model: &str,
+        messages: &Vec<ChatCompletionMessageParam>,
+    ) -> Result<Self, OpenAIError> {
+        if model.is_empty() {
+            return Err(validation_error("model", "a non-empty model name", "\"\""));
+        }
+        if messages.is_empty() {
+            return Err(validation_error("messages", "at least one message", "[]"));
+        }
+        let param = Self::with_messages(model, messages)?;
+        param.validate()?;
+        Ok(param)
+    }
+
+    fn with_messages(
+        model: &str,
+        messages: &Vec<ChatCompletionMessageParam>,
+    ) -> Result<Self, OpenAIError> {
+        let mut body = JsonBody::new();
+        body.insert("model".to_string(), Value::String(model.to_string()));
+        body.insert("messages".to_string(), messages_to_value(messages)?);
+
         let mut inner = InParam::new();
-        inner.body = Some(JsonBody::new());
-        let mut_body = inner.body.as_mut().unwrap();
-        mut_body.insert("model".to_string(), serde_json::to_value(model).unwrap());
-        mut_body.insert(
-            "messages".to_string(),
-            serde_json::to_value(messages).unwrap(),
-        );
-        ChatParam { inner }
+        inner.body = Some(body);
+        Ok(ChatParam { inner })
     }
 
     /// 频率惩罚。一个介于-2.0和2.0之间的数值。正值根据文本中现有频率对新令牌进行惩罚，
     /// 降低模型逐字重复同一行的可能性。
-    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+    pub fn frequency_penalty(mut self, frequency_penalty: impl Into<Penalty>) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "frequency_penalty".to_string(),
-            serde_json::to_value(frequency_penalty).unwrap(),
+            serde_json::to_value(frequency_penalty.into().get()).unwrap(),
         );
         self
     }
@@ -61,6 +106,19 @@ impl ChatParam {
         self
     }
 
+    /// 流式选项。是否在流式响应结束前额外发送一个包含令牌使用统计的数据块。
+    ///
+    /// 仅对`create_stream`生效，`create`发送请求时会移除此字段。启用后，最后一个数据块的`choices`为空，`usage`字段包含整个请求的令牌使用统计，
+    /// 可通过[`ChatCompletionStreamExt::collect_with_usage`](super::ChatCompletionStreamExt::collect_with_usage)
+    /// 或[`StreamUsage`](super::StreamUsage)获取。
+    pub fn include_usage(mut self, include_usage: bool) -> Self {
+        self.inner.body.as_mut().unwrap().insert(
+            "stream_options".to_string(),
+            serde_json::json!({ "include_usage": include_usage }),
+        );
+        self
+    }
+
     /// 输出模态。您希望模型生成的输出类型。
     ///
     /// 大多数模型都能够生成文本，这是默认值：`["text"]`。
@@ -98,6 +156,11 @@ impl ChatParam {
     }
 
     /// 并行工具调用。是否在工具使用期间启用并行函数调用。
+    ///
+    /// 未调用此方法时请求体中不会包含该字段，由服务端使用默认行为；
+    /// 部分服务或模型不支持该字段，此时不要调用此方法。
+    /// 禁用后流式响应中的每个工具调用都以`index`为0依次发送，合并时仍会保持为独立的工具调用；
+    /// 服务端回显的设置可通过[`ChatCompletion::parallel_tool_calls`](super::ChatCompletion::parallel_tool_calls)读取。
     pub fn parallel_tool_calls(mut self, parallel_tool_calls: bool) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "parallel_tool_calls".to_string(),
@@ -108,10 +171,10 @@ impl ChatParam {
 
     /// 存在惩罚。一个介于-2.0和2.0之间的数值。正值根据新令牌是否出现在迄今为止的文本中进行惩罚，
     /// 增加模型谈论新话题的可能性。
-    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+    pub fn presence_penalty(mut self, presence_penalty: impl Into<Penalty>) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "presence_penalty".to_string(),
-            serde_json::to_value(presence_penalty).unwrap(),
+            serde_json::to_value(presence_penalty.into().get()).unwrap(),
         );
         self
     }
@@ -119,7 +182,7 @@ impl ChatParam {
     /// 生成选项数。为每个输入消息生成多少个聊天补全选项。
     ///
     /// 请注意，将根据所有选项生成的令牌总数向您收费。
-    /// 将`n`保持在`1`以最小化成本。
+    /// 将`n`保持在`1`以最小化成本。`n`必须大于等于1，否则请求在发送前会返回错误。
     pub fn n(mut self, n: i32) -> Self {
         self.inner
             .body
@@ -148,25 +211,14 @@ impl ChatParam {
     /// 较高的值如0.8会使输出更加随机，而较低的值如0.2
     /// 会使输出更加集中和确定。我们通常建议修改此参数或`top_p`，
     /// 但不建议同时修改两者。
-    pub fn temperature(mut self, temperature: f32) -> Self {
+    pub fn temperature(mut self, temperature: impl Into<Temperature>) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "temperature".to_string(),
-            serde_json::to_value(temperature).unwrap(),
+            serde_json::to_value(temperature.into().get()).unwrap(),
         );
         self
     }
 
-    /// 终端用户标识符。代表您的终端用户的唯一标识符，这可以帮助OpenAI
-    /// 监控和检测滥用行为。
-    pub fn user(mut self, user: String) -> Self {
-        self.inner
-            .body
-            .as_mut()
-            .unwrap()
-            .insert("user".to_string(), serde_json::to_value(user).unwrap());
-        self
-    }
-
     /// 最可能令牌数。一个介于0和20之间的整数，指定在每个令牌位置返回的最可能令牌的数量，
     /// 每个令牌都有相关的对数概率。
     /// 如果使用此参数，`logprobs`必须设置为`true`。
@@ -243,35 +295,64 @@ impl ChatParam {
         self
     }
 
-    /// 重试次数。HTTP请求重试次数，覆盖客户端的全局设置。
+    /// 提供商路由偏好。**仅OpenRouter** - 控制请求被路由到哪些提供商。
     ///
-    /// 此字段不会在请求体中序列化。
-    pub fn retry_count(mut self, retry_count: usize) -> Self {
-        self.inner.extensions.insert(RetryCount(retry_count));
+    /// 例如`{"order": ["openai", "together"], "allow_fallbacks": false}`。
+    pub fn provider<V: Into<Value>>(mut self, provider: V) -> Self {
+        self.inner
+            .body
+            .as_mut()
+            .unwrap()
+            .insert("provider".to_string(), provider.into());
         self
     }
 
-    /// 超时时间。HTTP请求超时时间，覆盖客户端的全局设置。
+    /// 备用模型列表。**仅OpenRouter** - 当主模型不可用时按顺序尝试的模型。
     ///
+    /// 序列化为请求体中的`models`字段。
+    pub fn models_fallback(mut self, models: Vec<String>) -> Self {
+        self.inner
+            .body
+            .as_mut()
+            .unwrap()
+            .insert("models".to_string(), serde_json::to_value(models).unwrap());
+        self
+    }
+
+    /// 基础URL。仅为本次请求使用的基础URL，覆盖客户端的全局设置。
+    ///
+    /// 适用于将单个请求路由到其他端点（例如区域镜像），而无需修改共享客户端的配置。
     /// 此字段不会在请求体中序列化。
-    pub fn timeout(mut self, timeout: Duration) -> Self {
-        self.inner.extensions.insert(Timeout(timeout));
+    pub fn base_url<T: Into<String>>(mut self, base_url: T) -> Self {
+        self.inner.extensions.insert(BaseUrl(base_url.into()));
         self
     }
 
-    /// 用户代理。HTTP请求User-Agent，覆盖客户端的全局设置。
-    pub fn user_agent(mut self, user_agent: HeaderValue) -> Self {
-        self.inner.headers.insert(USER_AGENT, user_agent);
+    /// 取消令牌。用于主动终止进行中的请求。
+    ///
+    /// 对于`create`与`create_with_response`，令牌被取消时立即中止等待中的请求并返回
+    /// [`RequestError::Cancelled`](crate::error::RequestError::Cancelled)。
+    /// 对于`create_stream`，令牌被取消后，后台读取任务会立即退出并关闭底层连接，返回的流随之结束；
+    /// 丢弃返回的流同样会终止读取，此方法适用于流被其他位置持有的情况。
+    /// 此字段不会在请求体中序列化。
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.inner.extensions.insert(token);
         self
     }
 
-    /// 设置HTTP请求头信息。
-    pub fn header<K: IntoHeaderName>(mut self, key: K, val: HeaderValue) -> Self {
-        self.inner.headers.insert(key, val);
+    /// 添加URL查询参数，例如Azure OpenAI要求的`api-version`。
+    ///
+    /// 此字段不会在请求体中序列化。与[`ConfigBuilder::query`](crate::ConfigBuilder::query)
+    /// 设置的全局参数同名时，以此处的值为准。
+    pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, val: V) -> Self {
+        self.inner.query.push((key.into(), val.into()));
         self
     }
 
     /// 向请求体添加额外的JSON属性。
+    ///
+    /// `stream`字段由调用的方法决定：`create`始终发送`false`，`create_stream`始终发送`true`，
+    /// 此处设置的值会被覆盖。
     pub fn body<K: Into<String>, V: Into<Value>>(mut self, key: K, val: V) -> Self {
         self.inner
             .body
@@ -283,6 +364,170 @@ impl ChatParam {
 }
 
 impl ChatParam {
+    /// 在`messages`末尾追加一条消息，无需重新序列化已有的对话历史。
+    ///
+    /// 适用于多轮对话或智能体循环：保留同一个`ChatParam`，每轮追加助手回复与新的用户消息，
+    /// 再以其克隆发送请求。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// fn main() {
+    ///     let messages = vec![system!("You are a helpful assistant.")];
+    ///     let mut param = ChatParam::new("gpt-4o", &messages);
+    ///     param
+    ///         .push_message(user!("Hello"))
+    ///         .push_message(assistant!("Hi! How can I help you?"));
+    ///     assert_eq!(param.get("messages").unwrap().as_array().unwrap().len(), 3);
+    /// }
+    /// ```
+    pub fn push_message(&mut self, message: ChatCompletionMessageParam) -> &mut Self {
+        let message = messages_to_value(&message).expect(MESSAGES_SERIALIZE_INFALLIBLE);
+        self.messages_mut().push(message);
+        self
+    }
+
+    /// 按顺序在`messages`末尾追加多条消息。
+    pub fn extend_messages<I>(&mut self, messages: I) -> &mut Self
+    where
+        I: IntoIterator<Item = ChatCompletionMessageParam>,
+    {
+        let values = messages
+            .into_iter()
+            .map(|message| messages_to_value(&message).expect(MESSAGES_SERIALIZE_INFALLIBLE));
+        self.messages_mut().extend(values);
+        self
+    }
+
+    fn messages_mut(&mut self) -> &mut Vec<Value> {
+        let messages = self
+            .inner
+            .body
+            .as_mut()
+            .unwrap()
+            .entry("messages".to_string())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if !messages.is_array() {
+            *messages = Value::Array(Vec::new());
+        }
+        messages.as_array_mut().unwrap()
+    }
+
+    /// 读取请求体中指定字段的当前值。
+    ///
+    /// 便于中间件或框架在不重新解析请求的情况下检查已设置的参数。
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.inner.body.as_ref().and_then(|body| body.get(key))
+    }
+
+    /// 以`f64`读取请求体中的数值字段，例如`temperature`、`frequency_penalty`。
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(Value::as_f64)
+    }
+
+    /// 以`i64`读取请求体中的整数字段，例如`n`、`max_completion_tokens`。
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get(key).and_then(Value::as_i64)
+    }
+
+    /// 以`bool`读取请求体中的布尔字段，例如`logprobs`、`parallel_tool_calls`。
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(Value::as_bool)
+    }
+
+    /// 以字符串切片读取请求体中的字符串字段，例如`model`、`user`。
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    /// 检查请求体中是否已设置指定字段。
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+fn validation_error(field: &str, expected: &str, actual: impl ToString) -> OpenAIError {
+    RequestError::validation(field, expected, actual).into()
+}
+
+/// 消息类型只包含字符串键与可序列化的字段，序列化为JSON值不会失败。
+const MESSAGES_SERIALIZE_INFALLIBLE: &str = "Serializing chat messages to a JSON value cannot fail";
+
+/// 将消息序列化为JSON值，失败时返回[`RequestError::InvalidParameter`]。
+fn messages_to_value<T: Serialize + ?Sized>(messages: &T) -> Result<Value, OpenAIError> {
+    serde_json::to_value(messages).map_err(|e| {
+        RequestError::InvalidParameter(format!("Failed to serialize `messages`: {e}")).into()
+    })
+}
+
+impl ChatParam {
+    /// 在发送前检查参数是否有效。
+    ///
+    /// [`Chat::create`](super::Chat::create)和[`Chat::create_stream`](super::Chat::create_stream)
+    /// 会在发送请求前自动调用此方法，也可以提前调用以尽早发现错误。检查以下组合：
+    /// - `temperature`必须在`0..=2`之间；
+    /// - `top_p`必须在`0..=1`之间；
+    /// - `frequency_penalty`和`presence_penalty`必须在`-2..=2`之间；
+    /// - `n`必须至少为1；
+    /// - 设置`top_logprobs`时`logprobs`必须为`true`。
+    ///
+    /// # 错误
+    ///
+    /// 参数无效时返回[`RequestError::Validation`]，其中包含参数名、允许的取值与实际的值。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// fn main() {
+    ///     let messages = vec![user!("hello")];
+    ///     let param = ChatParam::new("gpt-4o", &messages).temperature(3.0);
+    ///     assert!(param.validate().is_err());
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(temperature) = self.get_f64("temperature")
+            && !(0.0..=2.0).contains(&temperature)
+        {
+            return Err(validation_error(
+                "temperature",
+                "a value between 0 and 2",
+                temperature,
+            ));
+        }
+        if let Some(top_p) = self.get_f64("top_p")
+            && !(0.0..=1.0).contains(&top_p)
+        {
+            return Err(validation_error("top_p", "a value between 0 and 1", top_p));
+        }
+        for key in ["frequency_penalty", "presence_penalty"] {
+            if let Some(penalty) = self.get_f64(key)
+                && !(-2.0..=2.0).contains(&penalty)
+            {
+                return Err(validation_error(key, "a value between -2 and 2", penalty));
+            }
+        }
+        if let Some(n) = self.get_i64("n")
+            && n < 1
+        {
+            return Err(validation_error("n", "a value of at least 1", n));
+        }
+        if self.get("top_logprobs").is_some() && self.get_bool("logprobs") != Some(true) {
+            let logprobs = self
+                .get("logprobs")
+                .map_or_else(|| "unset".to_string(), Value::to_string);
+            return Err(validation_error(
+                "logprobs",
+                "`true` when `top_logprobs` is set",
+                logprobs,
+            ));
+        }
+        Ok(())
+    }
+
     pub(crate) fn take(self) -> InParam {
         self.inner
     }
@@ -290,6 +535,7 @@ impl ChatParam {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::RequestError;
     use crate::*;
 
     #[test]
@@ -373,4 +619,175 @@ mod tests {
         let temp_right = right_map.get("temperature").unwrap().as_f64().unwrap();
         assert!((temp_left - temp_right).abs() < 1e-8);
     }
+
+    #[test]
+    fn test_request_params_openrouter_routing() {
+        let messages = vec![user!("user message")];
+        let request = ChatParam::new("openai/gpt-4o", &messages)
+            .provider(serde_json::json!({
+                "order": ["openai", "together"],
+                "allow_fallbacks": false
+            }))
+            .models_fallback(vec![
+                "anthropic/claude-3.5-sonnet".to_string(),
+                "gryphe/mythomax-l2-13b".to_string(),
+            ]);
+
+        let inner = request.take();
+        let body = inner.body.unwrap();
+        assert_eq!(
+            body.get("provider"),
+            Some(&serde_json::json!({
+                "order": ["openai", "together"],
+                "allow_fallbacks": false
+            }))
+        );
+        assert_eq!(
+            body.get("models"),
+            Some(&serde_json::json!([
+                "anthropic/claude-3.5-sonnet",
+                "gryphe/mythomax-l2-13b"
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_request_params_parallel_tool_calls() {
+        let messages = vec![user!("user message")];
+
+        let body = ChatParam::new("gpt-4o", &messages).take().body.unwrap();
+        assert!(!body.contains_key("parallel_tool_calls"));
+
+        let body = ChatParam::new("gpt-4o", &messages)
+            .parallel_tool_calls(false)
+            .take()
+            .body
+            .unwrap();
+        assert_eq!(
+            body.get("parallel_tool_calls"),
+            Some(&serde_json::json!(false))
+        );
+    }
+
+    #[test]
+    fn test_request_params_validate_n() {
+        let messages = vec![];
+        assert!(ChatParam::new("gpt-4o", &messages).validate().is_ok());
+        assert!(ChatParam::new("gpt-4o", &messages).n(2).validate().is_ok());
+
+        let error = ChatParam::new("gpt-4o", &messages).n(0).validate();
+        match error {
+            Err(OpenAIError::Request(RequestError::Validation {
+                field,
+                expected,
+                actual,
+            })) => {
+                assert_eq!(field, "n");
+                assert_eq!(expected, "a value of at least 1");
+                assert_eq!(actual, "0");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_request_params_validate_ranges() {
+        let messages = vec![user!("hello")];
+        let param = || ChatParam::new("gpt-4o", &messages);
+
+        assert!(param().temperature(0.0).validate().is_ok());
+        assert!(param().temperature(2.0).validate().is_ok());
+        assert!(param().temperature(2.5).validate().is_err());
+        assert!(param().temperature(-0.1).validate().is_err());
+
+        assert!(param().top_p(0.0).validate().is_ok());
+        assert!(param().top_p(1.0).validate().is_ok());
+        assert!(param().top_p(1.5).validate().is_err());
+
+        assert!(param().frequency_penalty(-2.0).validate().is_ok());
+        assert!(param().frequency_penalty(2.0).validate().is_ok());
+        assert!(param().frequency_penalty(2.1).validate().is_err());
+        assert!(param().frequency_penalty(-2.1).validate().is_err());
+
+        assert!(param().presence_penalty(-2.0).validate().is_ok());
+        assert!(param().presence_penalty(2.0).validate().is_ok());
+        assert!(param().presence_penalty(2.1).validate().is_err());
+        assert!(param().presence_penalty(-2.1).validate().is_err());
+
+        let error = param().presence_penalty(3.0).validate().unwrap_err();
+        assert!(error.message().contains("presence_penalty"));
+
+        assert!(param().top_logprobs(3).validate().is_err());
+        assert!(param().logprobs(false).top_logprobs(3).validate().is_err());
+        assert!(param().logprobs(true).top_logprobs(3).validate().is_ok());
+
+        let error = param().top_p(-1.0).validate().unwrap_err();
+        assert!(error.message().contains("top_p"));
+
+        let error = param().temperature(2.5).validate().unwrap_err();
+        assert_eq!(
+            error.message(),
+            "Invalid value for `temperature`: expected a value between 0 and 2, got 2.5"
+        );
+
+        let error = param().top_logprobs(3).validate().unwrap_err();
+        assert!(matches!(
+            error,
+            OpenAIError::Request(RequestError::Validation { ref field, ref actual, .. })
+                if field == "logprobs" && actual == "unset"
+        ));
+    }
+
+    #[test]
+    fn test_request_params_try_new() {
+        let messages = vec![system!("system message"), user!("hello")];
+        let param = ChatParam::try_new("gpt-4o", &messages).unwrap();
+        assert_eq!(param.get_str("model"), Some("gpt-4o"));
+        assert_eq!(param.get("messages").unwrap().as_array().unwrap().len(), 2);
+
+        let error = ChatParam::try_new("", &messages).err().unwrap();
+        assert!(matches!(
+            error,
+            OpenAIError::Request(RequestError::Validation { ref field, .. }) if field == "model"
+        ));
+        assert!(error.message().contains("model"));
+
+        let error = ChatParam::try_new("gpt-4o", &vec![]).err().unwrap();
+        assert!(error.message().contains("messages"));
+
+        // 序列化失败时返回错误而不是panic
+        struct Unserializable;
+        impl serde::Serialize for Unserializable {
+            fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("unsupported message"))
+            }
+        }
+        let error = super::messages_to_value(&[Unserializable]).unwrap_err();
+        assert!(matches!(
+            error,
+            OpenAIError::Request(RequestError::InvalidParameter(ref message))
+                if message.contains("unsupported message")
+        ));
+    }
+
+    #[test]
+    fn test_request_params_getters() {
+        let messages = vec![user!("user message")];
+        let request = ChatParam::new("gpt-4o", &messages)
+            .frequency_penalty(0.5)
+            .n(2)
+            .logprobs(true)
+            .user("user-1".to_string());
+
+        assert_eq!(request.get_str("model"), Some("gpt-4o"));
+        assert_eq!(request.get_f64("frequency_penalty"), Some(0.5));
+        assert_eq!(request.get_i64("n"), Some(2));
+        assert_eq!(request.get_bool("logprobs"), Some(true));
+        assert_eq!(request.get_str("user"), Some("user-1"));
+        assert!(request.contains("messages"));
+
+        assert_eq!(request.get_f64("temperature"), None);
+        assert_eq!(request.get_str("n"), None);
+        assert!(!request.contains("tools"));
+    }
 }
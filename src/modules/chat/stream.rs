@@ -0,0 +1,319 @@
+//! 流式聊天补全的辅助工具。
+
+use super::types::{ChatCompletion, ChatCompletionChunk, FinalChoice, StreamChoice};
+use crate::common::types::CompletionUsage;
+use crate::error::OpenAIError;
+use futures::{Future, Stream, StreamExt};
+
+/// 在消费流式响应的过程中收集令牌使用统计。
+///
+/// 启用`stream_options.include_usage`后，服务端会在流的末尾发送一个只包含`usage`的数据块。
+/// `StreamUsage`会记录最后一个非空的`usage`，流结束后即可读取整个请求的令牌使用情况。
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use openai4rs::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = OpenAI::from_env()?;
+///     let messages = vec![user!("hello")];
+///     let mut stream = client
+///         .chat()
+///         .create_stream(ChatParam::new("gpt-4o", &messages).include_usage(true))
+///         .await?;
+///
+///     let mut usage = StreamUsage::new();
+///     while let Some(chunk) = stream.next().await {
+///         let chunk = chunk?;
+///         usage.record(&chunk);
+///         if let Some(content) = chunk.content() {
+///             print!("{}", content);
+///         }
+///     }
+///     println!("\ntotal tokens: {:?}", usage.total_tokens());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamUsage {
+    usage: Option<CompletionUsage>,
+}
+
+impl StreamUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录数据块中的`usage`。空的`usage`（缺失或全部为0）会被忽略。
+    pub fn record(&mut self, chunk: &ChatCompletionChunk) {
+        if let Some(usage) = &chunk.usage
+            && !usage.is_empty()
+        {
+            self.usage = Some(usage.clone());
+        }
+    }
+
+    /// 最后记录的令牌使用统计。
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.usage.as_ref()
+    }
+
+    pub fn prompt_tokens(&self) -> Option<i64> {
+        self.usage.as_ref().map(|usage| usage.prompt_tokens)
+    }
+
+    pub fn completion_tokens(&self) -> Option<i64> {
+        self.usage.as_ref().map(|usage| usage.completion_tokens)
+    }
+
+    pub fn total_tokens(&self) -> Option<i64> {
+        self.usage.as_ref().map(|usage| usage.total_tokens)
+    }
+
+    pub fn into_inner(self) -> Option<CompletionUsage> {
+        self.usage
+    }
+}
+
+/// 将流式数据块按`choice.index`累积为完整的[`ChatCompletion`]。
+///
+/// 当`n > 1`时，不同选择的增量会交错到达。累积器为每个`index`分别合并增量，
+/// 因此每个选择都能独立地组装出完整的消息、工具调用和结束原因。
+///
+/// # 示例
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use openai4rs::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = OpenAI::from_env()?;
+///     let messages = vec![user!("hello")];
+///     let mut stream = client
+///         .chat()
+///         .create_stream(ChatParam::new("gpt-4o", &messages).n(2))
+///         .await?;
+///
+///     let mut accumulator = ChatCompletionAccumulator::new();
+///     while let Some(chunk) = stream.next().await {
+///         accumulator.push(chunk?);
+///     }
+///     if let Some(completion) = accumulator.finish() {
+///         for (index, content) in completion.all_content() {
+///             println!("[{index}] {content}");
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChatCompletionAccumulator {
+    /// 第一个数据块，保存`id`、`model`等元数据，后续数据块通过[`ChatCompletionChunk::merge`]合并到其中
+    completion: Option<ChatCompletionChunk>,
+}
+
+impl ChatCompletionAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 合并一个数据块。
+    pub fn push(&mut self, chunk: ChatCompletionChunk) {
+        match self.completion.as_mut() {
+            Some(completion) => completion.merge(chunk),
+            None => self.completion = Some(chunk),
+        }
+    }
+
+    /// 当前已累积的选择，按首次出现的顺序排列。
+    pub fn choices(&self) -> &[StreamChoice] {
+        self.completion
+            .as_ref()
+            .map(|completion| completion.choices.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// 结束累积，返回按`index`排序的完整聊天补全。未收到任何数据块时返回`None`。
+    pub fn finish(self) -> Option<ChatCompletion> {
+        let chunk = self.completion?;
+        let mut choices: Vec<FinalChoice> = chunk.choices.into_iter().map(Into::into).collect();
+        choices.sort_by_key(|choice| choice.index);
+
+        Some(ChatCompletion {
+            created: chunk.created,
+            id: chunk.id,
+            model: chunk.model,
+            object: "chat.completion".to_string(),
+            choices,
+            service_tier: chunk.service_tier,
+            system_fingerprint: chunk.system_fingerprint,
+            usage: chunk.usage.filter(|usage| !usage.is_empty()),
+            extra_fields: chunk.extra_fields,
+        })
+    }
+}
+
+/// [`ChatCompletionStreamExt::reasoning_then_content`]产生的事件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReasoningEvent {
+    /// 推理（思考）内容的增量。
+    Reasoning(String),
+    /// 推理结束。在推理内容之后收到第一段回复内容时、该回复内容之前发送。
+    ReasoningDone,
+    /// 回复内容的增量。
+    Content(String),
+}
+
+/// 为聊天补全流提供的扩展方法。
+pub trait ChatCompletionStreamExt:
+    Stream<Item = Result<ChatCompletionChunk, OpenAIError>> + Sized
+{
+    /// 消费整个流，返回所有数据块以及收集到的令牌使用统计。
+    ///
+    /// 遇到第一个错误时立即返回该错误。
+    fn collect_with_usage(
+        self,
+    ) -> impl Future<Output = Result<(Vec<ChatCompletionChunk>, StreamUsage), OpenAIError>> + Send
+    where
+        Self: Send,
+    {
+        async move {
+            let mut stream = std::pin::pin!(self);
+            let mut chunks = Vec::new();
+            let mut usage = StreamUsage::new();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk?;
+                usage.record(&chunk);
+                chunks.push(chunk);
+            }
+            Ok((chunks, usage))
+        }
+    }
+
+    /// 消费整个流，并按`choice.index`将所有数据块累积为完整的聊天补全。
+    ///
+    /// 参见[`ChatCompletionAccumulator`]。流为空时返回`Ok(None)`，遇到第一个错误时立即返回该错误。
+    fn collect_completion(
+        self,
+    ) -> impl Future<Output = Result<Option<ChatCompletion>, OpenAIError>> + Send
+    where
+        Self: Send,
+    {
+        async move {
+            let mut stream = std::pin::pin!(self);
+            let mut accumulator = ChatCompletionAccumulator::new();
+            while let Some(chunk) = stream.next().await {
+                accumulator.push(chunk?);
+            }
+            Ok(accumulator.finish())
+        }
+    }
+
+    /// 将流转换为推理内容与回复内容分开的事件流，便于分别渲染“思考”部分与最终回复。
+    ///
+    /// 只处理第一个选择，不包含内容的数据块（例如只有`usage`或工具调用的数据块）会被跳过。
+    /// 在推理内容之后收到第一段回复内容时，先发送[`ReasoningEvent::ReasoningDone`]；
+    /// 没有推理内容的流不会发送该事件。错误会原样传递。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use openai4rs::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenAI::from_env()?;
+    ///     let messages = vec![user!("9.11 and 9.8, which is greater?")];
+    ///     let mut events = client
+    ///         .chat()
+    ///         .create_stream(ChatParam::new("deepseek-reasoner", &messages))
+    ///         .await?
+    ///         .reasoning_then_content();
+    ///
+    ///     while let Some(event) = events.next().await {
+    ///         match event? {
+    ///             ReasoningEvent::Reasoning(text) => print!("{text}"),
+    ///             ReasoningEvent::ReasoningDone => println!("\n--- answer ---"),
+    ///             ReasoningEvent::Content(text) => print!("{text}"),
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn reasoning_then_content(
+        self,
+    ) -> impl Stream<Item = Result<ReasoningEvent, OpenAIError>> + Send
+    where
+        Self: Send,
+    {
+        let mut in_reasoning = false;
+        self.flat_map(move |chunk| {
+            let mut events = Vec::new();
+            match chunk {
+                Ok(chunk) => {
+                    if let Some(reasoning) = chunk.reasoning().filter(|r| !r.is_empty()) {
+                        in_reasoning = true;
+                        events.push(Ok(ReasoningEvent::Reasoning(reasoning.to_string())));
+                    }
+                    if let Some(content) = chunk.content().filter(|c| !c.is_empty()) {
+                        if in_reasoning {
+                            in_reasoning = false;
+                            events.push(Ok(ReasoningEvent::ReasoningDone));
+                        }
+                        events.push(Ok(ReasoningEvent::Content(content.to_string())));
+                    }
+                }
+                Err(error) => events.push(Err(error)),
+            }
+            futures::stream::iter(events)
+        })
+    }
+}
+
+impl<S> ChatCompletionStreamExt for S where
+    S: Stream<Item = Result<ChatCompletionChunk, OpenAIError>> + Sized
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FinishReason;
+
+    #[tokio::test]
+    async fn test_collect_with_usage() {
+        let chunks = vec![
+            Ok(ChatCompletionChunk::mock("Hello")),
+            Ok(ChatCompletionChunk::mock(" world").with_finish_reason(FinishReason::Stop)),
+            Ok(ChatCompletionChunk::mock_usage(9, 2)),
+        ];
+
+        let (chunks, usage) = futures::stream::iter(chunks)
+            .collect_with_usage()
+            .await
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(usage.prompt_tokens(), Some(9));
+        assert_eq!(usage.completion_tokens(), Some(2));
+        assert_eq!(usage.total_tokens(), Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_collect_without_usage() {
+        let chunks = vec![Ok(ChatCompletionChunk::mock("Hello"))];
+
+        let (_, usage) = futures::stream::iter(chunks)
+            .collect_with_usage()
+            .await
+            .unwrap();
+
+        assert!(usage.usage().is_none());
+        assert_eq!(usage.total_tokens(), None);
+    }
+}
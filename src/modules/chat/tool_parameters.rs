@@ -369,6 +369,20 @@ impl BooleanParametersBuilder {
 }
 
 impl Parameters {
+    /// 设置参数的描述，覆盖已有的描述。
+    pub fn with_description(mut self, description: &str) -> Parameters {
+        let slot = match &mut self {
+            Parameters::Object(params) => &mut params.description,
+            Parameters::Array(params) => &mut params.description,
+            Parameters::String(params) => &mut params.description,
+            Parameters::Number(params) => &mut params.description,
+            Parameters::Integer(params) => &mut params.description,
+            Parameters::Boolean(params) => &mut params.description,
+        };
+        *slot = Some(description.to_string());
+        self
+    }
+
     /// 创建一个新的对象参数构建器。
     pub fn object() -> ObjectParametersBuilder {
         ObjectParametersBuilder::new()
@@ -400,6 +414,105 @@ impl Parameters {
     }
 }
 
+/// 可以描述为工具参数模式的类型。
+///
+/// 通常通过`#[derive(ToolParameters)]`为工具的参数结构体自动实现：字段名、类型和文档注释
+/// 会映射为对应的[`Parameters::Object`]，`Option<T>`字段和带有`#[serde(default)]`的字段不会被标记为必需。
+/// 只包含单元变体的枚举会映射为字符串枚举。派生宏会遵循`#[serde(rename = "...")]`、
+/// `#[serde(rename_all = "...")]`和`#[serde(skip)]`，以便与[`Function::parse_arguments`](super::Function::parse_arguments)
+/// 的反序列化结果保持一致。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::*;
+///
+/// /// 温度单位
+/// #[derive(ToolParameters)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Unit {
+///     Celsius,
+///     Fahrenheit,
+/// }
+///
+/// /// 获取天气的参数
+/// #[derive(ToolParameters)]
+/// struct GetWeather {
+///     /// 城市和州，例如：旧金山，加利福尼亚州
+///     location: String,
+///     unit: Option<Unit>,
+/// }
+///
+/// fn main() {
+///     let tool = ChatCompletionToolParam::function(
+///         "get_weather",
+///         "获取指定地点的天气",
+///         GetWeather::parameters(),
+///     );
+/// }
+/// ```
+pub trait ToolParameters {
+    /// 该类型作为对象字段时是否可以省略。仅`Option<T>`为`true`。
+    #[doc(hidden)]
+    const OPTIONAL: bool = false;
+
+    /// 返回该类型对应的参数模式。
+    fn parameters() -> Parameters;
+}
+
+macro_rules! impl_tool_parameters {
+    ($constructor:ident => $($ty:ty),*) => {
+        $(
+            impl ToolParameters for $ty {
+                fn parameters() -> Parameters {
+                    Parameters::$constructor().build()
+                }
+            }
+        )*
+    };
+}
+
+impl_tool_parameters!(string => String, str, char);
+impl_tool_parameters!(integer => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_tool_parameters!(number => f32, f64);
+impl_tool_parameters!(boolean => bool);
+
+impl<T: ToolParameters + ?Sized> ToolParameters for &T {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn parameters() -> Parameters {
+        T::parameters()
+    }
+}
+
+impl<T: ToolParameters + ?Sized> ToolParameters for Box<T> {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn parameters() -> Parameters {
+        T::parameters()
+    }
+}
+
+impl<T: ToolParameters> ToolParameters for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn parameters() -> Parameters {
+        T::parameters()
+    }
+}
+
+impl<T: ToolParameters> ToolParameters for Vec<T> {
+    fn parameters() -> Parameters {
+        Parameters::array().items(T::parameters()).build()
+    }
+}
+
+impl<T: ToolParameters> ToolParameters for [T] {
+    fn parameters() -> Parameters {
+        Parameters::array().items(T::parameters()).build()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,10 @@
 use crate::chat::tool_parameters::Parameters;
 use crate::common::types::{CompletionGeneric, try_deserialize_or_skip};
 use crate::content;
+use crate::error::{OpenAIError, ProcessingError};
 use crate::utils::methods::merge_extra_fields_in_place;
 use derive_builder::Builder;
-use serde::de::{self, MapAccess, Visitor};
+use serde::de::{self, DeserializeOwned, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
@@ -38,12 +39,15 @@ pub struct ChoiceDelta {
     pub extra_fields: Option<HashMap<String, serde_json::Value>>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone)]
 pub enum ToolChoice {
     Auto,
     None,
     Required,
+    /// 强制模型调用指定名称的函数。
+    Function {
+        name: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -99,14 +103,64 @@ pub struct TopLogprob {
     pub bytes: Option<Vec<u8>>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case")]
+impl ChatCompletionTokenLogprob {
+    /// 返回该令牌的线性概率，即`exp(logprob)`，取值范围为`[0, 1]`。
+    pub fn prob(&self) -> f64 {
+        self.logprob.exp()
+    }
+}
+
+impl TopLogprob {
+    /// 返回该候选令牌的线性概率，即`exp(logprob)`，取值范围为`[0, 1]`。
+    pub fn prob(&self) -> f64 {
+        self.logprob.exp()
+    }
+}
+
+/// 模型停止生成的原因。
+///
+/// 兼容服务可能返回标准之外的值（例如`eos`、`tool_use`），这些值会被保留在[`FinishReason::Unknown`]中，
+/// 而不会导致整个响应反序列化失败。
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FinishReason {
     Stop,
     Length,
     ToolCalls,
     ContentFilter,
     FunctionCall,
+    /// 未识别的停止原因，保存服务端返回的原始值
+    Unknown(String),
+}
+
+impl FinishReason {
+    /// 返回服务端使用的字符串形式，例如`"tool_calls"`。
+    pub fn as_str(&self) -> &str {
+        match self {
+            FinishReason::Stop => "stop",
+            FinishReason::Length => "length",
+            FinishReason::ToolCalls => "tool_calls",
+            FinishReason::ContentFilter => "content_filter",
+            FinishReason::FunctionCall => "function_call",
+            FinishReason::Unknown(reason) => reason,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FinishReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let reason = String::deserialize(deserializer)?;
+        Ok(match reason.as_str() {
+            "stop" => FinishReason::Stop,
+            "length" => FinishReason::Length,
+            "tool_calls" => FinishReason::ToolCalls,
+            "content_filter" => FinishReason::ContentFilter,
+            "function_call" => FinishReason::FunctionCall,
+            _ => FinishReason::Unknown(reason),
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -115,18 +169,24 @@ pub enum ChatCompletionMessageParam {
     User(ChatCompletionUserMessageParam),
     Assistant(ChatCompletionAssistantMessageParam),
     Tool(ChatCompletionToolMessageParam),
-    // TODO 实现 Developer
-    // Developer,
+    Developer(ChatCompletionDeveloperMessageParam),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatCompletionSystemMessageParam {
     pub content: Content,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionDeveloperMessageParam {
+    pub content: Content,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatCompletionAssistantMessageParam {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -138,14 +198,14 @@ pub struct ChatCompletionAssistantMessageParam {
     pub tool_calls: Option<Vec<ChatCompletionMessageToolCallParam>>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatCompletionUserMessageParam {
     pub content: Content,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatCompletionToolMessageParam {
     pub tool_call_id: String,
     pub content: Content,
@@ -171,7 +231,7 @@ pub struct FunctionDefinition {
     pub strict: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Content {
     Text(String),
@@ -226,6 +286,23 @@ impl ChatCompletion {
             .first()
             .and_then(|choice| choice.message.content())
     }
+    /// 检查第一个选择的消息是否包含拒绝回答的说明。
+    pub fn has_refusal(&self) -> bool {
+        self.choices
+            .first()
+            .map(|choice| choice.message.has_refusal())
+            .unwrap_or(false)
+    }
+
+    /// 返回第一个选择的消息中拒绝回答的说明（如果有的话）。
+    ///
+    /// 模型出于安全原因拒绝请求时，说明位于`refusal`而不是`content`中，应与普通内容区分展示。
+    pub fn refusal(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.message.refusal())
+    }
+
     /// 检查第一个选择的消息是否包含任何工具调用。
     pub fn has_tool_calls(&self) -> bool {
         self.choices
@@ -241,6 +318,69 @@ impl ChatCompletion {
             .and_then(|choice| choice.message.tool_calls())
     }
 
+    /// 在第一个选择的工具调用中查找名为`name`的调用，并将其参数反序列化为指定类型。
+    ///
+    /// 没有同名的工具调用时返回`None`；存在多个同名调用时使用第一个。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct GetWeather {
+    ///     location: String,
+    /// }
+    ///
+    /// let completion =
+    ///     ChatCompletion::mock("").with_tool_call("call_1", "get_weather", r#"{"location":"Paris"}"#);
+    /// let args: GetWeather = completion
+    ///     .tool_arguments("get_weather")
+    ///     .transpose()?
+    ///     .expect("get_weather was called");
+    /// assert_eq!(args.location, "Paris");
+    /// # Ok::<(), OpenAIError>(())
+    /// ```
+    pub fn tool_arguments<T: DeserializeOwned>(
+        &self,
+        name: &str,
+    ) -> Option<Result<T, OpenAIError>> {
+        self.tool_calls()?
+            .iter()
+            .find(|tool_call| tool_call.function.name == name)
+            .map(ChatCompletionToolCall::parse_arguments)
+    }
+
+    /// 返回所有选择中的工具调用，每项附带所属选择的`index`。
+    ///
+    /// 与只检查第一个选择的[`ChatCompletion::tool_calls`]不同，适用于`n > 1`的响应。
+    pub fn all_tool_calls(&self) -> Vec<(usize, &ChatCompletionToolCall)> {
+        self.choices
+            .iter()
+            .flat_map(|choice| {
+                choice
+                    .message
+                    .tool_calls()
+                    .into_iter()
+                    .flatten()
+                    .map(move |tool_call| (choice.index, tool_call))
+            })
+            .collect()
+    }
+
+    /// 返回所有包含文本内容的选择的内容，每项附带所属选择的`index`。
+    pub fn all_content(&self) -> Vec<(usize, &str)> {
+        self.choices
+            .iter()
+            .filter_map(|choice| {
+                choice
+                    .message
+                    .content()
+                    .map(|content| (choice.index, content))
+            })
+            .collect()
+    }
+
     /// 检查第一个选择消息是否包含任何推理。
     pub fn has_reasoning(&self) -> bool {
         self.choices
@@ -256,14 +396,132 @@ impl ChatCompletion {
             .and_then(|choice| choice.message.reasoning())
     }
 
+    /// 返回第一个选择的消息中的注释列表（如果有的话），例如联网搜索模型返回的引用来源。
+    pub fn annotations(&self) -> Option<&Vec<Annotation>> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.message.annotations())
+    }
+
+    /// 返回服务端回显的`parallel_tool_calls`设置（如果有的话）。
+    ///
+    /// 该字段不属于标准响应，只有部分服务会在响应中回显请求的设置。
+    /// 禁用并行工具调用时，模型每轮最多返回一个工具调用。
+    pub fn parallel_tool_calls(&self) -> Option<bool> {
+        self.extra_fields
+            .as_ref()
+            .and_then(|fields| fields.get("parallel_tool_calls"))
+            .and_then(serde_json::Value::as_bool)
+    }
+
+    /// 返回第一个选择的对数概率信息（如果可用）。
+    ///
+    /// 仅当请求设置了[`ChatParam::logprobs`](super::ChatParam::logprobs)为`true`时，服务器才会返回该信息。
+    pub fn logprobs(&self) -> Option<&ChoiceLogprobs> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.logprobs.as_ref())
+    }
+
+    /// 返回第一个选择中内容令牌的对数概率列表（如果可用）。
+    pub fn token_logprobs(&self) -> Option<&[ChatCompletionTokenLogprob]> {
+        self.choices.first().and_then(FinalChoice::token_logprobs)
+    }
+
     /// 返回第一个选择的消息对象的引用。
     /// 当您需要访问消息的其他属性时（如 `role` 或 `refusal`），这很有用。
     pub fn first_choice_message(&self) -> Option<&ChatCompletionMessage> {
         self.choices.first().map(|choice| &choice.message)
     }
+
+    /// 将第一个选择的消息转换为可直接追加到对话历史中的助手消息。
+    ///
+    /// 工具调用及其id会被完整保留，以便随后追加对应的工具结果消息；
+    /// 当内容为空时会省略 `content` 字段。
+    pub fn to_assistant_message(&self) -> Option<ChatCompletionMessageParam> {
+        self.first_choice_message()
+            .map(ChatCompletionMessage::to_assistant_message)
+    }
+
+    /// 将第一个选择的消息作为助手消息追加到对话历史中，没有选择时不做任何修改。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenAI::from_env()?;
+    ///     let mut messages = vec![system!("You are a helpful assistant.")];
+    ///
+    ///     for question in ["What is Rust?", "Who created it?"] {
+    ///         messages.push(user!(question));
+    ///         let completion = client
+    ///             .chat()
+    ///             .create(ChatParam::new("gpt-4o", &messages))
+    ///             .await?;
+    ///         println!("{}", completion.content().unwrap_or_default());
+    ///         completion.append_to(&mut messages);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn append_to(&self, messages: &mut Vec<ChatCompletionMessageParam>) {
+        messages.extend(self.to_assistant_message());
+    }
 }
 
 impl ChatCompletionChunk {
+    /// 将同一流中后续的数据块合并到当前数据块中。
+    ///
+    /// 选择按`index`合并，新的`index`会追加到末尾；`id`、`model`、`created`保留当前值。
+    /// 数据块中出现的`system_fingerprint`、`service_tier`与非空的`usage`会覆盖当前值，
+    /// 因此启用`include_usage`时，流末尾只包含`usage`的数据块也能合并进来；
+    /// `extra_fields`中同名的字段以后到达的值为准。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// let mut completion = ChatCompletionChunk::mock("Hel");
+    /// completion.merge(ChatCompletionChunk::mock("lo"));
+    /// completion.merge(ChatCompletionChunk::mock_usage(5, 2));
+    ///
+    /// assert_eq!(completion.content(), Some("Hello"));
+    /// assert_eq!(completion.usage.unwrap().total_tokens, 7);
+    /// ```
+    pub fn merge(&mut self, chunk: Self) {
+        for delta in chunk.choices {
+            match self
+                .choices
+                .iter_mut()
+                .find(|choice| choice.index == delta.index)
+            {
+                Some(choice) => choice.merge(delta),
+                None => self.choices.push(delta),
+            }
+        }
+
+        if let Some(usage) = chunk.usage
+            && (!usage.is_empty() || self.usage.is_none())
+        {
+            self.usage = Some(usage);
+        }
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint = chunk.system_fingerprint;
+        }
+        if chunk.service_tier.is_some() {
+            self.service_tier = chunk.service_tier;
+        }
+        if let Some(extra_fields) = chunk.extra_fields {
+            self.extra_fields
+                .get_or_insert_with(HashMap::new)
+                .extend(extra_fields);
+        }
+    }
+
     /// 检查第一个选择的增量是否包含任何内容。
     pub fn has_content(&self) -> bool {
         self.choices
@@ -280,6 +538,21 @@ impl ChatCompletionChunk {
             .and_then(|choice| choice.delta.content())
     }
 
+    /// 检查第一个选择的增量是否包含拒绝回答的说明。
+    pub fn has_refusal(&self) -> bool {
+        self.choices
+            .first()
+            .map(|choice| choice.delta.has_refusal())
+            .unwrap_or(false)
+    }
+
+    /// 返回第一个选择的增量中拒绝回答的说明片段（如果有的话）。
+    pub fn refusal(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.delta.refusal())
+    }
+
     /// 检查第一个选择的增量是否包含任何工具调用。
     pub fn has_tool_calls(&self) -> bool {
         self.choices
@@ -334,6 +607,14 @@ impl ChatCompletionMessage {
         self.content.as_deref()
     }
 
+    pub fn has_refusal(&self) -> bool {
+        self.refusal.as_ref().is_some_and(|r| !r.is_empty())
+    }
+
+    pub fn refusal(&self) -> Option<&str> {
+        self.refusal.as_deref()
+    }
+
     pub fn reasoning(&self) -> Option<&str> {
         self.reasoning.as_deref()
     }
@@ -341,6 +622,44 @@ impl ChatCompletionMessage {
     pub fn tool_calls(&self) -> Option<&Vec<ChatCompletionToolCall>> {
         self.tool_calls.as_ref()
     }
+
+    pub fn annotations(&self) -> Option<&Vec<Annotation>> {
+        self.annotations.as_ref()
+    }
+
+    /// 返回消息中所有URL引用，按出现的顺序排列，没有注释时返回空列表。
+    ///
+    /// 每个引用的`start_index`与`end_index`指向`content`中引用该来源的文本范围。
+    pub fn citations(&self) -> Vec<&AnnotationURLCitation> {
+        self.annotations
+            .iter()
+            .flatten()
+            .map(|annotation| &annotation.url_citation)
+            .collect()
+    }
+
+    /// 将消息转换为助手消息参数，保留工具调用id，并在内容为空时省略 `content`。
+    pub fn to_assistant_message(&self) -> ChatCompletionMessageParam {
+        ChatCompletionMessageParam::Assistant(ChatCompletionAssistantMessageParam {
+            name: None,
+            content: self
+                .content
+                .as_ref()
+                .filter(|content| !content.is_empty())
+                .map(|content| Content::Text(content.clone())),
+            refusal: self.refusal.clone(),
+            tool_calls: self
+                .tool_calls
+                .as_ref()
+                .filter(|tool_calls| !tool_calls.is_empty())
+                .map(|tool_calls| {
+                    tool_calls
+                        .iter()
+                        .map(|tool_call| tool_call.clone().into())
+                        .collect()
+                }),
+        })
+    }
 }
 
 impl ChoiceDelta {
@@ -361,6 +680,14 @@ impl ChoiceDelta {
         self.content.as_deref()
     }
 
+    pub fn has_refusal(&self) -> bool {
+        self.refusal.as_ref().is_some_and(|r| !r.is_empty())
+    }
+
+    pub fn refusal(&self) -> Option<&str> {
+        self.refusal.as_deref()
+    }
+
     pub fn reasoning(&self) -> Option<&str> {
         self.reasoning.as_deref()
     }
@@ -399,6 +726,27 @@ impl Function {
             arguments: arguments.into(),
         }
     }
+
+    /// 将JSON字符串形式的`arguments`反序列化为指定类型。
+    ///
+    /// 流式响应被截断时，合并后的`arguments`可能不是完整的JSON，此时返回错误。
+    pub fn parse_arguments<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.arguments)
+    }
+
+    /// 将`arguments`解析为通用的[`serde_json::Value`]。
+    pub fn arguments_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        self.parse_arguments()
+    }
+}
+
+impl ToolChoice {
+    /// 创建一个强制模型调用指定函数的工具选择。
+    pub fn function(name: &str) -> Self {
+        Self::Function {
+            name: name.to_string(),
+        }
+    }
 }
 
 impl ChatCompletionMessageToolCallParam {
@@ -407,6 +755,113 @@ impl ChatCompletionMessageToolCallParam {
     }
 }
 
+impl ChatCompletionMessageParam {
+    /// 从聊天补全的第一个选择构造助手消息，等价于[`ChatCompletion::to_assistant_message`]。
+    pub fn from_completion(completion: &ChatCompletion) -> Option<Self> {
+        completion.to_assistant_message()
+    }
+
+    /// 为工具调用构造对应的工具结果消息，`tool_call_id`自动取自工具调用。
+    ///
+    /// `tool_call`可以是[`ChatCompletionToolCall`]或[`Function`]，等价于
+    /// `tool!(tool_call_id: tool_call.function.id, content: content)`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// let function = Function::new("call_1", "get_weather", r#"{"location":"Boston"}"#);
+    /// let message = ChatCompletionMessageParam::tool_result(&function, "72°F and sunny");
+    /// ```
+    pub fn tool_result<F: AsRef<Function>>(tool_call: F, content: impl Into<Content>) -> Self {
+        Self::Tool(ChatCompletionToolMessageParam {
+            tool_call_id: tool_call.as_ref().id.clone(),
+            content: content.into(),
+        })
+    }
+
+    /// 为工具调用构造工具结果消息，并将`value`序列化为JSON字符串作为内容。
+    ///
+    /// 工具结果的内容必须是字符串，因此结构化结果以JSON文本发送，无需手动调用`serde_json::to_string`。
+    ///
+    /// # 错误
+    ///
+    /// `value`序列化失败时返回错误。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::*;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Weather {
+    ///     temperature: i32,
+    ///     unit: &'static str,
+    /// }
+    ///
+    /// let function = Function::new("call_1", "get_weather", r#"{"location":"Boston"}"#);
+    /// let weather = Weather { temperature: 72, unit: "F" };
+    /// let message = ChatCompletionMessageParam::tool_result_json(&function, &weather).unwrap();
+    /// ```
+    pub fn tool_result_json<F: AsRef<Function>, T: Serialize + ?Sized>(
+        tool_call: F,
+        value: &T,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self::tool_result(tool_call, serde_json::to_string(value)?))
+    }
+}
+
+impl Content {
+    /// 如果内容是文本，返回其文本。
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Content::Text(text) => Some(text),
+            Content::Object(_) => None,
+        }
+    }
+
+    /// 如果内容是文本，取出其文本。
+    pub fn into_text(self) -> Option<String> {
+        match self {
+            Content::Text(text) => Some(text),
+            Content::Object(_) => None,
+        }
+    }
+}
+
+/// 文本内容原样输出，对象内容输出为紧凑的JSON。
+impl fmt::Display for Content {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Content::Text(text) => f.write_str(text),
+            Content::Object(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<&str> for Content {
+    fn from(value: &str) -> Self {
+        Content::Text(value.to_string())
+    }
+}
+
+impl From<String> for Content {
+    fn from(value: String) -> Self {
+        Content::Text(value)
+    }
+}
+
+impl From<serde_json::Value> for Content {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::String(text) => Content::Text(text),
+            serde_json::Value::Object(_) | serde_json::Value::Array(_) => Content::Object(value),
+            other => Content::Text(other.to_string()),
+        }
+    }
+}
+
 impl From<ChatCompletionToolCall> for ChatCompletionMessageToolCallParam {
     fn from(value: ChatCompletionToolCall) -> Self {
         Self::Function(value.function)
@@ -459,6 +914,17 @@ impl From<ChoiceDelta> for ChatCompletionMessage {
     }
 }
 
+impl FinalChoice {
+    /// 返回该选择中内容令牌的对数概率列表。
+    ///
+    /// 仅当请求设置了[`ChatParam::logprobs`](super::ChatParam::logprobs)为`true`时才会返回，否则为`None`。
+    pub fn token_logprobs(&self) -> Option<&[ChatCompletionTokenLogprob]> {
+        self.logprobs
+            .as_ref()
+            .and_then(|logprobs| logprobs.content.as_deref())
+    }
+}
+
 impl From<StreamChoice> for FinalChoice {
     fn from(value: StreamChoice) -> Self {
         Self {
@@ -471,10 +937,11 @@ impl From<StreamChoice> for FinalChoice {
 }
 
 impl StreamChoice {
+    /// 将同一选择的增量合并到当前选择中。
+    ///
+    /// 只应合并`index`相同的增量；`n > 1`时请使用[`ChatCompletionAccumulator`](super::ChatCompletionAccumulator)
+    /// 按`index`分组合并。
     pub fn merge(&mut self, delta: Self) {
-        if self.index == 0 {
-            self.index = delta.index;
-        }
         if delta.finish_reason.is_some() {
             self.finish_reason = delta.finish_reason;
         }
@@ -486,6 +953,10 @@ impl StreamChoice {
 }
 
 impl ChoiceDelta {
+    /// 将流式响应中的下一个增量合并到当前增量中。
+    ///
+    /// 工具调用同时兼容标准格式（并发的工具调用按`index`交错到达）和非标准格式（`index`均为0并依次到达）：
+    /// 携带新`id`或新函数名的增量视为新的工具调用，其余增量作为已有工具调用的延续。
     pub fn merge(&mut self, delta: Self) {
         // 合并响应内容
         match (self.content.as_mut(), delta.content) {
@@ -494,9 +965,11 @@ impl ChoiceDelta {
             _ => {}
         }
 
-        // 如果增量中存在拒绝内容则更新
-        if delta.refusal.is_some() {
-            self.refusal = delta.refusal;
+        // 拒绝说明与内容一样分多个增量发送，需要拼接
+        match (self.refusal.as_mut(), delta.refusal) {
+            (Some(left), Some(right)) => left.push_str(&right),
+            (None, Some(right)) => self.refusal = Some(right),
+            _ => {}
         }
 
         // 如果增量中存在角色则更新
@@ -507,28 +980,8 @@ impl ChoiceDelta {
         // 使用自适应逻辑合并工具调用
         match (self.tool_calls.as_mut(), delta.tool_calls) {
             (Some(left), Some(right)) => {
-                // 检测非标准、顺序的工具调用流的启发式方法。
-                // 如果传入的增量有一个索引为0的工具调用，
-                // 我们假设它是 `left` 向量中最后一个工具调用的延续。
-                if right.len() == 1 && right[0].index == 0 {
-                    if let Some(last_tool_call) = left.last_mut() {
-                        // 这是安全的，因为我们已检查 right.len() == 1。
-                        if let Some(r) = right.into_iter().next() {
-                            last_tool_call.merge(r);
-                        }
-                    } else {
-                        // 如果 `left` 为空，则直接获取 `right`。
-                        *left = right;
-                    }
-                } else {
-                    // 标准的基于索引的合并，用于稳健处理并发工具调用。
-                    for r in right.into_iter() {
-                        if let Some(l) = left.iter_mut().find(|l| l.index == r.index) {
-                            l.merge(r);
-                        } else {
-                            left.push(r);
-                        }
-                    }
+                for r in right {
+                    merge_tool_call(left, r);
                 }
             }
             (None, Some(right)) => self.tool_calls = Some(right),
@@ -547,13 +1000,84 @@ impl ChoiceDelta {
     }
 }
 
+/// 将一个工具调用增量合并到已有的工具调用列表中。
+///
+/// 同时兼容两种流式格式：
+/// - 标准格式：每个工具调用有各自的`index`，并发的工具调用可能按`index`交错到达；
+///   只有首个增量携带`id`，后续增量按`index`合并。
+/// - 非标准格式：所有工具调用的`index`均为0并依次到达，新的工具调用以新的`id`开始。
+///
+/// 合并规则：携带`id`的增量与`id`相同的工具调用合并（部分服务在每个增量中重复`id`），
+/// 否则视为新的工具调用；不携带`id`的增量与`index`相同的最后一个工具调用合并，
+/// 找不到时若`index`为0则作为最后一个工具调用的延续，否则视为新的工具调用。
+/// 若不携带`id`的增量带有函数名，而待合并的工具调用已有函数名，
+/// 说明这是一个新的工具调用（参数片段不会携带函数名），同样视为新的工具调用。
+fn merge_tool_call(left: &mut Vec<ChatCompletionToolCall>, mut delta: ChatCompletionToolCall) {
+    if !delta.function.id.is_empty() {
+        match left
+            .iter_mut()
+            .find(|tool_call| tool_call.function.id == delta.function.id)
+        {
+            Some(tool_call) => {
+                delta.function.id.clear();
+                tool_call.merge(delta);
+            }
+            None => left.push(delta),
+        }
+        return;
+    }
+
+    let target = match left
+        .iter()
+        .rposition(|tool_call| tool_call.index == delta.index)
+    {
+        Some(position) => Some(position),
+        None if delta.index == 0 => left.len().checked_sub(1),
+        None => None,
+    };
+
+    match target.map(|position| &mut left[position]) {
+        Some(tool_call) if delta.function.name.is_empty() || tool_call.function.name.is_empty() => {
+            tool_call.merge(delta)
+        }
+        _ => left.push(delta),
+    }
+}
+
 impl ChatCompletionToolCall {
+    /// 将函数的`arguments`反序列化为指定类型。
+    ///
+    /// 与[`Function::parse_arguments`]相同，但解析失败时返回
+    /// [`ProcessingError::ToolArguments`]，错误中包含函数名，便于与其他请求错误一起用`?`传播。
+    pub fn parse_arguments<T: DeserializeOwned>(&self) -> Result<T, OpenAIError> {
+        self.function.parse_arguments().map_err(|error| {
+            ProcessingError::ToolArguments {
+                error,
+                name: self.function.name.clone(),
+                target_type: std::any::type_name::<T>().to_string(),
+            }
+            .into()
+        })
+    }
+
     pub fn merge(&mut self, delta: Self) {
         self.index = delta.index;
         self.function.merge(delta.function);
     }
 }
 
+impl AsRef<Function> for ChatCompletionToolCall {
+    fn as_ref(&self) -> &Function {
+        &self.function
+    }
+}
+
+impl AsRef<Function> for Function {
+    fn as_ref(&self) -> &Function {
+        self
+    }
+}
+
 impl Function {
     pub fn merge(&mut self, delta: Self) {
         self.id.push_str(&delta.id);
@@ -574,6 +1098,30 @@ impl Serialize for ChatCompletionPredictionContentParam {
     }
 }
 
+impl Serialize for ToolChoice {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Auto => serializer.serialize_str("auto"),
+            Self::None => serializer.serialize_str("none"),
+            Self::Required => serializer.serialize_str("required"),
+            Self::Function { name } => {
+                #[derive(Serialize)]
+                struct NamedFunction<'a> {
+                    name: &'a str,
+                }
+
+                let mut state = serializer.serialize_struct("ToolChoice", 2)?;
+                state.serialize_field("type", "function")?;
+                state.serialize_field("function", &NamedFunction { name })?;
+                state.end()
+            }
+        }
+    }
+}
+
 impl Serialize for Function {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -673,7 +1221,70 @@ impl Serialize for ChatCompletionMessageParam {
                 state.serialize_field("tool_call_id", &inner.tool_call_id)?;
                 state.end()
             }
+            Self::Developer(inner) => {
+                let mut len = 2;
+                if inner.name.is_some() {
+                    len += 1;
+                }
+                let mut state = serializer.serialize_struct("ChatCompletionMessageParam", len)?;
+                state.serialize_field("role", "developer")?;
+                state.serialize_field("content", &inner.content)?;
+                if let Some(name) = &inner.name {
+                    state.serialize_field("name", name)?;
+                }
+                state.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatCompletionMessageParam {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(tag = "role", rename_all = "lowercase")]
+        enum MessageParamHelper {
+            System(ChatCompletionSystemMessageParam),
+            User(ChatCompletionUserMessageParam),
+            Assistant(ChatCompletionAssistantMessageParam),
+            Tool(ChatCompletionToolMessageParam),
+            Developer(ChatCompletionDeveloperMessageParam),
+        }
+
+        Ok(match MessageParamHelper::deserialize(deserializer)? {
+            MessageParamHelper::System(inner) => Self::System(inner),
+            MessageParamHelper::User(inner) => Self::User(inner),
+            MessageParamHelper::Assistant(inner) => Self::Assistant(inner),
+            MessageParamHelper::Tool(inner) => Self::Tool(inner),
+            MessageParamHelper::Developer(inner) => Self::Developer(inner),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatCompletionMessageToolCallParam {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct ToolCallParamHelper {
+            r#type: String,
+            id: String,
+            function: Function,
+        }
+
+        let helper = ToolCallParamHelper::deserialize(deserializer)?;
+        if helper.r#type != "function" {
+            return Err(de::Error::custom(format!(
+                "Expected type 'function', found '{}'",
+                helper.r#type
+            )));
         }
+        let mut function = helper.function;
+        function.id = helper.id;
+        Ok(Self::Function(function))
     }
 }
 
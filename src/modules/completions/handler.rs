@@ -1,10 +1,11 @@
 use super::params::CompletionsParam;
 use super::types::Completion;
-use crate::common::types::{InParam, RetryCount, Timeout};
+use crate::common::types::{ConnectTimeout, InParam, RetryCount, Timeout};
 use crate::error::OpenAIError;
 use crate::service::client::HttpClient;
 use crate::service::request::{RequestBuilder, RequestSpec};
 use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
 
 pub struct Completions {
     http_client: HttpClient,
@@ -15,20 +16,21 @@ impl Completions {
         Completions { http_client }
     }
 
+    /// 创建一个（旧版）文本补全，发送`POST /completions`请求。
+    ///
+    /// 无论参数中如何设置，请求体中的`stream`始终为`false`。
     pub async fn create(&self, param: CompletionsParam) -> Result<Completion, OpenAIError> {
         let mut inner = param.take();
-        inner
-            .body
-            .as_mut()
-            .unwrap()
-            .insert("stream".to_string(), serde_json::to_value(false).unwrap());
+        let body = inner.body.as_mut().unwrap();
+        body.insert("stream".to_string(), serde_json::to_value(false).unwrap());
+        // 服务端只允许在流式请求中携带`stream_options`
+        body.remove("stream_options");
 
         let http_params = RequestSpec::new(
-            |config| format!("{}/completions", config.base_url()),
-            move |config, request| {
+            |config| config.endpoint_url("/completions"),
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
         );
@@ -36,6 +38,32 @@ impl Completions {
         self.http_client.post_json(http_params).await
     }
 
+    /// 创建一个流式文本补全。
+    ///
+    /// 与[`Chat::create_stream`](crate::chat::Chat::create_stream)相同，通过SSE逐个返回补全数据块，
+    /// 每个数据块都是一个[`Completion`]，其中选择的`text`为本次新增的文本。
+    /// 服务端发送`[DONE]`后流结束。丢弃返回的流即可关闭底层连接，
+    /// 也可以通过`CompletionsParam::cancellation_token`传入`CancellationToken`取消。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// use futures::StreamExt;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenAI::from_env()?;
+    ///     let request = CompletionsParam::new("gpt-3.5-turbo-instruct", "Once upon a time");
+    ///     let mut stream = client.completions().create_stream(request).await?;
+    ///
+    ///     while let Some(chunk) = stream.next().await {
+    ///         if let Some(choice) = chunk?.choices.first() {
+    ///             print!("{}", choice.text);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
     pub async fn create_stream(
         &self,
         param: CompletionsParam,
@@ -46,17 +74,19 @@ impl Completions {
             .as_mut()
             .unwrap()
             .insert("stream".to_string(), serde_json::to_value(true).unwrap());
+        let cancel_token = inner.extensions.get::<CancellationToken>().cloned();
 
         let http_params = RequestSpec::new(
-            |config| format!("{}/completions", config.base_url()),
-            move |config, request| {
+            |config| config.endpoint_url("/completions"),
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
         );
-        self.http_client.post_json_sse(http_params).await
+        self.http_client
+            .post_json_sse(http_params, cancel_token)
+            .await
     }
 }
 
@@ -74,6 +104,10 @@ impl Completions {
             builder.timeout(time.0);
         }
 
+        if let Some(time) = params.extensions.get::<ConnectTimeout>() {
+            builder.connect_timeout(time.0);
+        }
+
         if let Some(retry) = params.extensions.get::<RetryCount>() {
             builder.request_mut().extensions_mut().insert(retry.clone());
         }
@@ -4,4 +4,4 @@ pub mod types;
 
 pub use handler::Completions;
 pub use params::CompletionsParam;
-pub use types::Completion;
+pub use types::{Completion, Prompt};
@@ -1,18 +1,20 @@
-use crate::common::types::{InParam, JsonBody, RetryCount, Timeout};
-use http::{
-    HeaderValue,
-    header::{IntoHeaderName, USER_AGENT},
-};
+use super::types::Prompt;
+use crate::common::params::{impl_request_options, impl_user_param};
+use crate::common::types::{InParam, JsonBody, Penalty, Temperature};
 use serde_json::Value;
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
+use tokio_util::sync::CancellationToken;
 
 pub struct CompletionsParam {
     inner: InParam,
 }
 
+impl_request_options!(CompletionsParam);
+impl_user_param!(CompletionsParam);
+
 impl CompletionsParam {
     #[doc = include_str!("../../docs/completions_param.md")]
-    pub fn new(model: &str, prompt: &str) -> Self {
+    pub fn new<T: Into<Prompt>>(model: &str, prompt: T) -> Self {
         let mut inner = InParam::new();
         inner.body = Some(JsonBody::new());
         inner
@@ -21,11 +23,10 @@ impl CompletionsParam {
             .unwrap()
             .insert("model".to_string(), serde_json::to_value(model).unwrap());
 
-        inner
-            .body
-            .as_mut()
-            .unwrap()
-            .insert("prompt".to_string(), serde_json::to_value(prompt).unwrap());
+        inner.body.as_mut().unwrap().insert(
+            "prompt".to_string(),
+            serde_json::to_value(prompt.into()).unwrap(),
+        );
 
         CompletionsParam { inner }
     }
@@ -46,10 +47,10 @@ impl CompletionsParam {
     /// 较高的值（如0.8）会使输出更加随机，而较低的值（如0.2）
     /// 会使输出更加集中和确定。
     /// 我们通常建议修改此参数或`top_p`，但不建议同时修改两者。
-    pub fn temperature(mut self, temperature: f32) -> Self {
+    pub fn temperature(mut self, temperature: impl Into<Temperature>) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "temperature".to_string(),
-            serde_json::to_value(temperature).unwrap(),
+            serde_json::to_value(temperature.into().get()).unwrap(),
         );
         self
     }
@@ -118,20 +119,20 @@ impl CompletionsParam {
 
     /// 存在惩罚。一个介于-2.0和2.0之间的数值。正值根据新令牌是否出现在迄今为止的文本中进行惩罚，
     /// 增加模型谈论新话题的可能性。
-    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+    pub fn presence_penalty(mut self, presence_penalty: impl Into<Penalty>) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "presence_penalty".to_string(),
-            serde_json::to_value(presence_penalty).unwrap(),
+            serde_json::to_value(presence_penalty.into().get()).unwrap(),
         );
         self
     }
 
     /// 频率惩罚。一个介于-2.0和2.0之间的数值。正值根据新令牌在迄今为止文本中的现有频率进行惩罚，
     /// 降低模型逐字重复同一行的可能性。
-    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+    pub fn frequency_penalty(mut self, frequency_penalty: impl Into<Penalty>) -> Self {
         self.inner.body.as_mut().unwrap().insert(
             "frequency_penalty".to_string(),
-            serde_json::to_value(frequency_penalty).unwrap(),
+            serde_json::to_value(frequency_penalty.into().get()).unwrap(),
         );
         self
     }
@@ -162,33 +163,32 @@ impl CompletionsParam {
         self
     }
 
-    /// 终端用户标识符。代表您的终端用户的唯一标识符，这可以帮助OpenAI监控和检测滥用行为。
-    pub fn user(mut self, user: String) -> Self {
-        self.inner
-            .body
-            .as_mut()
+    /// 为单个令牌设置Logit偏置，可多次调用以累积多个令牌。
+    ///
+    /// 与[`CompletionsParam::logit_bias`]写入同一字段：令牌ID会被转换为字符串键，
+    /// 同一令牌重复设置时以最后一次为准；之后调用`logit_bias`会覆盖此前设置的全部偏置。
+    pub fn logit_bias_token(mut self, token_id: u32, bias: i32) -> Self {
+        let body = self.inner.body.as_mut().unwrap();
+        let entry = body
+            .entry("logit_bias".to_string())
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(serde_json::Map::new());
+        }
+        entry
+            .as_object_mut()
             .unwrap()
-            .insert("user".to_string(), serde_json::to_value(user).unwrap());
+            .insert(token_id.to_string(), Value::from(bias));
         self
     }
 
-    /// 超时时间。HTTP请求超时时间，覆盖客户端的全局设置。
+    /// 取消令牌。用于在流式请求进行中主动终止读取。
     ///
-    /// 此字段不会在请求体中序列化。
-    pub fn timeout(mut self, timeout: Duration) -> Self {
-        self.inner.extensions.insert(Timeout(timeout));
-        self
-    }
-
-    /// 用户代理。HTTP请求User-Agent，覆盖客户端的全局设置。
-    pub fn user_agent(mut self, user_agent: HeaderValue) -> Self {
-        self.inner.headers.insert(USER_AGENT, user_agent);
-        self
-    }
-
-    /// 设置HTTP请求头信息。
-    pub fn header<K: IntoHeaderName>(mut self, key: K, val: HeaderValue) -> Self {
-        self.inner.headers.insert(key, val);
+    /// 令牌被取消后，后台读取任务会立即退出并关闭底层连接，返回的流随之结束。
+    /// 丢弃返回的流同样会终止读取，此方法适用于流被其他位置持有的情况。
+    /// 仅对`create_stream`生效，此字段不会在请求体中序列化。
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.inner.extensions.insert(token);
         self
     }
 
@@ -201,14 +201,6 @@ impl CompletionsParam {
             .insert(key.into(), val.into());
         self
     }
-
-    /// 重试次数。HTTP请求重试次数，覆盖客户端的全局设置。
-    ///
-    /// 此字段不会在请求体中序列化。
-    pub fn retry_count(mut self, retry_count: usize) -> Self {
-        self.inner.extensions.insert(RetryCount(retry_count));
-        self
-    }
 }
 
 impl CompletionsParam {
@@ -216,3 +208,58 @@ impl CompletionsParam {
         self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_param_prompt() {
+        let single = CompletionsParam::new("gpt-3.5-turbo-instruct", "Once upon a time").take();
+        assert_eq!(
+            single.body.unwrap()["prompt"],
+            serde_json::json!("Once upon a time")
+        );
+
+        let batch = CompletionsParam::new(
+            "gpt-3.5-turbo-instruct",
+            vec!["Once upon a time", "In a galaxy far away"],
+        )
+        .take();
+        assert_eq!(
+            batch.body.unwrap()["prompt"],
+            serde_json::json!(["Once upon a time", "In a galaxy far away"])
+        );
+    }
+
+    #[test]
+    fn test_completions_param_sampling_serialization() {
+        let body = CompletionsParam::new("gpt-3.5-turbo-instruct", "Say this is a test")
+            .logit_bias_token(50256, -100)
+            .logit_bias_token(1820, 5)
+            .logit_bias_token(50256, -50)
+            .presence_penalty(0.5)
+            .frequency_penalty(-1.0)
+            .stop(vec!["\n".to_string()])
+            .take()
+            .body
+            .unwrap();
+
+        assert_eq!(
+            body["logit_bias"],
+            serde_json::json!({"50256": -50, "1820": 5})
+        );
+        assert_eq!(body["presence_penalty"], serde_json::json!(0.5));
+        assert_eq!(body["frequency_penalty"], serde_json::json!(-1.0));
+        assert_eq!(body["stop"], serde_json::json!(["\n"]));
+
+        let bias = HashMap::from([("50256".to_string(), -100)]);
+        let body = CompletionsParam::new("gpt-3.5-turbo-instruct", "Say this is a test")
+            .logit_bias_token(1820, 5)
+            .logit_bias(bias)
+            .take()
+            .body
+            .unwrap();
+        assert_eq!(body["logit_bias"], serde_json::json!({"50256": -100}));
+    }
+}
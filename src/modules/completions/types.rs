@@ -1,11 +1,23 @@
 use crate::common::types::CompletionGeneric;
-use serde::Deserialize;
 use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
 pub type Completion = CompletionGeneric<CompletionChoice>;
 
+/// 补全请求的提示。
+///
+/// 可以是单个提示文本，也可以是多个提示组成的列表，以便在一次请求中批量生成补全。
+/// 批量请求时，响应中每个选择的`index`对应提示在列表中的位置（`n > 1`时按`n`分组）。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prompt {
+    /// 单个提示
+    Single(String),
+    /// 批量提示
+    Multi(Vec<String>),
+}
+
 #[derive(Debug, Clone)]
 pub struct CompletionChoice {
     pub index: usize,
@@ -32,6 +44,23 @@ pub struct Logprobs {
     pub top_logprobs: Option<Vec<HashMap<String, f64>>>,
 }
 
+impl Completion {
+    /// 返回批量请求中第`prompt_index`个提示对应的选择，按`index`排序。
+    ///
+    /// `n`为请求中为每个提示生成的补全数量；服务端将第`i`个提示的选择编号为
+    /// `i * n`到`i * n + n - 1`。`n`为0时按1处理。
+    pub fn choices_for_prompt(&self, prompt_index: usize, n: usize) -> Vec<&CompletionChoice> {
+        let n = n.max(1);
+        let mut choices: Vec<&CompletionChoice> = self
+            .choices
+            .iter()
+            .filter(|choice| choice.prompt_index(n) == prompt_index)
+            .collect();
+        choices.sort_by_key(|choice| choice.index);
+        choices
+    }
+}
+
 impl CompletionChoice {
     pub fn is_reasoning(&self) -> bool {
         self.reasoning.as_ref().is_some_and(|reas| !reas.is_empty())
@@ -47,6 +76,11 @@ impl CompletionChoice {
     pub fn get_text_str(&self) -> &str {
         self.text.as_str()
     }
+
+    /// 返回此选择在批量请求中对应的提示位置，`n`为每个提示生成的补全数量。
+    pub fn prompt_index(&self, n: usize) -> usize {
+        self.index / n.max(1)
+    }
 }
 
 impl<'de> Deserialize<'de> for CompletionChoice {
@@ -138,3 +172,51 @@ impl<'de> Deserialize<'de> for CompletionChoice {
         deserializer.deserialize_map(CompletionChoiceVisitor)
     }
 }
+
+impl Serialize for Prompt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Prompt::Single(prompt) => serializer.serialize_str(prompt),
+            Prompt::Multi(prompts) => prompts.serialize(serializer),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Prompt {
+    fn from(val: &'a str) -> Self {
+        Prompt::Single(val.to_string())
+    }
+}
+
+impl From<String> for Prompt {
+    fn from(val: String) -> Self {
+        Prompt::Single(val)
+    }
+}
+
+impl<'a, T> From<&'a [T]> for Prompt
+where
+    T: AsRef<str>,
+{
+    fn from(slice: &'a [T]) -> Self {
+        Prompt::Multi(slice.iter().map(|s| s.as_ref().to_string()).collect())
+    }
+}
+
+impl<T> From<Vec<T>> for Prompt
+where
+    T: AsRef<str>,
+{
+    fn from(vec: Vec<T>) -> Self {
+        Prompt::Multi(vec.into_iter().map(|s| s.as_ref().to_string()).collect())
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for Prompt {
+    fn from(val: [&str; N]) -> Self {
+        Prompt::Multi(val.iter().map(|s| s.to_string()).collect())
+    }
+}
@@ -1,11 +1,15 @@
 use super::params::EmbeddingsParam;
-use super::types::EmbeddingResponse;
+use super::types::{EmbeddingResponse, Usage};
 use crate::OpenAIError;
-use crate::common::types::{InParam, RetryCount, Timeout};
+use crate::common::types::{ConnectTimeout, InParam, RetryCount, Timeout};
+use crate::error::RequestError;
 use crate::service::{
     HttpClient,
     request::{RequestBuilder, RequestSpec},
 };
+use futures::{StreamExt, TryStreamExt};
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// 处理嵌入请求，用于生成文本的向量表示。
 pub struct Embeddings {
@@ -43,22 +47,174 @@ impl Embeddings {
     /// }
     /// ```
     pub async fn create(&self, param: EmbeddingsParam) -> Result<EmbeddingResponse, OpenAIError> {
+        self.create_inner(param.take()).await
+    }
+
+    /// 将大批量输入拆分为多个请求并发发送，再按原顺序合并结果。
+    ///
+    /// `param`中的`input`列表会被拆分为每批最多`batch_size`个输入（文本或令牌序列），最多同时发送`concurrency`个请求。
+    /// 合并后的嵌入按输入顺序排列，`index`为其在原始输入列表中的位置，`usage`为所有请求之和。
+    /// 其他参数（如`dimensions`、请求头、超时）会应用到每个请求。
+    /// 输入为单个文本、单个令牌序列或不超过`batch_size`时，与[`Embeddings::create`]相同，只发送一个请求。
+    ///
+    /// 任一批次失败时返回该错误，尚未完成的批次会被取消。
+    ///
+    /// # 错误
+    ///
+    /// `batch_size`或`concurrency`为0时返回[`RequestError::InvalidParameter`]，不会发送任何请求。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// use openai4rs::embeddings::EmbeddingsParam;
+    /// use dotenvy::dotenv;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     dotenv().ok();
+    ///     let client = OpenAI::from_env()?;
+    ///     let documents: Vec<String> = (0..5000).map(|i| format!("document {i}")).collect();
+    ///     let request = EmbeddingsParam::new("text-embedding-3-small", documents);
+    ///     let response = client.embeddings().create_batched(request, 512, 4).await?;
+    ///     assert_eq!(response.len(), 5000);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_batched(
+        &self,
+        param: EmbeddingsParam,
+        batch_size: usize,
+        concurrency: usize,
+    ) -> Result<EmbeddingResponse, OpenAIError> {
+        self.create_batched_with_progress(param, batch_size, concurrency, |_, _| {})
+            .await
+    }
+
+    /// 与[`Embeddings::create_batched`]相同，并在每个批次完成后报告进度。
+    ///
+    /// `on_progress`接收已完成的输入数量与输入总数，批次可能乱序完成，但已完成数量总是递增的。
+    /// 只发送一个请求时，在该请求成功后报告一次。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// use openai4rs::embeddings::EmbeddingsParam;
+    /// use dotenvy::dotenv;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     dotenv().ok();
+    ///     let client = OpenAI::from_env()?;
+    ///     let documents: Vec<String> = (0..5000).map(|i| format!("document {i}")).collect();
+    ///     let request = EmbeddingsParam::new("text-embedding-3-small", documents);
+    ///     let response = client
+    ///         .embeddings()
+    ///         .create_batched_with_progress(request, 512, 4, |done, total| {
+    ///             println!("embedded {done}/{total}");
+    ///         })
+    ///         .await?;
+    ///     assert_eq!(response.len(), 5000);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_batched_with_progress<F>(
+        &self,
+        param: EmbeddingsParam,
+        batch_size: usize,
+        concurrency: usize,
+        on_progress: F,
+    ) -> Result<EmbeddingResponse, OpenAIError>
+    where
+        F: Fn(usize, usize) + Send + Sync,
+    {
+        if batch_size == 0 || concurrency == 0 {
+            return Err(RequestError::InvalidParameter(
+                "`batch_size` and `concurrency` must be greater than 0".to_string(),
+            )
+            .into());
+        }
+
         let inner = param.take();
+        // 单个令牌序列（数字数组）是一个输入，不能拆分
+        let inputs = match inner.body.as_ref().and_then(|body| body.get("input")) {
+            Some(Value::Array(inputs)) if !is_token_array(inputs) && inputs.len() > batch_size => {
+                inputs.clone()
+            }
+            input => {
+                let total = match input {
+                    Some(Value::Array(inputs)) if !is_token_array(inputs) => inputs.len(),
+                    _ => 1,
+                };
+                let response = self.create_inner(inner).await?;
+                on_progress(total, total);
+                return Ok(response);
+            }
+        };
+
+        let total = inputs.len();
+        let completed = AtomicUsize::new(0);
+        let batches = inputs.chunks(batch_size).map(|chunk| {
+            let mut batch = inner.clone();
+            if let Some(body) = batch.body.as_mut() {
+                body.insert("input".to_string(), Value::Array(chunk.to_vec()));
+            }
+            let (completed, on_progress) = (&completed, &on_progress);
+            async move {
+                let response = self.create_inner(batch).await?;
+                let done = completed.fetch_add(chunk.len(), Ordering::Relaxed) + chunk.len();
+                on_progress(done, total);
+                Ok::<_, OpenAIError>(response)
+            }
+        });
 
+        let responses: Vec<EmbeddingResponse> = futures::stream::iter(batches)
+            .buffered(concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(Self::merge_batches(responses, batch_size))
+    }
+}
+
+impl Embeddings {
+    async fn create_inner(&self, inner: InParam) -> Result<EmbeddingResponse, OpenAIError> {
         let http_params = RequestSpec::new(
-            |config| format!("{}/embeddings", config.base_url()),
-            move |config, request| {
+            |config| config.endpoint_url("/embeddings"),
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
         );
         self.http_client.post_json(http_params).await
     }
-}
 
-impl Embeddings {
+    /// 按顺序合并各批次的响应，并将`index`修正为在原始输入中的位置。
+    fn merge_batches(responses: Vec<EmbeddingResponse>, batch_size: usize) -> EmbeddingResponse {
+        let mut responses = responses.into_iter().enumerate();
+        let (_, mut merged) = responses
+            .next()
+            .expect("create_batched always sends at least two batches");
+
+        for (batch_index, response) in responses {
+            let offset = batch_index * batch_size;
+            merged
+                .data
+                .extend(response.data.into_iter().map(|mut embedding| {
+                    embedding.index += offset;
+                    embedding
+                }));
+            merged.usage = Usage {
+                prompt_tokens: merged.usage.prompt_tokens + response.usage.prompt_tokens,
+                total_tokens: merged.usage.total_tokens + response.usage.total_tokens,
+            };
+        }
+        // 服务端通常按输入顺序返回，这里再按全局位置排序以防万一
+        merged.data.sort_by_key(|embedding| embedding.index);
+
+        merged
+    }
+
     fn apply_request_settings(builder: &mut RequestBuilder, params: InParam) {
         let body = params
             .body
@@ -72,8 +228,33 @@ impl Embeddings {
             builder.timeout(time.0);
         }
 
+        if let Some(time) = params.extensions.get::<ConnectTimeout>() {
+            builder.connect_timeout(time.0);
+        }
+
         if let Some(retry) = params.extensions.get::<RetryCount>() {
             builder.request_mut().extensions_mut().insert(retry.clone());
         }
     }
 }
+
+/// 判断`input`数组是否为单个令牌序列（非空且元素均为数字）。
+fn is_token_array(inputs: &[Value]) -> bool {
+    !inputs.is_empty() && inputs.iter().all(Value::is_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_token_array() {
+        assert!(is_token_array(&[json!(1), json!(2)]));
+        assert!(!is_token_array(&[]));
+        assert!(!is_token_array(&[json!("a"), json!("b")]));
+        // 每个元素都必须是数字
+        assert!(!is_token_array(&[json!(1), json!("a")]));
+        assert!(!is_token_array(&[json!(1), json!([2, 3])]));
+    }
+}
@@ -4,4 +4,6 @@ pub mod types;
 
 pub use handler::Embeddings;
 pub use params::EmbeddingsParam;
-pub use types::{EmbeddingResponse, Input, EncodingFormat};
+pub use types::{
+    EmbeddingDecodeError, EmbeddingResponse, EncodingFormat, Input, InputText, SimilarityError,
+};
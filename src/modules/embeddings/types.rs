@@ -1,12 +1,23 @@
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
 
+/// 要嵌入的输入。
+///
+/// 除文本外，也可以传入在客户端分词得到的令牌ID，以精确控制输入：
+/// 单个令牌序列（`Vec<u32>`）对应一个输入，令牌序列的列表（`Vec<Vec<u32>>`）对应多个输入。
 #[derive(Debug, Clone)]
 pub enum Input {
     Text(String),
     List(Vec<String>),
+    /// 单个输入的令牌ID
+    Tokens(Vec<u32>),
+    /// 多个输入的令牌ID
+    TokenBatches(Vec<Vec<u32>>),
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +48,34 @@ pub struct Embedding {
     pub object: String,
 }
 
+/// 计算相似度矩阵时发生的错误。
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SimilarityError {
+    /// 无法将嵌入解码为浮点向量
+    #[error("Embedding at position {index} cannot be decoded into a float vector")]
+    Undecodable { index: usize },
+
+    /// 嵌入向量的维度不一致
+    #[error("Embedding at position {index} has {actual} dimensions, expected {expected}")]
+    DimensionMismatch {
+        index: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// 无法将base64编码的嵌入解码为浮点向量。
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Embedding at position {index} cannot be decoded into a float vector")]
+pub struct EmbeddingDecodeError {
+    /// 嵌入在`data`中的位置
+    pub index: usize,
+}
+
+/// 服务端返回嵌入的格式。
+///
+/// `Base64`以小端序`f32`字节的base64字符串返回嵌入，响应体通常比JSON浮点数组小得多，
+/// 适合高维或批量嵌入；可以用[`EmbeddingResponse::into_float_vectors`]统一得到浮点向量。
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum EncodingFormat {
@@ -91,6 +130,73 @@ impl EmbeddingResponse {
     pub fn embedding_vectors_decoded(&self) -> Vec<Vec<f32>> {
         self.data.iter().filter_map(|e| e.vector()).collect()
     }
+
+    /// 将所有嵌入按顺序转换为浮点向量，base64编码的嵌入会被解码
+    ///
+    /// 无论请求时使用哪种[`EncodingFormat`]，都会得到相同的结果。
+    ///
+    /// # 错误
+    ///
+    /// 如果某个嵌入无法解码，则返回[`EmbeddingDecodeError`]。
+    pub fn into_float_vectors(self) -> Result<Vec<Vec<f32>>, EmbeddingDecodeError> {
+        self.data
+            .into_iter()
+            .enumerate()
+            .map(|(index, e)| e.to_float().ok_or(EmbeddingDecodeError { index }))
+            .collect()
+    }
+
+    /// 计算所有嵌入两两之间的余弦相似度，返回N×N矩阵
+    ///
+    /// 矩阵第`i`行第`j`列为`data[i]`与`data[j]`的余弦相似度，base64编码的嵌入会先被解码。
+    /// 零向量与任何向量的相似度均为0。
+    ///
+    /// # 错误
+    ///
+    /// 如果某个嵌入无法解码，或各嵌入的维度不一致，则返回[`SimilarityError`]。
+    pub fn similarity_matrix(&self) -> Result<Vec<Vec<f32>>, SimilarityError> {
+        let vectors = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(index, e)| e.vector().ok_or(SimilarityError::Undecodable { index }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(expected) = vectors.first().map(Vec::len)
+            && let Some((index, vector)) = vectors
+                .iter()
+                .enumerate()
+                .find(|(_, vector)| vector.len() != expected)
+        {
+            return Err(SimilarityError::DimensionMismatch {
+                index,
+                expected,
+                actual: vector.len(),
+            });
+        }
+
+        let norms: Vec<f32> = vectors
+            .iter()
+            .map(|vector| vector.iter().map(|x| x * x).sum::<f32>().sqrt())
+            .collect();
+
+        let n = vectors.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in i..n {
+                let denominator = norms[i] * norms[j];
+                let similarity = if denominator == 0.0 {
+                    0.0
+                } else {
+                    let dot: f32 = vectors[i].iter().zip(&vectors[j]).map(|(a, b)| a * b).sum();
+                    dot / denominator
+                };
+                matrix[i][j] = similarity;
+                matrix[j][i] = similarity;
+            }
+        }
+        Ok(matrix)
+    }
 }
 
 impl Embedding {
@@ -181,6 +287,8 @@ impl Serialize for Input {
         match self {
             Input::Text(text) => serializer.serialize_str(text),
             Input::List(list) => list.serialize(serializer),
+            Input::Tokens(tokens) => tokens.serialize(serializer),
+            Input::TokenBatches(batches) => batches.serialize(serializer),
         }
     }
 }
@@ -375,21 +483,90 @@ impl<'a> From<&'a str> for Input {
     }
 }
 
+/// 可以作为嵌入文本输入的字符串类型。
+///
+/// 元素类型实现了此trait的`Vec<T>`与`&[T]`可以转换为[`Input::List`]。
+/// 泛型转换没有直接约束`T: AsRef<str>`，因为那会与令牌输入的`From<Vec<u32>>`、`From<Vec<Vec<u32>>>`冲突；
+/// 常见的字符串类型及其引用均已实现，自定义的字符串类型可以自行实现此trait。
+pub trait InputText {
+    /// 返回输入文本
+    fn as_input_text(&self) -> &str;
+}
+
+impl InputText for str {
+    fn as_input_text(&self) -> &str {
+        self
+    }
+}
+
+impl InputText for String {
+    fn as_input_text(&self) -> &str {
+        self
+    }
+}
+
+impl InputText for Cow<'_, str> {
+    fn as_input_text(&self) -> &str {
+        self
+    }
+}
+
+impl InputText for Box<str> {
+    fn as_input_text(&self) -> &str {
+        self
+    }
+}
+
+impl InputText for Arc<str> {
+    fn as_input_text(&self) -> &str {
+        self
+    }
+}
+
+impl<T: InputText + ?Sized> InputText for &T {
+    fn as_input_text(&self) -> &str {
+        (**self).as_input_text()
+    }
+}
+
 impl<'a, T> From<&'a [T]> for Input
 where
-    T: AsRef<str>,
+    T: InputText,
 {
     fn from(slice: &'a [T]) -> Self {
-        Input::List(slice.iter().map(|s| s.as_ref().to_string()).collect())
+        Input::List(
+            slice
+                .iter()
+                .map(|s| s.as_input_text().to_string())
+                .collect(),
+        )
     }
 }
 
 impl<T> From<Vec<T>> for Input
 where
-    T: AsRef<str>,
+    T: InputText,
 {
     fn from(vec: Vec<T>) -> Self {
-        Input::List(vec.into_iter().map(|s| s.as_ref().to_string()).collect())
+        Input::List(vec.iter().map(|s| s.as_input_text().to_string()).collect())
+    }
+}
+
+impl From<Vec<u32>> for Input {
+    fn from(tokens: Vec<u32>) -> Self {
+        Input::Tokens(tokens)
+    }
+}
+
+impl<'a> From<&'a [u32]> for Input {
+    fn from(tokens: &'a [u32]) -> Self {
+        Input::Tokens(tokens.to_vec())
+    }
+}
+
+impl From<Vec<Vec<u32>>> for Input {
+    fn from(batches: Vec<Vec<u32>>) -> Self {
+        Input::TokenBatches(batches)
     }
 }
 
@@ -405,6 +582,62 @@ mod tests {
     use base64::Engine;
     use serde_json;
 
+    fn response_with(data: Vec<EmbeddingData>) -> EmbeddingResponse {
+        EmbeddingResponse {
+            model: "text-embedding-3-small".to_string(),
+            object: "list".to_string(),
+            data: data
+                .into_iter()
+                .enumerate()
+                .map(|(index, embedding)| Embedding {
+                    embedding,
+                    index,
+                    object: "embedding".to_string(),
+                })
+                .collect(),
+            usage: Usage {
+                prompt_tokens: 0,
+                total_tokens: 0,
+            },
+            extra_fields: None,
+        }
+    }
+
+    #[test]
+    fn test_similarity_matrix() {
+        let response = response_with(vec![
+            EmbeddingData::Float(vec![1.0, 0.0]),
+            EmbeddingData::Float(vec![0.0, 2.0]),
+            EmbeddingData::Float(vec![1.0, 1.0]),
+            EmbeddingData::Float(vec![0.0, 0.0]),
+        ]);
+        let matrix = response.similarity_matrix().unwrap();
+
+        assert_eq!(matrix.len(), 4);
+        assert!((matrix[0][0] - 1.0).abs() < 1e-6);
+        assert!(matrix[0][1].abs() < 1e-6);
+        assert!((matrix[0][2] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(matrix[2][0], matrix[0][2]);
+        assert_eq!(matrix[3], vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_similarity_matrix_ragged() {
+        let response = response_with(vec![
+            EmbeddingData::Float(vec![1.0, 0.0]),
+            EmbeddingData::Float(vec![1.0, 0.0, 0.0]),
+        ]);
+
+        assert_eq!(
+            response.similarity_matrix(),
+            Err(SimilarityError::DimensionMismatch {
+                index: 1,
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+
     #[test]
     fn test_into_input() {
         let _input: Input = Input::from("test");
@@ -413,6 +646,46 @@ mod tests {
         let _input: Input = Input::from(vec!["t1".to_string(), "t2".to_string()]);
     }
 
+    #[test]
+    fn test_text_list_into_input() {
+        let owned = ["a".to_string(), "b".to_string()];
+        let expected = serde_json::json!(["a", "b"]);
+
+        let inputs = [
+            Input::from(&owned[..]),
+            Input::from(owned.iter().collect::<Vec<&String>>()),
+            Input::from(vec![Cow::Borrowed("a"), Cow::Owned("b".to_string())]),
+            Input::from(vec![Box::<str>::from("a"), Box::from("b")]),
+            Input::from(vec![Arc::<str>::from("a"), Arc::from("b")]),
+            Input::from(&["a", "b"][..]),
+        ];
+        for input in inputs {
+            assert_eq!(serde_json::to_value(&input).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_token_input_serialization() {
+        let input = Input::from(vec![1u32, 2, 3]);
+        assert_eq!(serde_json::to_string(&input).unwrap(), "[1,2,3]");
+
+        let input = Input::from(&[4u32, 5][..]);
+        assert_eq!(serde_json::to_string(&input).unwrap(), "[4,5]");
+    }
+
+    #[test]
+    fn test_token_batches_serialization() {
+        let input = Input::from(vec![vec![1u32, 2], vec![3]]);
+        assert_eq!(serde_json::to_string(&input).unwrap(), "[[1,2],[3]]");
+
+        let param = crate::modules::embeddings::params::EmbeddingsParam::new(
+            "text-embedding-3-small",
+            vec![vec![7u32, 8]],
+        );
+        let body = param.take().body.unwrap();
+        assert_eq!(body["input"], serde_json::json!([[7, 8]]));
+    }
+
     #[test]
     fn test_encoding_format_serialization() {
         assert_eq!(
@@ -491,10 +764,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_into_float_vectors() {
+        let bytes: Vec<u8> = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        let response = response_with(vec![
+            EmbeddingData::Float(vec![1.0, 2.0]),
+            EmbeddingData::Base64(base64::engine::general_purpose::STANDARD.encode(&bytes)),
+        ]);
+        assert_eq!(
+            response.into_float_vectors().unwrap(),
+            vec![vec![1.0, 2.0], vec![0.5, -1.0]]
+        );
+
+        let response = response_with(vec![
+            EmbeddingData::Float(vec![1.0]),
+            EmbeddingData::Base64("not base64!".to_string()),
+        ]);
+        assert_eq!(
+            response.into_float_vectors(),
+            Err(EmbeddingDecodeError { index: 1 })
+        );
+    }
+
     #[test]
     fn test_decode_base64_embedding() {
         // Create a simple test with some float values and encode them to base64
-        let original_values = vec![1.0f32, 2.0f32, 3.0f32];
+        let original_values = [1.0f32, 2.0f32, 3.0f32];
         let bytes: Vec<u8> = original_values
             .iter()
             .flat_map(|f| f.to_le_bytes())
@@ -10,8 +10,11 @@ pub mod models;
 /// Re-exports for easier access to module functionalities.
 pub use chat::Chat;
 pub use chat::ChatParam;
-pub use chat::tool_parameters::Parameters;
+pub use chat::stream::{
+    ChatCompletionAccumulator, ChatCompletionStreamExt, ReasoningEvent, StreamUsage,
+};
+pub use chat::tool_parameters::{Parameters, ToolParameters};
 pub use chat::types::*;
-pub use completions::{Completions, CompletionsParam};
+pub use completions::{Completions, CompletionsParam, Prompt};
 pub use embeddings::{Embeddings, EmbeddingsParam, EncodingFormat};
 pub use models::{Models, ModelsParam};
@@ -1,6 +1,6 @@
 use super::params::ModelsParam;
-use super::types::{Model, ModelsData};
-use crate::common::types::{InParam, RetryCount, Timeout};
+use super::types::{Model, ModelDeletion, ModelsData};
+use crate::common::types::{ConnectTimeout, InParam, RetryCount, Timeout};
 use crate::error::OpenAIError;
 use crate::service::client::HttpClient;
 use crate::service::request::{RequestBuilder, RequestSpec};
@@ -14,15 +14,31 @@ impl Models {
         Models { http_client }
     }
 
+    /// 检索单个模型的信息。
+    ///
+    /// 发送`GET /models/{model}`请求，可用于在发送请求前确认模型是否存在。
+    /// 模型不存在时通常返回404，可通过`OpenAIError::status_code`判断。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenAI::from_env()?;
+    ///     let model = client.models().retrieve("gpt-4o", ModelsParam::new()).await?;
+    ///     println!("{} is owned by {:?}", model.id, model.owned_by);
+    ///     Ok(())
+    /// }
+    /// ```
     pub async fn retrieve(&self, model: &str, param: ModelsParam) -> Result<Model, OpenAIError> {
         let inner = param.take();
 
         let http_params = RequestSpec::new(
-            |config| format!("{}/models/{}", config.base_url(), model),
-            move |config, request| {
+            |config| config.resource_url(&format!("/models/{model}")),
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
         );
@@ -30,15 +46,53 @@ impl Models {
         self.http_client.get_json(http_params).await
     }
 
+    /// 删除一个微调模型。
+    ///
+    /// 发送`DELETE /models/{model}`请求，只有微调模型的所有者才能删除该模型。
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use openai4rs::*;
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = OpenAI::from_env()?;
+    ///     let deletion = client
+    ///         .models()
+    ///         .delete("ft:gpt-4o-mini:acme::abc123", ModelsParam::new())
+    ///         .await?;
+    ///     assert!(deletion.deleted);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete(
+        &self,
+        model: &str,
+        param: ModelsParam,
+    ) -> Result<ModelDeletion, OpenAIError> {
+        let inner = param.take();
+
+        let http_params = RequestSpec::new(
+            |config| config.resource_url(&format!("/models/{model}")),
+            move |_, request| {
+                let mut builder = RequestBuilder::new(request);
+                Self::apply_request_settings(&mut builder, inner);
+                builder.take()
+            },
+        );
+
+        self.http_client.delete_json(http_params).await
+    }
+
+    /// 列出当前可用的模型。
     pub async fn list(&self, param: ModelsParam) -> Result<ModelsData, OpenAIError> {
         let inner = param.take();
 
         let http_params = RequestSpec::new(
-            |config| format!("{}/models", config.base_url()),
-            move |config, request| {
+            |config| config.resource_url("/models"),
+            move |_, request| {
                 let mut builder = RequestBuilder::new(request);
                 Self::apply_request_settings(&mut builder, inner);
-                builder.bearer_auth(config.api_key());
                 builder.take()
             },
         );
@@ -55,12 +109,51 @@ impl Models {
 
         *builder.request_mut().headers_mut() = params.headers;
 
+        for (key, value) in params.query {
+            builder.query(key, value);
+        }
+
         if let Some(time) = params.extensions.get::<Timeout>() {
             builder.timeout(time.0);
         }
 
+        if let Some(time) = params.extensions.get::<ConnectTimeout>() {
+            builder.connect_timeout(time.0);
+        }
+
         if let Some(retry) = params.extensions.get::<RetryCount>() {
             builder.request_mut().extensions_mut().insert(retry.clone());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::request::Request;
+
+    #[test]
+    fn test_apply_request_settings_query() {
+        let param = ModelsParam::new()
+            .limit(10)
+            .query("provider", "openai")
+            .limit(50);
+
+        let mut builder = RequestBuilder::new(Request::new(
+            reqwest::Method::GET,
+            "https://gateway.example.com/v1/models".to_string(),
+        ));
+        Models::apply_request_settings(&mut builder, param.take());
+
+        let request = builder
+            .take()
+            .to_reqwest(&reqwest::Client::new())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://gateway.example.com/v1/models?provider=openai&limit=50"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! 用于测试的`ModelsData`构造器。
+//!
+//! 仅在启用`test-util`特性（或在本crate的测试中）时可用。
+
+use super::types::{Model, ModelsData};
+use serde_json::{Value, json};
+
+const MOCK_CREATED: i64 = 1_700_000_000;
+
+impl ModelsData {
+    /// 构造一个包含给定模型ID的模型列表，`ids`为空时得到空列表。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use openai4rs::models::ModelsData;
+    ///
+    /// let models = ModelsData::mock(&["gpt-4o", "gpt-4o-mini"]);
+    /// assert_eq!(models.data[1].id, "gpt-4o-mini");
+    /// assert_eq!(models.to_mock_json()["data"][0]["id"], "gpt-4o");
+    /// ```
+    pub fn mock(ids: &[&str]) -> Self {
+        let data = ids
+            .iter()
+            .map(|id| Model {
+                created: MOCK_CREATED,
+                id: id.to_string(),
+                object: Some("model".to_string()),
+                owned_by: Some("mock".to_string()),
+                context_length: None,
+                pricing: None,
+                architecture: None,
+                extra_fields: None,
+            })
+            .collect();
+        Self {
+            data,
+            object: Some("list".to_string()),
+            extra_fields: None,
+        }
+    }
+
+    /// 转换为服务端返回的JSON格式，供[`MockTransport`](crate::MockTransport)或模拟HTTP服务器使用。
+    ///
+    /// 只输出`id`、`object`、`created`与`owned_by`，提供商特有的字段不会输出。
+    pub fn to_mock_json(&self) -> Value {
+        let data: Vec<Value> = self
+            .data
+            .iter()
+            .map(|model| {
+                json!({
+                    "id": model.id,
+                    "object": model.object,
+                    "created": model.created,
+                    "owned_by": model.owned_by,
+                })
+            })
+            .collect();
+        json!({
+            "object": self.object.as_deref().unwrap_or("list"),
+            "data": data,
+        })
+    }
+}
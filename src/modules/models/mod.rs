@@ -1,4 +1,6 @@
 pub mod handler;
+#[cfg(any(test, feature = "test-util"))]
+mod mock;
 pub mod params;
 pub mod types;
 
@@ -1,38 +1,36 @@
-use crate::common::types::{InParam, JsonBody, RetryCount, Timeout};
-use http::{
-    HeaderValue,
-    header::{IntoHeaderName, USER_AGENT},
-};
+use crate::common::params::impl_request_options;
+use crate::common::types::{InParam, JsonBody};
 use serde_json::Value;
-use std::time::Duration;
 
 pub struct ModelsParam {
     inner: InParam,
 }
 
+impl_request_options!(ModelsParam);
+
 impl ModelsParam {
     pub fn new() -> Self {
         Self {
             inner: InParam::new(),
         }
     }
-    /// 超时时间。HTTP请求超时时间，覆盖客户端的全局设置。
-    ///
-    /// 此字段不会在请求体中序列化。
-    pub fn timeout(mut self, timeout: Duration) -> Self {
-        self.inner.extensions.insert(Timeout(timeout));
-        self
-    }
 
-    /// 用户代理。HTTP请求User-Agent，覆盖客户端的全局设置。
-    pub fn user_agent(mut self, user_agent: HeaderValue) -> Self {
-        self.inner.headers.insert(USER_AGENT, user_agent);
+    /// 限制返回的模型数量，以`limit`查询参数发送。
+    ///
+    /// OpenAI官方接口会忽略此参数，适用于支持分页的网关。重复调用时以最后一次为准。
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.inner.query.retain(|(key, _)| key != "limit");
+        self.inner
+            .query
+            .push(("limit".to_string(), limit.to_string()));
         self
     }
 
-    /// 设置HTTP请求头信息。
-    pub fn header<K: IntoHeaderName>(mut self, key: K, val: HeaderValue) -> Self {
-        self.inner.headers.insert(key, val);
+    /// 添加URL查询参数，例如网关提供的供应商过滤条件。
+    ///
+    /// 与[`ConfigBuilder::query`](crate::ConfigBuilder::query)设置的全局参数同名时，以此处的值为准。
+    pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, val: V) -> Self {
+        self.inner.query.push((key.into(), val.into()));
         self
     }
 
@@ -44,14 +42,6 @@ impl ModelsParam {
             .insert(key.into(), val.into());
         self
     }
-
-    /// 重试次数。HTTP请求重试次数，覆盖客户端的全局设置。
-    ///
-    /// 此字段不会在请求体中序列化。
-    pub fn retry_count(mut self, retry_count: usize) -> Self {
-        self.inner.extensions.insert(RetryCount(retry_count));
-        self
-    }
 }
 
 impl ModelsParam {
@@ -1,3 +1,4 @@
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -7,9 +8,54 @@ pub struct Model {
     pub id: String,
     pub object: Option<String>,
     pub owned_by: Option<String>,
+    /// 模型支持的最大上下文长度（令牌数），由OpenRouter等提供商返回
+    pub context_length: Option<u64>,
+    /// 模型的价格信息，由OpenRouter等提供商返回
+    pub pricing: Option<ModelPricing>,
+    /// 模型的输入输出模态与分词器信息，由OpenRouter等提供商返回
+    pub architecture: Option<ModelArchitecture>,
+    /// 未被建模的字段，`context_length`、`pricing`与`architecture`的原始值也会保留在这里
     pub extra_fields: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// 模型的价格信息。
+///
+/// 价格为每个令牌（或每次请求、每张图片）的美元金额。提供商通常以字符串返回以避免精度损失，
+/// 因此这里保留原始字符串，需要计算时可以自行解析。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModelPricing {
+    /// 每个提示令牌的价格
+    pub prompt: Option<String>,
+    /// 每个补全令牌的价格
+    pub completion: Option<String>,
+    /// 每次请求的固定价格
+    pub request: Option<String>,
+    /// 每张输入图片的价格
+    pub image: Option<String>,
+    /// 其他价格项，例如`web_search`、`input_cache_read`
+    #[serde(flatten)]
+    pub extra_fields: HashMap<String, serde_json::Value>,
+}
+
+/// 模型的架构信息。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModelArchitecture {
+    /// 模态的简写，例如`text+image->text`
+    pub modality: Option<String>,
+    /// 支持的输入模态，例如`text`、`image`、`file`
+    #[serde(default)]
+    pub input_modalities: Vec<String>,
+    /// 支持的输出模态
+    #[serde(default)]
+    pub output_modalities: Vec<String>,
+    /// 使用的分词器，例如`GPT`、`Llama3`
+    pub tokenizer: Option<String>,
+    /// 指令格式，例如`chatml`
+    pub instruct_type: Option<String>,
+    #[serde(flatten)]
+    pub extra_fields: HashMap<String, serde_json::Value>,
+}
+
 #[derive(Debug)]
 pub struct ModelsData {
     pub data: Vec<Model>,
@@ -17,6 +63,44 @@ pub struct ModelsData {
     pub extra_fields: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// 删除模型的结果。
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelDeletion {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
+}
+
+impl Model {
+    /// 检查模型是否归属于指定的所有者（例如`openai`、`system`），忽略大小写。
+    ///
+    /// 未返回`owned_by`的提供商（例如OpenRouter）始终返回`false`。
+    pub fn is_owned_by(&self, owner: &str) -> bool {
+        self.owned_by
+            .as_deref()
+            .is_some_and(|owned_by| owned_by.eq_ignore_ascii_case(owner))
+    }
+
+    /// 检查模型是否支持指定的输入模态（例如`image`），忽略大小写。
+    ///
+    /// 未返回`architecture`的提供商始终返回`false`。
+    pub fn supports_input_modality(&self, modality: &str) -> bool {
+        self.architecture.as_ref().is_some_and(|architecture| {
+            architecture
+                .input_modalities
+                .iter()
+                .any(|input| input.eq_ignore_ascii_case(modality))
+        })
+    }
+
+    /// 读取提供商特有的字段，例如OpenRouter的`name`、`top_provider`。
+    pub fn extra_field(&self, key: &str) -> Option<&serde_json::Value> {
+        self.extra_fields
+            .as_ref()
+            .and_then(|fields| fields.get(key))
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for Model {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -39,6 +123,9 @@ impl<'de> serde::Deserialize<'de> for Model {
                 let mut id = None;
                 let mut object = None;
                 let mut owned_by = None;
+                let mut context_length = None;
+                let mut pricing = None;
+                let mut architecture = None;
                 let mut extra_fields = HashMap::new();
 
                 while let Some(key) = map.next_key::<String>()? {
@@ -67,6 +154,23 @@ impl<'de> serde::Deserialize<'de> for Model {
                             }
                             owned_by = Some(map.next_value()?);
                         }
+                        // 提供商特有的字段始终以原始值保留在`extra_fields`中，
+                        // 格式不符合预期时对应的类型化字段为`None`，而不是使反序列化失败
+                        "context_length" => {
+                            let value: serde_json::Value = map.next_value()?;
+                            context_length = value.as_u64();
+                            extra_fields.insert(key, value);
+                        }
+                        "pricing" => {
+                            let value: serde_json::Value = map.next_value()?;
+                            pricing = ModelPricing::deserialize(&value).ok();
+                            extra_fields.insert(key, value);
+                        }
+                        "architecture" => {
+                            let value: serde_json::Value = map.next_value()?;
+                            architecture = ModelArchitecture::deserialize(&value).ok();
+                            extra_fields.insert(key, value);
+                        }
                         other => {
                             let value: serde_json::Value = map.next_value()?;
                             extra_fields.insert(other.to_string(), value);
@@ -87,6 +191,9 @@ impl<'de> serde::Deserialize<'de> for Model {
                     id,
                     object,
                     owned_by,
+                    context_length,
+                    pricing,
+                    architecture,
                     extra_fields,
                 })
             }
@@ -1,12 +1,20 @@
+use super::interceptor::{InterceptorChain, RateLimitInterceptor};
+use super::logging::{RequestLogger, buffer_response, is_event_stream};
 use super::request::{Request, RequestBuilder, RequestSpec};
-use crate::common::types::RetryCount;
-use crate::config::Config;
-use crate::error::{ApiError, ApiErrorKind, OpenAIError, RequestError};
+use super::transport::Transport;
+use crate::common::rate_limit::RateLimitSnapshot;
+#[cfg(target_arch = "wasm32")]
+use crate::common::types::Timeout;
+use crate::common::types::{ConnectTimeout, RetryCount};
+use crate::config::{ApiKeyProvider, AuthScheme, Config, RetryCallback, RetryPolicy};
+use crate::error::{ApiError, OpenAIError, RequestError};
+use crate::utils::runtime;
 use crate::utils::traits::AsyncFrom;
-use rand::Rng;
 use reqwest::{Client, Response};
-use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, SystemTime};
+use tracing::Instrument;
 
 /// 处理实际发送HTTP请求的HTTP请求执行器。
 ///
@@ -17,17 +25,26 @@ use std::time::Duration;
 ///
 /// 执行器对reqwest客户端使用读写锁，以允许并发读取，
 /// 同时确保配置更改时的线程安全更新。
+///
+/// 连接超时是客户端级别的设置，请求级别的`connect_timeout`需要单独的客户端。
+/// 这些客户端按连接超时缓存并复用，配置更改重建客户端时一并清空。
+///
+/// 执行器还记录最近一次成功响应中的速率限制信息，不占用配置中的拦截器链。
 pub(crate) struct HttpExecutor {
     config: RwLock<Config>,
     reqwest_client: RwLock<Client>,
+    connect_timeout_clients: Mutex<HashMap<Duration, Client>>,
+    rate_limit: RateLimitInterceptor,
 }
 
 impl HttpExecutor {
     pub fn new(config: Config) -> HttpExecutor {
-        let reqwest_client = config.http().build_reqwest_client();
+        let reqwest_client = Self::reqwest_client_for(&config);
         HttpExecutor {
             config: RwLock::new(config),
             reqwest_client: RwLock::new(reqwest_client),
+            connect_timeout_clients: Mutex::new(HashMap::new()),
+            rate_limit: RateLimitInterceptor::new(),
         }
     }
 
@@ -42,12 +59,15 @@ impl HttpExecutor {
     }
 
     pub fn rebuild_reqwest_client(&self) {
-        let new_client = {
-            let config_guard = self.config_read();
-            config_guard.http().build_reqwest_client()
-        };
+        let new_client = Self::reqwest_client_for(&self.config_read());
         let mut client_guard = self.client_write();
         *client_guard = new_client;
+        self.connect_timeout_clients_lock().clear();
+    }
+
+    /// 最近一次成功响应中的速率限制信息
+    pub fn rate_limit_status(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limit.snapshot()
     }
 
     /// 根据请求参数发送post请求
@@ -67,14 +87,48 @@ impl HttpExecutor {
     {
         self.send(reqwest::Method::GET, params).await
     }
+
+    /// 根据请求参数发送delete请求
+    pub async fn delete<U, F>(&self, params: RequestSpec<U, F>) -> Result<Response, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+    {
+        self.send(reqwest::Method::DELETE, params).await
+    }
 }
 
 impl HttpExecutor {
+    /// 优先使用配置中外部构建的客户端，否则根据HTTP配置构建。
+    fn reqwest_client_for(config: &Config) -> Client {
+        match config.http_client() {
+            Some(client) => client.clone(),
+            None => config.http().build_reqwest_client(),
+        }
+    }
+
     #[inline]
     fn client_read(&self) -> RwLockReadGuard<'_, Client> {
         self.reqwest_client.read().expect("Failed to acquire read lock on reqwest_client. This indicates a serious internal error, possibly due to a poisoned RwLock.")
     }
 
+    #[inline]
+    fn connect_timeout_clients_lock(&self) -> MutexGuard<'_, HashMap<Duration, Client>> {
+        self.connect_timeout_clients.lock().expect("Failed to acquire lock on connect_timeout_clients. This indicates a serious internal error, possibly due to a poisoned Mutex.")
+    }
+
+    /// 获取使用指定连接超时的客户端，首次使用时根据当前HTTP配置构建并缓存。
+    fn connect_timeout_client(&self, config: &Config, connect_timeout: Duration) -> Client {
+        self.connect_timeout_clients_lock()
+            .entry(connect_timeout)
+            .or_insert_with(|| {
+                let mut http = config.http().clone();
+                http.with_connect_timeout(connect_timeout);
+                http.build_reqwest_client()
+            })
+            .clone()
+    }
+
     #[inline]
     pub fn client_write(&self) -> RwLockWriteGuard<'_, Client> {
         self.reqwest_client.write().expect("Failed to acquire write lock on reqwest_client during rebuild. This indicates a serious internal error, possibly due to a poisoned RwLock.")
@@ -89,9 +143,23 @@ impl HttpExecutor {
         U: FnOnce(&Config) -> String,
         F: FnOnce(&Config, Request) -> Request,
     {
-        let client = self.client_read().clone();
+        // 设置了动态密钥提供者时，在构建请求前获取（或刷新）密钥
+        let provider = self.config_read().api_key_provider().cloned();
+        let provided_api_key = match &provider {
+            Some(provider) => Some(provider.api_key().await?),
+            None => None,
+        };
 
-        let (retry_count, request) = {
+        let (
+            sender,
+            retry_count,
+            mut request,
+            interceptors,
+            on_retry,
+            retry_policy,
+            logger,
+            api_key_refresh,
+        ) = {
             let config_guard = self.config_read();
 
             let mut request = Request::new(method, (params.url_fn)(&config_guard));
@@ -100,19 +168,77 @@ impl HttpExecutor {
 
             let mut request_builder = RequestBuilder::new(request);
 
+            let api_key = provided_api_key
+                .as_deref()
+                .unwrap_or_else(|| config_guard.api_key());
+            request_builder.auth(config_guard.auth_scheme(), api_key)?;
+
             HttpExecutor::apply_global_http_settings(&config_guard, &mut request_builder);
 
             request = request_builder.take();
 
+            // 请求级别的重试次数（包括0）始终优先于全局设置
             let retry_count = match request.extensions().get::<RetryCount>() {
-                Some(retry) if retry.0 != 0 => retry.0,
-                _ => config_guard.retry_count(),
+                Some(retry) => retry.0,
+                None => config_guard.retry_count(),
             };
 
-            (retry_count, request)
+            let sender = match (
+                config_guard.transport(),
+                request.extensions().get::<ConnectTimeout>(),
+            ) {
+                (Some(transport), _) => Sender::Custom(Arc::clone(transport)),
+                // 连接超时是客户端级别的设置，请求级别的覆盖使用按超时缓存的客户端；
+                // wasm32下连接超时不生效，无需创建额外的客户端
+                (None, Some(ConnectTimeout(connect_timeout)))
+                    if cfg!(not(target_arch = "wasm32"))
+                        && config_guard.http_client().is_none()
+                        && *connect_timeout != config_guard.http().connect_timeout() =>
+                {
+                    Sender::Reqwest(self.connect_timeout_client(&config_guard, *connect_timeout))
+                }
+                (None, _) => Sender::Reqwest(self.client_read().clone()),
+            };
+
+            (
+                sender,
+                retry_count,
+                request,
+                config_guard.interceptors().clone(),
+                config_guard.on_retry().cloned(),
+                config_guard.retry_policy().clone(),
+                RequestLogger::new(&config_guard),
+                provider.map(|provider| ApiKeyRefresh {
+                    provider,
+                    auth_scheme: config_guard.auth_scheme().clone(),
+                }),
+            )
         };
 
-        HttpExecutor::send_with_retries(request, retry_count as u32, client).await
+        interceptors.execute_request_interceptors(&mut request)?;
+
+        let span = tracing::debug_span!(
+            "openai4rs_request",
+            method = %request.method(),
+            url = %request.url()
+        );
+        span.in_scope(|| logger.log_request(&request));
+
+        let response = HttpExecutor::send_with_retries(
+            request,
+            retry_count as u32,
+            &sender,
+            &interceptors,
+            on_retry.as_ref(),
+            retry_policy.as_ref(),
+            &logger,
+            api_key_refresh.as_ref(),
+        )
+        .instrument(span)
+        .await?;
+
+        self.rate_limit.record(response.headers());
+        Ok(response)
     }
 
     fn apply_global_http_settings(config: &Config, request_builder: &mut RequestBuilder) {
@@ -123,18 +249,42 @@ impl HttpExecutor {
             }
         });
 
+        // 仅在本地未设置时才应用全局查询参数
+        for (key, value) in config.http().query() {
+            if !request_builder.has_query(key) {
+                request_builder.query(key.clone(), value.clone());
+            }
+        }
+
         // 仅在本地未设置时才应用全局主体字段
         config.http().bodys().iter().for_each(|(k, v)| {
             if !request_builder.has_body_field(k) {
                 request_builder.body_field(k, v.clone());
             }
         });
+
+        // wasm32下客户端不支持全局超时，改为在请求上设置
+        #[cfg(target_arch = "wasm32")]
+        if request_builder
+            .request()
+            .extensions()
+            .get::<Timeout>()
+            .is_none()
+        {
+            request_builder.timeout(config.http().timeout());
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn send_with_retries(
-        request: Request,
+        mut request: Request,
         retry_count: u32,
-        client: reqwest::Client,
+        sender: &Sender,
+        interceptors: &InterceptorChain,
+        on_retry: Option<&RetryCallback>,
+        retry_policy: &dyn RetryPolicy,
+        logger: &RequestLogger,
+        api_key_refresh: Option<&ApiKeyRefresh>,
     ) -> Result<Response, OpenAIError> {
         let mut attempts = 0;
         let max_attempts = retry_count.max(1);
@@ -142,145 +292,187 @@ impl HttpExecutor {
         loop {
             attempts += 1;
 
-            // Convert to reqwest RequestBuilder
-            let request_builder = request.to_reqwest(&client);
+            // 首次尝试使用构建请求时获取的密钥，重试前重新获取以使用轮换后的密钥
+            if attempts > 1
+                && let Some(api_key_refresh) = api_key_refresh
+            {
+                request = api_key_refresh.apply(request).await?;
+            }
+
+            let result = match sender.send(&request).await {
+                Ok(response) => logger.log_response(response, attempts).await,
+                Err(error) => Err(error),
+            };
 
-            match request_builder.send().await {
+            match result {
                 Ok(response) => {
                     // Check for retry-after header from the server
                     let retry_after = response
                         .headers()
                         .get(reqwest::header::RETRY_AFTER)
                         .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                        .map(Duration::from_secs);
+                        .and_then(|s| parse_retry_after(s, runtime::now()));
 
                     if response.status().is_success() {
+                        interceptors.execute_response_interceptors(&response)?;
+                        if !interceptors.wants_response_body() || is_event_stream(&response) {
+                            return Ok(response);
+                        }
+                        let (response, body) = buffer_response(response).await?;
+                        if let Some(body) = body {
+                            interceptors.execute_response_body_interceptors(&response, &body)?;
+                        }
                         return Ok(response);
-                    } else {
-                        let api_error = ApiError::async_from(response).await;
+                    }
 
-                        // Check if we should retry or return error with interceptors applied
-                        if attempts >= max_attempts || !api_error.is_retryable() {
-                            return Err(api_error.into());
-                        }
+                    let error: OpenAIError = ApiError::async_from(response).await.into();
+                    if attempts >= max_attempts {
+                        interceptors.execute_error_interceptors(&error);
+                        return Err(error);
+                    }
+
+                    // 由重试策略决定是否重试以及等待多久
+                    let Some(delay) = retry_policy.delay(attempts, &error, retry_after) else {
+                        interceptors.execute_error_interceptors(&error);
+                        return Err(error);
+                    };
 
-                        tracing::debug!(
-                            "Attempt {}/{}: Retrying after API error: {:?}",
-                            attempts,
-                            max_attempts,
-                            api_error
-                        );
-                        tokio::time::sleep(calculate_retry_delay(
-                            attempts,
-                            &api_error.kind,
-                            retry_after,
-                        ))
-                        .await;
+                    tracing::debug!(
+                        "Attempt {}/{}: Retrying after API error: {:?}",
+                        attempts,
+                        max_attempts,
+                        error
+                    );
+                    if let Some(on_retry) = on_retry {
+                        on_retry(attempts, max_attempts, &error, delay);
                     }
+                    runtime::sleep(delay).await;
                 }
-                Err(e) => {
-                    let request_error: RequestError = e.into();
-
-                    // Check if we should retry or return error with interceptors applied
-                    if attempts >= max_attempts || !request_error.is_retryable() {
-                        return Err(request_error.into());
+                Err(error) => {
+                    if attempts >= max_attempts {
+                        interceptors.execute_error_interceptors(&error);
+                        return Err(error);
                     }
 
+                    // 由重试策略决定是否重试以及等待多久
+                    let Some(delay) = retry_policy.delay(attempts, &error, None) else {
+                        interceptors.execute_error_interceptors(&error);
+                        return Err(error);
+                    };
+
                     tracing::debug!(
                         "Attempt {}/{}: Retrying after request error: {:?}",
                         attempts,
                         max_attempts,
-                        request_error
+                        error
                     );
-                    tokio::time::sleep(calculate_retry_delay_for_request_error(
-                        attempts,
-                        &request_error,
-                    ))
-                    .await;
+                    if let Some(on_retry) = on_retry {
+                        on_retry(attempts, max_attempts, &error, delay);
+                    }
+                    runtime::sleep(delay).await;
                 }
             }
         }
     }
 }
 
-const API_ERROR_DEFAULT_BASE_DELAY_MS: u64 = 500;
-const API_ERROR_INTERNAL_SERVER_BASE_DELAY_MS: u64 = 1000;
-const API_ERROR_RATE_LIMIT_BASE_DELAY_MS: u64 = 5000;
-const API_ERROR_MAX_DELAY_MS: u64 = 30_000;
+/// 重试前重新获取动态密钥所需的信息。
+struct ApiKeyRefresh {
+    provider: ApiKeyProvider,
+    auth_scheme: AuthScheme,
+}
 
-const REQUEST_ERROR_DEFAULT_BASE_DELAY_MS: u64 = 100;
-const REQUEST_ERROR_CONNECTION_BASE_DELAY_MS: u64 = 200;
-const REQUEST_ERROR_MAX_DELAY_MS: u64 = 10_000;
+impl ApiKeyRefresh {
+    /// 通过提供者获取密钥（缓存过期时刷新）并替换请求中的认证请求头。
+    async fn apply(&self, request: Request) -> Result<Request, OpenAIError> {
+        let api_key = self.provider.api_key().await?;
+        let mut request_builder = RequestBuilder::new(request);
+        request_builder.auth(&self.auth_scheme, &api_key)?;
+        Ok(request_builder.take())
+    }
+}
 
-const RETRY_AFTER_JITTER_MS: u64 = 1000;
+/// 单次尝试的发送方式。
+enum Sender {
+    /// 使用reqwest客户端发送
+    Reqwest(Client),
+    /// 使用配置中的自定义传输层发送
+    Custom(Arc<dyn Transport>),
+}
 
-/// 根据错误类型计算重试前的适当延迟。
-///
-/// 此函数实现带有抖动的指数退避策略，
-/// 并对速率限制错误和服务器错误进行特殊处理。
-///
-/// # 参数
-/// * `attempt` - 当前尝试次数（从1开始）
-/// * `error_kind` - 发生的API错误类型
-/// * `retry_after` - 服务器指定的可选重试延迟
+impl Sender {
+    async fn send(&self, request: &Request) -> Result<Response, OpenAIError> {
+        match self {
+            Sender::Reqwest(client) => request
+                .to_reqwest(client)
+                .send()
+                .await
+                .map_err(|e| RequestError::from(e).into()),
+            Sender::Custom(transport) => transport.send(request.clone()).await,
+        }
+    }
+}
+
+/// 解析`Retry-After`头的值。
 ///
-/// # 返回值
-/// 重试前等待的持续时间
-fn calculate_retry_delay(
-    attempt: u32,
-    error_kind: &ApiErrorKind,
-    retry_after: Option<Duration>,
-) -> Duration {
-    // 如果服务器指定了重试延迟，使用该延迟并添加抖动
-    if let Some(duration) = retry_after {
-        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..RETRY_AFTER_JITTER_MS));
-        return duration + jitter;
+/// 支持两种格式：
+/// - 秒数（delta-seconds），例如`5`；
+/// - HTTP日期（IMF-fixdate），例如`Tue, 21 Oct 2025 07:28:00 GMT`，转换为相对于`now`的时长，
+///   已经过去的日期视为立即重试。
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
     }
 
-    // 基础延迟因错误类型而异
-    let base_delay_ms = match error_kind {
-        ApiErrorKind::RateLimit => API_ERROR_RATE_LIMIT_BASE_DELAY_MS,
-        ApiErrorKind::InternalServer => API_ERROR_INTERNAL_SERVER_BASE_DELAY_MS,
-        _ => API_ERROR_DEFAULT_BASE_DELAY_MS,
-    };
-
-    // 指数退避：base_delay * 2^(attempt-1)
-    let delay_ms = base_delay_ms.saturating_mul(2u64.pow(attempt - 1));
-    // 将延迟限制在最大值内
-    let base_delay = Duration::from_millis(delay_ms.min(API_ERROR_MAX_DELAY_MS));
-
-    // 添加0-10%的抖动以防止雷鸣般涌入
-    let jitter_percent = rand::thread_rng().gen_range(0..10);
-    let jitter_ms = (base_delay.as_millis() as u64 * jitter_percent) / 100;
-    base_delay + Duration::from_millis(jitter_ms)
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
 }
 
-/// 根据请求错误计算重试前的适当延迟。
-///
-/// 此函数为网络级请求错误实现带有抖动的指数退避策略。
-///
-/// # 参数
-/// * `attempt` - 当前尝试次数（从1开始）
-/// * `error` - 发生的请求错误
-///
-/// # 返回值
-/// 重试前等待的持续时间
-fn calculate_retry_delay_for_request_error(attempt: u32, error: &RequestError) -> Duration {
-    // 基础延迟因错误类型而异
-    let base_delay_ms = match error {
-        RequestError::Timeout(_) => REQUEST_ERROR_DEFAULT_BASE_DELAY_MS,
-        RequestError::Connection(_) => REQUEST_ERROR_CONNECTION_BASE_DELAY_MS,
-        _ => REQUEST_ERROR_DEFAULT_BASE_DELAY_MS,
-    };
-
-    // 指数退避：base_delay * 2^(attempt-1)
-    let delay_ms = base_delay_ms.saturating_mul(2u64.pow(attempt - 1));
-    // 将延迟限制在最大值内
-    let base_delay = Duration::from_millis(delay_ms.min(REQUEST_ERROR_MAX_DELAY_MS));
-
-    // 添加0-10%的抖动以防止雷鸣般涌入
-    let jitter_percent = rand::thread_rng().gen_range(0..10);
-    let jitter_ms = (base_delay.as_millis() as u64 * jitter_percent) / 100;
-    base_delay + Duration::from_millis(jitter_ms)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let now = SystemTime::now();
+        assert_eq!(parse_retry_after("5", now), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-1", now), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = httpdate::parse_http_date("Tue, 21 Oct 2025 07:27:30 GMT").unwrap();
+        assert_eq!(
+            parse_retry_after("Tue, 21 Oct 2025 07:28:00 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+
+        // 已经过去的日期立即重试
+        let later = now + Duration::from_secs(3600);
+        assert_eq!(
+            parse_retry_after("Tue, 21 Oct 2025 07:28:00 GMT", later),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_connect_timeout_clients_are_cached() {
+        let executor = HttpExecutor::new(Config::new("test-key", "http://localhost:8000/v1"));
+        {
+            let config = executor.config_read();
+            executor.connect_timeout_client(&config, Duration::from_secs(1));
+            executor.connect_timeout_client(&config, Duration::from_secs(1));
+            assert_eq!(executor.connect_timeout_clients_lock().len(), 1);
+
+            executor.connect_timeout_client(&config, Duration::from_secs(2));
+            assert_eq!(executor.connect_timeout_clients_lock().len(), 2);
+        }
+
+        // 配置更改重建客户端时清空缓存
+        executor.rebuild_reqwest_client();
+        assert!(executor.connect_timeout_clients_lock().is_empty());
+    }
 }
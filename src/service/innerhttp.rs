@@ -1,28 +1,33 @@
 use super::request::RequestSpec;
+use crate::common::rate_limit::RateLimitSnapshot;
+use crate::common::types::ApiResponse;
 use crate::config::Config;
-use crate::error::{OpenAIError, ProcessingError};
+use crate::error::{ApiError, OpenAIError, ProcessingError, RequestError};
 use crate::service::executor::HttpExecutor;
 use crate::service::request::Request;
+use crate::utils::runtime;
 use eventsource_stream::{Event, EventStreamError, Eventsource};
 use futures::StreamExt;
 use http::HeaderValue;
+use reqwest::Response;
 use std::any::type_name;
 use std::sync::{RwLockReadGuard, RwLockWriteGuard};
 use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+/// 视为保活的SSE事件类型。
+const KEEPALIVE_EVENTS: [&str; 3] = ["ping", "keepalive", "heartbeat"];
 
 /// 用于处理流事件的结果类型。
 ///
 /// 此枚举表示处理流事件时可能出现的结果：
-/// - Skip: 应该忽略该事件（例如，空数据）
+/// - Keepalive: 保活事件，不转发但会通知保活回调
 /// - Data: 事件包含应转发的有效数据
 /// - Done: 流已完成
 /// - Error: 处理事件时发生错误
-enum SseEventResult<T>
-where
-    T: serde::de::DeserializeOwned,
-{
-    /// 跳过此事件（例如，空数据）
-    Skip,
+enum SseEventResult<T> {
+    /// 保活事件（`ping`等类型的事件或空数据事件），携带事件类型
+    Keepalive(String),
     /// 从事件中提取的有效数据
     Data(T),
     /// 流已完成
@@ -54,6 +59,11 @@ impl InnerHttp {
         }
     }
 
+    /// 最近一次成功响应中的速率限制信息。
+    pub fn rate_limit_status(&self) -> Option<RateLimitSnapshot> {
+        self.executor.rate_limit_status()
+    }
+
     /// 获取对配置的只读访问权限。
     pub fn config_read(&self) -> RwLockReadGuard<'_, Config> {
         self.executor.config_read()
@@ -64,6 +74,24 @@ impl InnerHttp {
         self.executor.config_write()
     }
 
+    /// 根据请求参数发送post请求并返回原始响应。
+    pub async fn post_raw<U, F>(&self, params: RequestSpec<U, F>) -> Result<Response, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+    {
+        self.executor.post(params).await
+    }
+
+    /// 根据请求参数发送get请求并返回原始响应。
+    pub async fn get_raw<U, F>(&self, params: RequestSpec<U, F>) -> Result<Response, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+    {
+        self.executor.get(params).await
+    }
+
     /// 根据请求参数发送post请求并反序列化JSON响应。
     pub async fn post_json<U, F, T>(&self, params: RequestSpec<U, F>) -> Result<T, OpenAIError>
     where
@@ -72,18 +100,27 @@ impl InnerHttp {
         T: serde::de::DeserializeOwned,
     {
         let res = self.executor.post(params).await?;
+        Self::json_response(res).await
+    }
 
+    /// 根据请求参数发送post请求并反序列化JSON响应，同时保留响应的状态码与响应头。
+    pub async fn post_json_with_response<U, F, T>(
+        &self,
+        params: RequestSpec<U, F>,
+    ) -> Result<ApiResponse<T>, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+        T: serde::de::DeserializeOwned,
+    {
+        let res = self.executor.post(params).await?;
         let status = res.status();
-        let url = res.url().clone();
-
-        res.json().await.map_err(|e| {
-            ProcessingError::JsonDeserialization {
-                error: e,
-                target_type: type_name::<T>().to_string(),
-                status_code: Some(status.as_u16()),
-                url: Some(url.to_string()),
-            }
-            .into()
+        let headers = res.headers().clone();
+        let body = Self::json_response(res).await?;
+        Ok(ApiResponse {
+            status,
+            headers,
+            body,
         })
     }
 
@@ -95,30 +132,113 @@ impl InnerHttp {
         T: serde::de::DeserializeOwned,
     {
         let res = self.executor.get(params).await?;
+        Self::json_response(res).await
+    }
+
+    /// 根据请求参数发送delete请求并反序列化JSON响应。
+    pub async fn delete_json<U, F, T>(&self, params: RequestSpec<U, F>) -> Result<T, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+        T: serde::de::DeserializeOwned,
+    {
+        let res = self.executor.delete(params).await?;
+        Self::json_response(res).await
+    }
 
+    /// 将响应体反序列化为JSON。
+    ///
+    /// 直接读取响应字节并使用`serde_json::from_slice`解析，不会先将整个响应体转换为`String`，
+    /// 因此大型响应（例如批量嵌入）不会产生额外的UTF-8校验与复制。
+    /// 仅在解析失败时才将响应体按UTF-8有损解码，与`serde_json`的错误、状态码和URL一起保存在
+    /// [`ProcessingError::JsonBody`]中便于调试。读取响应体失败时返回[`RequestError`]。
+    async fn json_response<T>(res: Response) -> Result<T, OpenAIError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
         let status = res.status();
         let url = res.url().clone();
 
-        res.json().await.map_err(|e| {
-            ProcessingError::JsonDeserialization {
-                error: e,
+        // 读取响应体失败属于传输错误，而不是反序列化错误
+        let bytes = res
+            .bytes()
+            .await
+            .map_err(|e| OpenAIError::from(RequestError::from(e)))?;
+
+        serde_json::from_slice(&bytes).map_err(|error| {
+            ProcessingError::JsonBody {
+                error,
+                raw: String::from_utf8_lossy(&bytes).into_owned(),
                 target_type: type_name::<T>().to_string(),
-                status_code: Some(status.as_u16()),
-                url: Some(url.to_string()),
+                status_code: status.as_u16(),
+                url: url.to_string(),
             }
             .into()
         })
     }
 
     /// 根据请求参数发送post请求,尝试接收sse,并反序列化JSON响应。
+    ///
+    /// 事件在后台任务中读取并通过通道转发。以下任一情况发生时，后台任务会立即退出并释放底层连接：
+    /// - 返回的流被丢弃；
+    /// - 传入的`cancel_token`被取消；
+    /// - 服务器发送`[DONE]`或连接结束。
+    ///
+    /// 无法反序列化的事件若为`{"error": {...}}`形式的错误对象，会作为[`OpenAIError::Api`]返回。
     pub async fn post_json_sse<U, F, T>(
         &self,
         params: RequestSpec<U, F>,
-    ) -> Result<tokio_stream::wrappers::ReceiverStream<Result<T, OpenAIError>>, OpenAIError>
+        cancel_token: Option<CancellationToken>,
+    ) -> Result<ReceiverStream<Result<T, OpenAIError>>, OpenAIError>
     where
         U: FnOnce(&Config) -> String,
         F: FnOnce(&Config, Request) -> Request,
         T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        self.post_sse(params, cancel_token, |data| {
+            // 尝试将事件数据反序列化为预期类型，失败时检查是否为服务端发送的错误事件
+            serde_json::from_str::<T>(&data).map_err(|_| {
+                if let Some(error) = ApiError::from_stream_event(&data) {
+                    return error.into();
+                }
+                ProcessingError::Conversion {
+                    raw: data,
+                    target_type: type_name::<T>().to_string(),
+                }
+                .into()
+            })
+        })
+        .await
+    }
+
+    /// 根据请求参数发送post请求,尝试接收sse,并原样返回每个事件的数据。
+    ///
+    /// 与[`InnerHttp::post_json_sse`]相同，保活事件会被跳过，`[DONE]`会结束流，
+    /// 但事件数据不会被反序列化。
+    pub async fn post_raw_sse<U, F>(
+        &self,
+        params: RequestSpec<U, F>,
+        cancel_token: Option<CancellationToken>,
+    ) -> Result<ReceiverStream<Result<String, OpenAIError>>, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+    {
+        self.post_sse(params, cancel_token, Ok).await
+    }
+
+    /// 发送sse请求，并使用`decode`将每个事件的数据转换为流中的元素。
+    async fn post_sse<U, F, T, D>(
+        &self,
+        params: RequestSpec<U, F>,
+        cancel_token: Option<CancellationToken>,
+        decode: D,
+    ) -> Result<ReceiverStream<Result<T, OpenAIError>>, OpenAIError>
+    where
+        U: FnOnce(&Config) -> String,
+        F: FnOnce(&Config, Request) -> Request,
+        T: Send + 'static,
+        D: Fn(String) -> Result<T, OpenAIError> + Send + 'static,
     {
         let RequestSpec { url_fn, builder_fn } = params;
         let params = RequestSpec::new(url_fn, move |config, request| {
@@ -132,25 +252,44 @@ impl InnerHttp {
         let res = self.executor.post(params).await?;
         let mut event_stream = res.bytes_stream().eventsource();
         let (tx, rx) = tokio::sync::mpsc::channel(32);
+        let on_keepalive = self.config_read().on_keepalive().cloned();
+        // 未提供令牌时使用一个永远不会被取消的令牌，以统一处理逻辑
+        let cancel_token = cancel_token.unwrap_or_default();
 
-        tokio::spawn(async move {
-            while let Some(event_result) = event_stream.next().await {
-                let process_result = Self::process_stream_event(event_result);
-                match process_result {
-                    SseEventResult::Skip => continue,
-                    SseEventResult::Data(chunk) => {
-                        if tx.send(Ok(chunk)).await.is_err() {
-                            break;
+        runtime::spawn(async move {
+            loop {
+                let event_result = tokio::select! {
+                    _ = cancel_token.cancelled() => break,
+                    _ = tx.closed() => break,
+                    event_result = event_stream.next() => match event_result {
+                        Some(event_result) => event_result,
+                        None => break,
+                    },
+                };
+
+                let item = match Self::process_stream_event(event_result, &decode) {
+                    SseEventResult::Keepalive(event_type) => {
+                        if let Some(on_keepalive) = &on_keepalive {
+                            on_keepalive(&event_type);
                         }
+                        continue;
                     }
+                    SseEventResult::Data(chunk) => Ok(chunk),
                     SseEventResult::Done => break,
-                    SseEventResult::Error(error) => {
-                        if tx.send(Err(error)).await.is_err() {
-                            break;
-                        }
-                    }
+                    SseEventResult::Error(error) => Err(error),
+                };
+
+                let sent = tokio::select! {
+                    _ = cancel_token.cancelled() => false,
+                    result = tx.send(item) => result.is_ok(),
+                };
+
+                if !sent {
+                    break;
                 }
             }
+            // 显式丢弃事件流以关闭底层的reqwest响应
+            drop(event_stream);
             drop(tx);
         });
 
@@ -158,33 +297,32 @@ impl InnerHttp {
     }
 
     /// 处理服务器发送的事件。
-    fn process_stream_event<T>(
+    ///
+    /// `ping`、`keepalive`、`heartbeat`类型的事件与空数据事件被视为保活事件，不会进入流中。
+    fn process_stream_event<T, D>(
         event_result: Result<Event, EventStreamError<reqwest::Error>>,
+        decode: &D,
     ) -> SseEventResult<T>
     where
-        T: serde::de::DeserializeOwned + Send + 'static,
+        D: Fn(String) -> Result<T, OpenAIError>,
     {
         match event_result {
             Ok(event) => {
-                // 如果数据为空就跳过这个事件
-                if event.data.is_empty() {
-                    return SseEventResult::Skip;
+                if event.data.is_empty()
+                    || KEEPALIVE_EVENTS
+                        .iter()
+                        .any(|name| event.event.eq_ignore_ascii_case(name))
+                {
+                    return SseEventResult::Keepalive(event.event);
                 }
 
                 // 检查sse完成标志
                 if event.data == "[DONE]" {
                     SseEventResult::Done
                 } else {
-                    // 尝试将事件数据反序列化为预期类型
-                    match serde_json::from_str::<T>(&event.data) {
+                    match decode(event.data) {
                         Ok(chunk) => SseEventResult::Data(chunk),
-                        Err(_) => SseEventResult::Error(
-                            ProcessingError::Conversion {
-                                raw: event.data,
-                                target_type: type_name::<T>().to_string(),
-                            }
-                            .into(),
-                        ),
+                        Err(error) => SseEventResult::Error(error),
                     }
                 }
             }
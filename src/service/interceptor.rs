@@ -0,0 +1,196 @@
+//! 请求拦截器。
+//!
+//! 拦截器可以在请求发送前修改请求，在收到响应或发生错误时观察结果，
+//! 适用于日志记录、注入追踪头、统计指标等横切关注点。
+//!
+//! 多个拦截器按照洋葱模型执行：请求阶段按注册顺序执行，响应和错误阶段按注册的逆序执行。
+//! 任何拦截器返回错误都会立即中止后续拦截器以及本次请求。
+
+use super::request::Request;
+use crate::common::rate_limit::{RateLimitInfo, RateLimitSnapshot};
+use crate::error::OpenAIError;
+use reqwest::Response;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// 在请求生命周期的各个阶段被调用的拦截器。
+///
+/// 所有方法都有默认的空实现，只需实现关心的阶段。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::*;
+///
+/// struct TraceId;
+///
+/// impl Interceptor for TraceId {
+///     fn on_request(&self, request: &mut Request) -> Result<(), OpenAIError> {
+///         request
+///             .headers_mut()
+///             .insert("x-trace-id", HeaderValue::from_static("trace-123"));
+///         Ok(())
+///     }
+/// }
+///
+/// let client = OpenAI::new("sk-...", "https://api.openai.com/v1");
+/// client.add_interceptor(TraceId);
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// 在请求发送前调用，可以修改请求。返回错误将阻止请求发送。
+    fn on_request(&self, _request: &mut Request) -> Result<(), OpenAIError> {
+        Ok(())
+    }
+
+    /// 在收到成功响应后调用。返回错误将使本次请求以该错误失败。
+    fn on_response(&self, _response: &Response) -> Result<(), OpenAIError> {
+        Ok(())
+    }
+
+    /// 是否需要读取响应体。返回`true`时，非流式的成功响应会被完整读取后传给
+    /// [`on_response_body`](Self::on_response_body)。
+    fn wants_response_body(&self) -> bool {
+        false
+    }
+
+    /// 在[`on_response`](Self::on_response)之后以完整的响应体调用，
+    /// 仅当[`wants_response_body`](Self::wants_response_body)返回`true`且响应不是事件流时触发。
+    /// 返回错误将使本次请求以该错误失败。
+    fn on_response_body(&self, _response: &Response, _body: &[u8]) -> Result<(), OpenAIError> {
+        Ok(())
+    }
+
+    /// 在请求最终失败（重试耗尽或不可重试）时调用。
+    fn on_error(&self, _error: &OpenAIError) {}
+}
+
+/// 按洋葱模型依次执行的拦截器链。
+#[derive(Clone, Default)]
+pub struct InterceptorChain {
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+impl InterceptorChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 在链的末尾添加一个拦截器。
+    pub fn push<I: Interceptor + 'static>(&mut self, interceptor: I) -> &mut Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// 按注册顺序执行请求拦截器。
+    pub fn execute_request_interceptors(&self, request: &mut Request) -> Result<(), OpenAIError> {
+        self.interceptors
+            .iter()
+            .try_for_each(|interceptor| interceptor.on_request(request))
+    }
+
+    /// 按注册的逆序执行响应拦截器。
+    pub fn execute_response_interceptors(&self, response: &Response) -> Result<(), OpenAIError> {
+        self.interceptors
+            .iter()
+            .rev()
+            .try_for_each(|interceptor| interceptor.on_response(response))
+    }
+
+    /// 是否有拦截器需要读取响应体。
+    pub fn wants_response_body(&self) -> bool {
+        self.interceptors
+            .iter()
+            .any(|interceptor| interceptor.wants_response_body())
+    }
+
+    /// 按注册的逆序执行响应体拦截器。
+    pub fn execute_response_body_interceptors(
+        &self,
+        response: &Response,
+        body: &[u8],
+    ) -> Result<(), OpenAIError> {
+        self.interceptors
+            .iter()
+            .rev()
+            .filter(|interceptor| interceptor.wants_response_body())
+            .try_for_each(|interceptor| interceptor.on_response_body(response, body))
+    }
+
+    /// 按注册的逆序执行错误拦截器。
+    pub fn execute_error_interceptors(&self, error: &OpenAIError) {
+        self.interceptors
+            .iter()
+            .rev()
+            .for_each(|interceptor| interceptor.on_error(error));
+    }
+}
+
+/// 记录最近一次成功响应中速率限制信息的内置拦截器。
+///
+/// 每次收到带有`x-ratelimit-*`响应头的成功响应时，用解析出的[`RateLimitInfo`]整体替换保存的快照，
+/// 因此读取到的各字段总是来自同一个响应。克隆得到的实例共享同一个快照。
+///
+/// 每个[`OpenAI`](crate::OpenAI)客户端内部已经记录速率限制信息，可以直接通过
+/// [`OpenAI::rate_limit_status`](crate::OpenAI::rate_limit_status)读取，无需添加此拦截器；
+/// 需要在客户端之外共享快照（例如多个客户端汇总到同一个快照）时再手动添加。
+#[derive(Clone, Default)]
+pub struct RateLimitInterceptor {
+    latest: Arc<Mutex<Option<RateLimitSnapshot>>>,
+}
+
+impl RateLimitInterceptor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回最近一次收到的速率限制信息，尚未收到带有速率限制响应头的响应时返回`None`。
+    pub fn snapshot(&self) -> Option<RateLimitSnapshot> {
+        self.latest().clone()
+    }
+
+    /// 从响应头解析速率限制信息并替换保存的快照，响应头中没有速率限制信息时保留原快照。
+    pub(crate) fn record(&self, headers: &http::HeaderMap) {
+        if let Some(info) = RateLimitInfo::from_headers(headers) {
+            *self.latest() = Some(RateLimitSnapshot::new(info));
+        }
+    }
+
+    fn latest(&self) -> MutexGuard<'_, Option<RateLimitSnapshot>> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Interceptor for RateLimitInterceptor {
+    fn on_response(&self, response: &Response) -> Result<(), OpenAIError> {
+        self.record(response.headers());
+        Ok(())
+    }
+}
+
+impl fmt::Debug for RateLimitInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimitInterceptor")
+            .field("latest", &*self.latest())
+            .finish()
+    }
+}
+
+impl fmt::Debug for InterceptorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterceptorChain")
+            .field("len", &self.interceptors.len())
+            .finish()
+    }
+}
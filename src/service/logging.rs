@@ -0,0 +1,365 @@
+//! 基于`tracing`的请求日志。
+//!
+//! 每个请求都会在`openai4rs_request`跨度内记录方法、URL、请求头与每次尝试的响应状态，
+//! 启用[`ConfigBuilder::log_bodies`](crate::ConfigBuilder::log_bodies)后还会记录请求体与响应体。
+//! 携带API密钥的请求头在记录前会被替换为`[REDACTED]`，密钥不会出现在日志中。
+
+use super::request::Request;
+use crate::config::{AuthScheme, Config};
+use crate::error::OpenAIError;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, HeaderName};
+use reqwest::Response;
+use std::fmt;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// 除认证方式指定的请求头外，始终视为敏感的请求头。
+const SENSITIVE_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "api-key",
+    "x-api-key",
+];
+
+/// 根据配置记录请求与响应。
+pub(crate) struct RequestLogger {
+    log_bodies: bool,
+    secret_header: Option<HeaderName>,
+}
+
+impl RequestLogger {
+    pub(crate) fn new(config: &Config) -> Self {
+        let secret_header = match config.auth_scheme() {
+            AuthScheme::Header { name } => Some(name.clone()),
+            _ => None,
+        };
+        Self {
+            log_bodies: config.log_bodies(),
+            secret_header,
+        }
+    }
+
+    /// 记录即将发送的请求，敏感请求头会被遮蔽。
+    pub(crate) fn log_request(&self, request: &Request) {
+        tracing::debug!(
+            headers = ?RedactedHeaders {
+                headers: request.headers(),
+                secret_headers: self.secret_header.as_slice(),
+            },
+            "sending request"
+        );
+
+        if self.log_bodies
+            && let Some(body) = request.body()
+        {
+            let body = serde_json::to_string(body).unwrap_or_default();
+            tracing::debug!(body = %body, "request body");
+        }
+    }
+
+    /// 记录一次尝试得到的响应状态。
+    ///
+    /// 启用`log_bodies`时，非流式响应的响应体会被完整读取并记录，随后重新构造响应返回。
+    pub(crate) async fn log_response(
+        &self,
+        response: Response,
+        attempt: u32,
+    ) -> Result<Response, OpenAIError> {
+        tracing::debug!(
+            attempt,
+            status = response.status().as_u16(),
+            "received response"
+        );
+
+        if !self.log_bodies || is_event_stream(&response) {
+            return Ok(response);
+        }
+
+        Self::log_response_body(response).await
+    }
+
+    async fn log_response_body(response: Response) -> Result<Response, OpenAIError> {
+        let (response, body) = buffer_response(response).await?;
+        if let Some(body) = body {
+            tracing::debug!(body = %String::from_utf8_lossy(&body), "response body");
+        }
+        Ok(response)
+    }
+}
+
+/// 完整读取响应体，并以读取到的内容重新构造响应返回。
+///
+/// `wasm32`下无法从缓冲的响应体重新构造响应，因此原样返回响应且不读取响应体。
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn buffer_response(
+    response: Response,
+) -> Result<(Response, Option<bytes::Bytes>), OpenAIError> {
+    use crate::error::RequestError;
+    use reqwest::ResponseBuilderExt;
+
+    let status = response.status();
+    let version = response.version();
+    let url = response.url().clone();
+    let headers = response.headers().clone();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| OpenAIError::from(RequestError::from(e)))?;
+
+    let mut builder = http::Response::builder()
+        .status(status)
+        .version(version)
+        .url(url);
+    if let Some(builder_headers) = builder.headers_mut() {
+        *builder_headers = headers;
+    }
+    let response = builder
+        .body(body.clone())
+        .expect("Rebuilding a response from valid parts cannot fail")
+        .into();
+    Ok((response, Some(body)))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn buffer_response(
+    response: Response,
+) -> Result<(Response, Option<bytes::Bytes>), OpenAIError> {
+    Ok((response, None))
+}
+
+pub(crate) fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"))
+}
+
+/// 以`Debug`格式输出请求头，敏感请求头的值会被遮蔽。
+struct RedactedHeaders<'a> {
+    headers: &'a HeaderMap,
+    secret_headers: &'a [HeaderName],
+}
+
+impl RedactedHeaders<'_> {
+    fn is_sensitive(&self, name: &HeaderName) -> bool {
+        SENSITIVE_HEADERS.contains(&name.as_str()) || self.secret_headers.contains(name)
+    }
+}
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (name, value) in self.headers {
+            if !self.is_sensitive(name) {
+                map.entry(&name.as_str(), &value.to_str().unwrap_or("<binary>"));
+                continue;
+            }
+            // 保留认证方案前缀（例如`Bearer`），便于确认使用了哪种认证方式
+            let redacted = match value.to_str().ok().and_then(|v| v.split_once(' ')) {
+                Some((scheme, _)) => format!("{scheme} {REDACTED}"),
+                None => REDACTED.to_string(),
+            };
+            map.entry(&name.as_str(), &redacted);
+        }
+        map.finish()
+    }
+}
+
+/// 以指定级别输出`tracing`事件，`tracing`的宏要求级别为常量。
+#[cfg(feature = "debug-logging")]
+macro_rules! event_at {
+    ($level:expr, $($arg:tt)+) => {
+        match $level {
+            tracing::Level::ERROR => tracing::error!($($arg)+),
+            tracing::Level::WARN => tracing::warn!($($arg)+),
+            tracing::Level::INFO => tracing::info!($($arg)+),
+            tracing::Level::DEBUG => tracing::debug!($($arg)+),
+            tracing::Level::TRACE => tracing::trace!($($arg)+),
+        }
+    };
+}
+
+/// 通过`tracing`记录每个请求与响应细节的拦截器，用于排查与兼容服务之间的问题。
+///
+/// 需要启用crate特性`debug-logging`。请求阶段记录方法、URL、查询参数、请求头与JSON请求体，
+/// 响应阶段记录状态码、响应头与原始响应体，请求最终失败时记录错误（API错误包含服务端返回的错误信息）。
+/// 流式（`text/event-stream`）响应不记录响应体，`wasm32`下也不记录响应体。
+///
+/// `Authorization`、`api-key`等携带密钥的请求头会被替换为`[REDACTED]`，
+/// 使用自定义认证请求头时可以通过[`redact_header`](Self::redact_header)追加。
+/// 超过[`max_body_len`](Self::max_body_len)字节的请求体与响应体会被截断。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::*;
+///
+/// let client = OpenAI::new("sk-...", "https://api.openai.com/v1");
+/// client.add_interceptor(
+///     DebugLoggingInterceptor::new()
+///         .level(tracing::Level::INFO)
+///         .max_body_len(1024),
+/// );
+/// ```
+#[cfg(feature = "debug-logging")]
+#[derive(Debug, Clone)]
+pub struct DebugLoggingInterceptor {
+    level: tracing::Level,
+    max_body_len: usize,
+    redacted_headers: Vec<HeaderName>,
+}
+
+#[cfg(feature = "debug-logging")]
+impl DebugLoggingInterceptor {
+    /// 默认的请求体与响应体长度上限（字节）
+    pub const DEFAULT_MAX_BODY_LEN: usize = 4096;
+
+    /// 以`DEBUG`级别记录，请求体与响应体最多保留[`DEFAULT_MAX_BODY_LEN`](Self::DEFAULT_MAX_BODY_LEN)字节。
+    pub fn new() -> Self {
+        Self {
+            level: tracing::Level::DEBUG,
+            max_body_len: Self::DEFAULT_MAX_BODY_LEN,
+            redacted_headers: Vec::new(),
+        }
+    }
+
+    /// 设置日志级别。
+    pub fn level(mut self, level: tracing::Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// 设置请求体与响应体的最大记录长度（字节），超出部分会被截断。
+    pub fn max_body_len(mut self, max_body_len: usize) -> Self {
+        self.max_body_len = max_body_len;
+        self
+    }
+
+    /// 追加一个需要遮蔽的请求头，可多次调用。
+    pub fn redact_header(mut self, name: HeaderName) -> Self {
+        self.redacted_headers.push(name);
+        self
+    }
+
+    fn redacted<'a>(&'a self, headers: &'a HeaderMap) -> RedactedHeaders<'a> {
+        RedactedHeaders {
+            headers,
+            secret_headers: &self.redacted_headers,
+        }
+    }
+}
+
+#[cfg(feature = "debug-logging")]
+impl Default for DebugLoggingInterceptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "debug-logging")]
+impl super::interceptor::Interceptor for DebugLoggingInterceptor {
+    fn on_request(&self, request: &mut Request) -> Result<(), OpenAIError> {
+        let body = request
+            .body()
+            .map(|body| serde_json::to_string(body).unwrap_or_default())
+            .unwrap_or_default();
+        event_at!(
+            self.level,
+            method = %request.method(),
+            url = %request.url(),
+            query = ?request.query(),
+            headers = ?self.redacted(request.headers()),
+            body = %truncate_body(&body, self.max_body_len),
+            "openai4rs request"
+        );
+        Ok(())
+    }
+
+    fn on_response(&self, response: &Response) -> Result<(), OpenAIError> {
+        event_at!(
+            self.level,
+            status = response.status().as_u16(),
+            url = %response.url(),
+            headers = ?self.redacted(response.headers()),
+            "openai4rs response"
+        );
+        Ok(())
+    }
+
+    fn wants_response_body(&self) -> bool {
+        true
+    }
+
+    fn on_response_body(&self, response: &Response, body: &[u8]) -> Result<(), OpenAIError> {
+        let body = String::from_utf8_lossy(body);
+        event_at!(
+            self.level,
+            url = %response.url(),
+            body = %truncate_body(&body, self.max_body_len),
+            "openai4rs response body"
+        );
+        Ok(())
+    }
+
+    fn on_error(&self, error: &OpenAIError) {
+        event_at!(self.level, error = %error, "openai4rs request failed");
+    }
+}
+
+/// 将超过`max_len`字节的内容截断到字符边界，并注明被截断的字节数。
+#[cfg(feature = "debug-logging")]
+fn truncate_body(body: &str, max_len: usize) -> std::borrow::Cow<'_, str> {
+    if body.len() <= max_len {
+        return body.into();
+    }
+    let mut end = max_len;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...({} bytes truncated)", &body[..end], body.len() - end).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use http::header::AUTHORIZATION;
+
+    #[test]
+    fn test_redacted_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_static("Bearer sk-secret-key"),
+        );
+        headers.insert("api-key", HeaderValue::from_static("azure-secret"));
+        headers.insert("x-custom-key", HeaderValue::from_static("custom-secret"));
+        headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+
+        let secret_header = HeaderName::from_static("x-custom-key");
+        let output = format!(
+            "{:?}",
+            RedactedHeaders {
+                headers: &headers,
+                secret_headers: std::slice::from_ref(&secret_header),
+            }
+        );
+
+        assert!(output.contains("Bearer [REDACTED]"));
+        assert!(output.contains("req-1"));
+        assert!(!output.contains("sk-secret-key"));
+        assert!(!output.contains("azure-secret"));
+        assert!(!output.contains("custom-secret"));
+    }
+
+    #[cfg(feature = "debug-logging")]
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("short", 10), "short");
+        assert_eq!(truncate_body("0123456789", 4), "0123...(6 bytes truncated)");
+        // 不会在多字节字符中间截断
+        assert_eq!(truncate_body("你好", 4), "你...(3 bytes truncated)");
+    }
+}
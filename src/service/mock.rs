@@ -0,0 +1,178 @@
+//! 用于测试的传输层。
+//!
+//! 仅在启用`test-util`特性（或在本crate的测试中）时可用。
+
+use super::request::Request;
+use super::transport::{Transport, TransportFuture};
+use crate::modules::chat::{ChatCompletion, ChatCompletionChunk};
+use http::StatusCode;
+use http::header::CONTENT_TYPE;
+use reqwest::ResponseBuilderExt;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// 按顺序返回预设响应的[`Transport`]，用于在不启动服务的情况下测试调用逻辑。
+///
+/// 每次尝试（包括重试）消耗队列中的一个响应，收到的请求会被记录下来供断言使用。
+/// 队列为空时收到请求会panic。克隆得到的实例共享同一个队列与请求记录，
+/// 因此可以在将其交给[`ConfigBuilder::transport`](crate::ConfigBuilder::transport)之前保留一个克隆。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let transport = MockTransport::new();
+///     transport
+///         .push_chat_completion(&ChatCompletion::mock("Hello!"))
+///         .push_chat_stream([ChatCompletionChunk::mock("Hel"), ChatCompletionChunk::mock("lo")]);
+///
+///     let client = Config::builder()
+///         .api_key("test-key")
+///         .base_url("https://api.test.com/v1")
+///         .transport(transport.clone())
+///         .build_openai()
+///         .unwrap();
+///
+///     let messages = vec![user!("Hi")];
+///     let response = client
+///         .chat()
+///         .create(ChatParam::new("mock-model", &messages))
+///         .await
+///         .unwrap();
+///     assert_eq!(response.content(), Some("Hello!"));
+///
+///     let completion = client
+///         .chat()
+///         .create_stream(ChatParam::new("mock-model", &messages))
+///         .await
+///         .unwrap()
+///         .collect_completion()
+///         .await
+///         .unwrap()
+///         .unwrap();
+///     assert_eq!(completion.content(), Some("Hello"));
+///
+///     assert_eq!(transport.requests().len(), 2);
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: VecDeque<MockResponse>,
+    requests: Vec<Request>,
+}
+
+struct MockResponse {
+    status: StatusCode,
+    content_type: &'static str,
+    body: String,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个成功的非流式聊天补全响应。
+    pub fn push_chat_completion(&self, completion: &ChatCompletion) -> &Self {
+        self.push_json(200, completion.to_mock_json())
+    }
+
+    /// 追加一个流式聊天补全响应，依次发送每个数据块，最后发送`[DONE]`。
+    pub fn push_chat_stream(&self, chunks: impl IntoIterator<Item = ChatCompletionChunk>) -> &Self {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {}\n\n", chunk.to_mock_json()));
+        }
+        body.push_str("data: [DONE]\n\n");
+        self.push(StatusCode::OK, "text/event-stream", body)
+    }
+
+    /// 追加一个任意的JSON响应，可用于补全、嵌入、模型等接口。
+    ///
+    /// # Panics
+    ///
+    /// `status`不是有效的HTTP状态码时panic。
+    pub fn push_json(&self, status: u16, body: Value) -> &Self {
+        let status = StatusCode::from_u16(status).expect("invalid HTTP status code");
+        self.push(status, "application/json", body.to_string())
+    }
+
+    /// 追加一个OpenAI格式的错误响应，例如`push_error(429, "Rate limit exceeded")`。
+    pub fn push_error(&self, status: u16, message: &str) -> &Self {
+        self.push_json(
+            status,
+            serde_json::json!({
+                "error": {
+                    "message": message,
+                    "type": "mock_error",
+                    "param": null,
+                    "code": null,
+                }
+            }),
+        )
+    }
+
+    /// 返回目前为止收到的所有请求（按接收顺序）。
+    pub fn requests(&self) -> Vec<Request> {
+        self.state().requests.clone()
+    }
+
+    /// 返回尚未被消耗的响应数量。
+    pub fn remaining(&self) -> usize {
+        self.state().responses.len()
+    }
+
+    fn push(&self, status: StatusCode, content_type: &'static str, body: String) -> &Self {
+        self.state().responses.push_back(MockResponse {
+            status,
+            content_type,
+            body,
+        });
+        self
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: Request) -> TransportFuture {
+        let response = {
+            let mut state = self.state();
+            let response = state.responses.pop_front();
+            let Some(response) = response else {
+                panic!(
+                    "MockTransport: no response queued for {} {}",
+                    request.method(),
+                    request.url()
+                );
+            };
+            state.requests.push(request.clone());
+            response
+        };
+
+        let mut builder = http::Response::builder()
+            .status(response.status)
+            .header(CONTENT_TYPE, response.content_type);
+        if let Ok(url) = reqwest::Url::parse(request.url()) {
+            builder = builder.url(url);
+        }
+        let response = builder
+            .body(response.body)
+            .expect("Building a mock response from valid parts cannot fail");
+
+        Box::pin(async move { Ok(response.into()) })
+    }
+}
@@ -3,7 +3,18 @@
 pub mod client;
 pub mod executor;
 pub mod innerhttp;
+pub mod interceptor;
+mod logging;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
 pub mod request;
+pub mod transport;
 
 pub(crate) use client::HttpClient;
+pub use interceptor::{Interceptor, InterceptorChain, RateLimitInterceptor};
+#[cfg(feature = "debug-logging")]
+pub use logging::DebugLoggingInterceptor;
+#[cfg(any(test, feature = "test-util"))]
+pub use mock::MockTransport;
 pub use request::{Request, RequestBuilder};
+pub use transport::Transport;
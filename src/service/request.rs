@@ -1,7 +1,9 @@
 use crate::Config;
-use crate::common::types::{JsonBody, Timeout};
+use crate::common::types::{ConnectTimeout, JsonBody, Timeout};
+use crate::config::AuthScheme;
+use crate::error::{OpenAIError, RequestError};
 use http::header::{AUTHORIZATION, AsHeaderName, IntoHeaderName};
-use http::{Extensions, HeaderMap, HeaderValue};
+use http::{Extensions, HeaderMap, HeaderName, HeaderValue};
 use reqwest::{Method, RequestBuilder as ReqwestRequestBuilder};
 use serde_json::Value;
 use std::time::Duration;
@@ -38,6 +40,7 @@ pub struct Request {
     method: Method,
     url: String,
     headers: HeaderMap<HeaderValue>,
+    query: Vec<(String, String)>,
     body: Option<JsonBody>,
     extensions: Extensions,
 }
@@ -48,6 +51,7 @@ impl Request {
             method,
             url,
             headers: HeaderMap::new(),
+            query: Vec::new(),
             body: None,
             extensions: Extensions::new(),
         }
@@ -83,6 +87,16 @@ impl Request {
         &mut self.headers
     }
 
+    #[inline]
+    pub fn query(&self) -> &[(String, String)] {
+        &self.query
+    }
+
+    #[inline]
+    pub fn query_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.query
+    }
+
     #[inline]
     pub fn body(&self) -> Option<&JsonBody> {
         self.body.as_ref()
@@ -113,6 +127,10 @@ impl Request {
             builder = builder.header(k, v);
         }
 
+        if !self.query.is_empty() {
+            builder = builder.query(&self.query);
+        }
+
         if let Some(body) = &self.body {
             builder = builder.json(body);
         }
@@ -151,20 +169,48 @@ impl RequestBuilder {
         self
     }
 
-    /// 添加认证请求头
-    pub fn bearer_auth(&mut self, token: &str) -> &mut Self {
-        let val = format!("Bearer {token}");
-        self.request.headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&val).unwrap_or_else(|_| {
-                panic!(
-                    "Unable to convert `api_key` to HeaderValue, please check if its value is valid"
-                )
-            }),
-        );
+    /// 添加URL查询参数
+    pub fn query<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> &mut Self {
+        self.request.query.push((key.into(), value.into()));
         self
     }
 
+    /// 按照指定的认证方式添加携带API密钥的请求头。
+    ///
+    /// # 错误
+    ///
+    /// 密钥包含换行符等不能出现在请求头中的字符时返回[`RequestError::InvalidApiKey`]。
+    pub fn auth(
+        &mut self,
+        auth_scheme: &AuthScheme,
+        api_key: &str,
+    ) -> Result<&mut Self, OpenAIError> {
+        match auth_scheme {
+            AuthScheme::Bearer => self.bearer_auth(api_key),
+            AuthScheme::Raw => self.api_key_header(AUTHORIZATION, api_key),
+            AuthScheme::Header { name } => self.api_key_header(name.clone(), api_key),
+            AuthScheme::None => Ok(self),
+        }
+    }
+
+    fn api_key_header(
+        &mut self,
+        name: HeaderName,
+        api_key: &str,
+    ) -> Result<&mut Self, OpenAIError> {
+        let value = HeaderValue::from_str(api_key).map_err(RequestError::InvalidApiKey)?;
+        self.request.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// 添加`Bearer`认证请求头，错误情况同[`auth`](Self::auth)。
+    pub fn bearer_auth(&mut self, token: &str) -> Result<&mut Self, OpenAIError> {
+        let value = HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(RequestError::InvalidApiKey)?;
+        self.request.headers.insert(AUTHORIZATION, value);
+        Ok(self)
+    }
+
     /// 添加请求体字段
     pub fn body_field<K: Into<String>, V: Into<Value>>(&mut self, key: K, value: V) -> &mut Self {
         self.request
@@ -189,11 +235,24 @@ impl RequestBuilder {
         self
     }
 
+    #[inline]
+    pub fn connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.request
+            .extensions
+            .insert(ConnectTimeout(connect_timeout));
+        self
+    }
+
     #[inline]
     pub fn has_header<K: AsHeaderName>(&self, key: K) -> bool {
         self.request.headers.contains_key(key)
     }
 
+    #[inline]
+    pub fn has_query(&self, key: &str) -> bool {
+        self.request.query.iter().any(|(k, _)| k == key)
+    }
+
     #[inline]
     pub fn has_body_field(&self, key: &str) -> bool {
         match self.request.body.as_ref() {
@@ -206,3 +265,52 @@ impl RequestBuilder {
         self.request
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_headers(auth_scheme: AuthScheme) -> HeaderMap {
+        let mut builder = RequestBuilder::new(Request::new(
+            Method::POST,
+            "https://api.example.com/v1/chat/completions".to_string(),
+        ));
+        builder.auth(&auth_scheme, "sk-test").unwrap();
+        builder.take().headers
+    }
+
+    #[test]
+    fn test_auth_scheme_bearer() {
+        let headers = auth_headers(AuthScheme::Bearer);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[AUTHORIZATION], "Bearer sk-test");
+    }
+
+    #[test]
+    fn test_auth_scheme_raw() {
+        let headers = auth_headers(AuthScheme::Raw);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[AUTHORIZATION], "sk-test");
+    }
+
+    #[test]
+    fn test_auth_scheme_header() {
+        let headers = auth_headers(AuthScheme::Header {
+            name: HeaderName::from_static("x-api-key"),
+        });
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["x-api-key"], "sk-test");
+    }
+
+    #[test]
+    fn test_auth_scheme_none() {
+        let headers = auth_headers(AuthScheme::None);
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn test_auth_scheme_default_is_bearer() {
+        let config = Config::new("sk-test", "https://api.example.com/v1");
+        assert_eq!(config.auth_scheme(), &AuthScheme::Bearer);
+    }
+}
@@ -0,0 +1,49 @@
+use super::request::Request;
+use crate::error::OpenAIError;
+use reqwest::Response;
+use std::future::Future;
+use std::pin::Pin;
+
+/// [`Transport::send`]返回的future。
+pub type TransportFuture = Pin<Box<dyn Future<Output = Result<Response, OpenAIError>> + Send>>;
+
+/// 发送单次HTTP请求的传输层。
+///
+/// 默认情况下请求通过内部的`reqwest::Client`发送。通过
+/// [`ConfigBuilder::transport`](crate::ConfigBuilder::transport)设置自定义传输层后，
+/// 每次尝试都会调用[`Transport::send`]，而认证、拦截器、重试、日志以及响应的反序列化
+/// （包括SSE流）仍按原样执行，因此可以用它在不启动服务的情况下测试完整的调用流程。
+///
+/// 传入的请求已经应用了认证信息、全局请求头与请求体字段。
+/// `reqwest::Response`可以由`http::Response`转换得到。
+///
+/// 启用`test-util`特性后可以使用现成的[`MockTransport`](crate::MockTransport)。
+///
+/// # 示例
+///
+/// ```rust
+/// use openai4rs::service::transport::{Transport, TransportFuture};
+/// use openai4rs::{Config, Request};
+///
+/// struct Echo;
+///
+/// impl Transport for Echo {
+///     fn send(&self, request: Request) -> TransportFuture {
+///         let body = serde_json::to_vec(&request.body()).unwrap();
+///         Box::pin(async move { Ok(http::Response::new(body).into()) })
+///     }
+/// }
+///
+/// let config = Config::builder()
+///     .api_key("test-key")
+///     .base_url("https://api.test.com/v1")
+///     .transport(Echo)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait Transport: Send + Sync {
+    /// 发送请求并返回响应。
+    ///
+    /// 返回的错误与非成功状态码的响应都会交给重试策略处理。
+    fn send(&self, request: Request) -> TransportFuture;
+}
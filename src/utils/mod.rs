@@ -11,4 +11,5 @@
 //! - [`AsyncFrom`]: A trait for asynchronous conversion between types (used internally).
 
 pub mod methods;
+pub(crate) mod runtime;
 pub mod traits;
@@ -0,0 +1,49 @@
+//! 屏蔽本地与`wasm32`平台差异的运行时工具。
+//!
+//! 本地平台使用tokio运行时；`wasm32-unknown-unknown`下没有多线程运行时与系统计时器，
+//! 改用浏览器事件循环（`wasm-bindgen-futures`）与`setTimeout`（`gloo-timers`）。
+
+use std::future::Future;
+use std::time::{Duration, SystemTime};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
+
+/// 当前系统时间。`wasm32`下`SystemTime::now`会panic，改为读取`Date.now()`。
+pub(crate) fn now() -> SystemTime {
+    #[cfg(not(target_arch = "wasm32"))]
+    return SystemTime::now();
+    #[cfg(target_arch = "wasm32")]
+    return SystemTime::UNIX_EPOCH
+        + web_time::SystemTime::now()
+            .duration_since(web_time::SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+}
+
+/// 在后台运行任务。
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(future);
+}
+
+/// 在后台运行任务。浏览器环境是单线程的，任务无需满足`Send`。
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+/// 异步等待指定时长。
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
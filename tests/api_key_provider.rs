@@ -0,0 +1,219 @@
+use openai4rs::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn counting_provider(ttl: Duration) -> (ApiKeyProvider, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let provider = ApiKeyProvider::new(ttl, move || {
+        let counter = counter.clone();
+        async move {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("token-{n}"))
+        }
+    });
+    (provider, calls)
+}
+
+#[tokio::test]
+async fn test_api_key_provider_is_cached() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(header("authorization", "Bearer token-1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(models::ModelsData::mock(&[]).to_mock_json()),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let (provider, calls) = counting_provider(Duration::from_secs(60));
+    let client = Config::builder()
+        .base_url(server.uri())
+        .api_key_provider(provider)
+        .retry_count(1)
+        .build_openai()
+        .unwrap();
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+    client.models().list(ModelsParam::new()).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_api_key_provider_refreshes_after_expiry() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(models::ModelsData::mock(&[]).to_mock_json()),
+        )
+        .mount(&server)
+        .await;
+
+    let (provider, calls) = counting_provider(Duration::ZERO);
+    let client = Config::builder()
+        .base_url(server.uri())
+        .api_key("static-key")
+        .api_key_provider(provider)
+        .retry_count(1)
+        .build_openai()
+        .unwrap();
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+    client.models().list(ModelsParam::new()).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].headers["authorization"], "Bearer token-1");
+    assert_eq!(requests[1].headers["authorization"], "Bearer token-2");
+}
+
+#[tokio::test]
+async fn test_api_key_provider_uncached() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(models::ModelsData::mock(&[]).to_mock_json()),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let provider = ApiKeyProvider::uncached(move || {
+        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+        async move { Ok(format!("token-{n}")) }
+    });
+    assert_eq!(provider.ttl(), Duration::ZERO);
+
+    let client = Config::builder()
+        .base_url(server.uri())
+        .api_key_provider(provider)
+        .retry_count(1)
+        .build_openai()
+        .unwrap();
+
+    for _ in 0..3 {
+        client.models().list(ModelsParam::new()).await.unwrap();
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    let tokens: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| {
+            request.headers["authorization"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        tokens,
+        ["Bearer token-1", "Bearer token-2", "Bearer token-3"]
+    );
+}
+
+#[tokio::test]
+async fn test_api_key_provider_uncached_refetches_on_retry() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(header("authorization", "Bearer token-2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(models::ModelsData::mock(&[]).to_mock_json()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let provider = ApiKeyProvider::uncached(move || {
+        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+        async move { Ok(format!("token-{n}")) }
+    });
+    let client = Config::builder()
+        .base_url(server.uri())
+        .api_key_provider(provider)
+        .retry_count(2)
+        .retry_policy(ExponentialBackoff {
+            internal_server_error_base_delay: Duration::from_millis(1),
+            ..ExponentialBackoff::without_jitter()
+        })
+        .build_openai()
+        .unwrap();
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+
+    // 首次尝试与重试各获取一次密钥
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].headers["authorization"], "Bearer token-1");
+    assert_eq!(requests[1].headers["authorization"], "Bearer token-2");
+}
+
+#[tokio::test]
+async fn test_api_key_provider_error() {
+    let server = MockServer::start().await;
+
+    let provider = ApiKeyProvider::new(Duration::from_secs(60), || async {
+        Err(OpenAIError::Processing(
+            openai4rs::error::ProcessingError::Unknown("token service unavailable".to_string()),
+        ))
+    });
+    let client = Config::builder()
+        .base_url(server.uri())
+        .api_key_provider(provider)
+        .build_openai()
+        .unwrap();
+
+    let result = client.models().list(ModelsParam::new()).await;
+
+    assert!(result.unwrap_err().is_processing_error());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_api_key_provider_invalid_header_value() {
+    let server = MockServer::start().await;
+
+    let provider = ApiKeyProvider::uncached(|| async { Ok("bad\nkey".to_string()) });
+    let client = Config::builder()
+        .base_url(server.uri())
+        .api_key_provider(provider)
+        .build_openai()
+        .unwrap();
+
+    let error = client.models().list(ModelsParam::new()).await.unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            OpenAIError::Request(openai4rs::error::RequestError::InvalidApiKey(_))
+        ),
+        "{error:?}"
+    );
+    assert!(!error.to_string().contains("bad\nkey"));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,114 @@
+use openai4rs::*;
+use wiremock::matchers::{header, header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_azure_chat_path_and_header() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/openai/deployments/my-deployment/chat/completions"))
+        .and(query_param("api-version", "2024-10-21"))
+        .and(header("api-key", "azure-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(ChatCompletion::mock("Hello from Azure").to_mock_json()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .azure(server.uri(), "my-deployment", "2024-10-21")
+        .api_key("azure-key")
+        .retry_count(1)
+        .build_openai()
+        .unwrap();
+
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("Hello from Azure"));
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("authorization"));
+}
+
+#[tokio::test]
+async fn test_azure_embeddings_path() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/openai/deployments/embedding-deployment/embeddings"))
+        .and(query_param("api-version", "2024-10-21"))
+        .and(header_exists("api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": [
+                { "object": "embedding", "index": 0, "embedding": [0.1, 0.2] }
+            ],
+            "usage": { "prompt_tokens": 2, "total_tokens": 2 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .azure(
+            format!("{}/", server.uri()),
+            "embedding-deployment",
+            "2024-10-21",
+        )
+        .api_key("azure-key")
+        .retry_count(1)
+        .build_openai()
+        .unwrap();
+
+    let response = client
+        .embeddings()
+        .create(EmbeddingsParam::new("text-embedding-3-small", "hello"))
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 1);
+}
+
+#[tokio::test]
+async fn test_openai_azure_constructor() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/openai/deployments/gpt-4o-deployment/chat/completions",
+        ))
+        .and(query_param("api-version", "2024-10-21"))
+        .and(header("api-key", "azure-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(ChatCompletion::mock("Hello from Azure").to_mock_json()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::azure(
+        &format!("{}/", server.uri()),
+        "azure-key",
+        "gpt-4o-deployment",
+        "2024-10-21",
+    );
+
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("Hello from Azure"));
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("authorization"));
+}
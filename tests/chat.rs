@@ -0,0 +1,504 @@
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use openai4rs::*;
+use std::io::Write;
+use wiremock::matchers::{header_regex, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_chat_per_request_base_url() {
+    let default_server = MockServer::start().await;
+    let mirror_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(ChatCompletion::mock("default").to_mock_json()),
+        )
+        .mount(&default_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ChatCompletion::mock("mirror").to_mock_json()),
+        )
+        .expect(1)
+        .mount(&mirror_server)
+        .await;
+
+    let client = OpenAI::new("test-key", &default_server.uri());
+    let messages = vec![user!("hello")];
+
+    let response = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages).base_url(format!("{}/v1", mirror_server.uri())))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("mirror"));
+    assert!(default_server.received_requests().await.unwrap().is_empty());
+
+    // 未设置时仍使用客户端的基础URL
+    let response = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("default"));
+    assert_eq!(client.base_url(), default_server.uri());
+}
+
+/// 返回一个不再接受新连接的地址：监听队列被占满后，新的连接请求不会得到响应，连接一直挂起直到超时。
+async fn unresponsive_address() -> (tokio::net::TcpListener, Vec<tokio::net::TcpStream>, String) {
+    let socket = tokio::net::TcpSocket::new_v4().unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let listener = socket.listen(1).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut backlog = Vec::new();
+    for _ in 0..64 {
+        match tokio::time::timeout(
+            std::time::Duration::from_millis(200),
+            tokio::net::TcpStream::connect(addr),
+        )
+        .await
+        {
+            Ok(Ok(stream)) => backlog.push(stream),
+            _ => break,
+        }
+    }
+
+    (listener, backlog, format!("http://{addr}"))
+}
+
+#[tokio::test]
+async fn test_chat_per_request_connect_timeout() {
+    let (_listener, _backlog, base_url) = unresponsive_address().await;
+
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(base_url)
+        .connect_timeout(std::time::Duration::from_secs(30))
+        .build_openai()
+        .unwrap();
+
+    let messages = vec![user!("hello")];
+    let started = std::time::Instant::now();
+    let result = client
+        .chat()
+        .create(
+            ChatParam::new("gpt-4o", &messages)
+                .connect_timeout(std::time::Duration::from_millis(300))
+                .no_retry(),
+        )
+        .await;
+    let elapsed = started.elapsed();
+
+    // 使用客户端级别的30秒连接超时时请求会一直挂起，请求级别的覆盖使其很快失败
+    assert!(result.is_err());
+    assert!(
+        elapsed >= std::time::Duration::from_millis(300),
+        "elapsed: {elapsed:?}"
+    );
+    assert!(
+        elapsed < std::time::Duration::from_secs(5),
+        "elapsed: {elapsed:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_chat_per_request_connect_timeout_reuses_client() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ChatCompletion::mock("ok").to_mock_json()),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .connect_timeout(std::time::Duration::from_secs(30))
+        .build_openai()
+        .unwrap();
+
+    let messages = vec![user!("hello")];
+    for _ in 0..2 {
+        let response = client
+            .chat()
+            .create(
+                ChatParam::new("gpt-4o", &messages)
+                    .connect_timeout(std::time::Duration::from_secs(2)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content(), Some("ok"));
+    }
+}
+
+#[tokio::test]
+async fn test_chat_structured_api_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "message": "This model's maximum context length is 128000 tokens. However, your messages resulted in 130532 tokens. Please reduce the length of the messages.",
+                "type": "invalid_request_error",
+                "param": "messages",
+                "code": "context_length_exceeded"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hello")];
+    let error = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap_err();
+
+    assert!(error.is_context_length_exceeded());
+    let api_error = error.as_api_error().unwrap();
+    assert_eq!(api_error.status, 400);
+    assert_eq!(api_error.error_type(), Some("invalid_request_error"));
+    assert_eq!(api_error.code(), Some("context_length_exceeded"));
+    assert_eq!(api_error.param(), Some("messages"));
+    assert!(
+        api_error
+            .message
+            .starts_with("This model's maximum context length")
+    );
+}
+
+#[tokio::test]
+async fn test_chat_gzip_response() {
+    let server = MockServer::start().await;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&serde_json::to_vec(&ChatCompletion::mock("compressed").to_mock_json()).unwrap())
+        .unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header_regex("accept-encoding", "gzip"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .insert_header("content-type", "application/json")
+                .set_body_raw(compressed, "application/json"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("compressed"));
+}
+
+#[tokio::test]
+async fn test_chat_gzip_override() {
+    let server = MockServer::start().await;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&serde_json::to_vec(&ChatCompletion::mock("gzip only").to_mock_json()).unwrap())
+        .unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header_regex("accept-encoding", "^gzip$"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_raw(compressed, "application/json"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .accept_compression(false)
+        .gzip(true)
+        .build()
+        .unwrap();
+    let client = OpenAI::with_config(config);
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("gzip only"));
+}
+
+#[tokio::test]
+async fn test_chat_accept_compression_disabled() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ChatCompletion::mock("plain").to_mock_json()),
+        )
+        .mount(&server)
+        .await;
+
+    let config = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .accept_compression(false)
+        .build()
+        .unwrap();
+    let client = OpenAI::with_config(config);
+    let messages = vec![user!("hello")];
+    client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("accept-encoding"));
+}
+
+#[tokio::test]
+async fn test_chat_create_overrides_stream() {
+    let transport = MockTransport::new();
+    transport.push_chat_completion(&ChatCompletion::mock("unary"));
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url("https://api.test.com/v1")
+        .transport(transport.clone())
+        .build_openai()
+        .unwrap();
+
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create(
+            ChatParam::new("gpt-4o", &messages)
+                .body("stream", true)
+                .include_usage(true),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("unary"));
+
+    let body = transport.requests()[0].body().unwrap().clone();
+    assert_eq!(body["stream"], false);
+    assert!(!body.contains_key("stream_options"));
+}
+
+#[tokio::test]
+async fn test_chat_create_stream_overrides_stream() {
+    let transport = MockTransport::new();
+    transport.push_chat_stream([ChatCompletionChunk::mock("streamed")]);
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url("https://api.test.com/v1")
+        .transport(transport.clone())
+        .build_openai()
+        .unwrap();
+
+    let messages = vec![user!("hello")];
+    let completion = client
+        .chat()
+        .create_stream(
+            ChatParam::new("gpt-4o", &messages)
+                .body("stream", false)
+                .include_usage(true),
+        )
+        .await
+        .unwrap()
+        .collect_completion()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(completion.content(), Some("streamed"));
+
+    let body = transport.requests()[0].body().unwrap().clone();
+    assert_eq!(body["stream"], true);
+    assert_eq!(body["stream_options"]["include_usage"], true);
+}
+
+#[tokio::test]
+async fn test_chat_global_query_params() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(query_param("tenant", "acme"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ChatCompletion::mock("ok").to_mock_json()),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .query("api-version", "2024-10-21")
+        .query("tenant", "acme")
+        .build_openai()
+        .unwrap();
+    let messages = vec![user!("hello")];
+
+    client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages).query("api-version", "2025-01-01"))
+        .await
+        .unwrap();
+
+    let queries: Vec<Vec<(String, String)>> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| request.url.query_pairs().into_owned().collect())
+        .collect();
+    assert_eq!(
+        queries[0],
+        vec![
+            ("api-version".to_string(), "2024-10-21".to_string()),
+            ("tenant".to_string(), "acme".to_string()),
+        ]
+    );
+    // 请求级别的同名参数覆盖全局参数，且不会重复
+    assert_eq!(
+        queries[1],
+        vec![
+            ("api-version".to_string(), "2025-01-01".to_string()),
+            ("tenant".to_string(), "acme".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_chat_create_with_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(ChatCompletion::mock("hi").to_mock_json())
+                .insert_header("x-request-id", "req-123")
+                .insert_header("x-ratelimit-remaining-requests", "59"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create_with_response(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, http::StatusCode::OK);
+    assert_eq!(response.request_id(), Some("req-123"));
+    assert_eq!(
+        response.header("x-ratelimit-remaining-requests"),
+        Some("59")
+    );
+    assert_eq!(response.header("x-missing"), None);
+    assert_eq!(response.into_body().content(), Some("hi"));
+}
+
+#[tokio::test]
+async fn test_chat_rate_limit_headers() {
+    use std::time::Duration;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(ChatCompletion::mock("hi").to_mock_json())
+                .insert_header("x-ratelimit-limit-requests", "60")
+                .insert_header("x-ratelimit-remaining-requests", "0")
+                .insert_header("x-ratelimit-reset-requests", "6m0s"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hello")];
+    let response = client
+        .chat()
+        .create_with_response(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    let rate_limit = response.rate_limit().unwrap();
+    assert_eq!(rate_limit.limit_requests, Some(60));
+    assert_eq!(rate_limit.remaining_requests, Some(0));
+    assert_eq!(rate_limit.reset_requests, Some(Duration::from_secs(360)));
+    assert_eq!(rate_limit.limit_tokens, None);
+}
+
+#[tokio::test]
+async fn test_chat_create_cancellation_token() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(ChatCompletion::mock("late").to_mock_json())
+                .set_delay(std::time::Duration::from_secs(10)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hello")];
+    let token = tokio_util::sync::CancellationToken::new();
+
+    let cancel = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        cancel.cancel();
+    });
+
+    let started = std::time::Instant::now();
+    let error = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages).cancellation_token(token))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        OpenAIError::Request(error::RequestError::Cancelled)
+    ));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
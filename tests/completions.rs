@@ -0,0 +1,43 @@
+use openai4rs::*;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_completions_batched_prompts() {
+    let server = MockServer::start().await;
+
+    // 服务端不保证按`index`顺序返回选择
+    Mock::given(method("POST"))
+        .and(path("/completions"))
+        .and(body_partial_json(serde_json::json!({"prompt": ["a", "b"]})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "cmpl-batch",
+            "object": "text_completion",
+            "created": 1700000000,
+            "model": "gpt-3.5-turbo-instruct",
+            "choices": [
+                {"index": 1, "text": "answer b", "finish_reason": "stop", "logprobs": null},
+                {"index": 0, "text": "answer a", "finish_reason": "stop", "logprobs": null}
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let prompts = ["a", "b"];
+    let completion = client
+        .completions()
+        .create(CompletionsParam::new("gpt-3.5-turbo-instruct", prompts))
+        .await
+        .unwrap();
+
+    assert_eq!(completion.choices.len(), 2);
+    for (prompt_index, prompt) in prompts.iter().enumerate() {
+        let choices = completion.choices_for_prompt(prompt_index, 1);
+        assert_eq!(choices.len(), 1);
+        assert_eq!(choices[0].index, prompt_index);
+        assert_eq!(choices[0].prompt_index(1), prompt_index);
+        assert_eq!(choices[0].text, format!("answer {prompt}"));
+    }
+}
@@ -1,5 +1,7 @@
 use http::HeaderValue;
-use openai4rs::Config;
+use openai4rs::config::http::{Certificate, HttpConfig, HttpConfigBuilder};
+use openai4rs::error::ConfigError;
+use openai4rs::{Config, OpenAI, OpenAIError};
 use std::time::Duration;
 
 #[test]
@@ -43,6 +45,117 @@ fn test_config_builder_defaults() {
     assert_eq!(config.connect_timeout(), Duration::from_secs(10)); // 默认值
     assert_eq!(config.proxy(), None); // 默认值
     assert_eq!(config.user_agent(), None); // 默认值
+    assert_eq!(config.http().pool_max_idle_per_host(), usize::MAX);
+    assert_eq!(
+        config.http().pool_idle_timeout(),
+        Some(Duration::from_secs(90))
+    );
+    assert!(!config.http().http2_prior_knowledge());
+    assert_eq!(config.http().tcp_keepalive(), Some(Duration::from_secs(15)));
+    assert!(config.http().accept_compression());
+}
+
+#[test]
+fn test_http_config_compression_overrides() {
+    let http = HttpConfig::builder().build().unwrap();
+    assert!(http.gzip());
+    assert!(http.brotli());
+
+    let http = HttpConfig::builder()
+        .accept_compression(false)
+        .gzip(true)
+        .build()
+        .unwrap();
+    assert!(http.gzip());
+    assert!(!http.brotli());
+
+    let http = HttpConfig::builder()
+        .gzip(false)
+        .brotli(false)
+        .build()
+        .unwrap();
+    assert!(http.accept_compression());
+    assert!(!http.gzip());
+    assert!(!http.brotli());
+    let _client = http.build_reqwest_client();
+
+    let config = Config::builder()
+        .api_key("test-key")
+        .base_url("https://api.test.com/v1")
+        .brotli(false)
+        .build()
+        .unwrap();
+    assert!(config.http().gzip());
+    assert!(!config.http().brotli());
+}
+
+#[tokio::test]
+async fn test_config_builder_connection_pool() {
+    let config = Config::builder()
+        .api_key("test-key")
+        .base_url("https://api.test.com/v1")
+        .pool_max_idle_per_host(8)
+        .pool_idle_timeout(Duration::from_secs(30))
+        .http2_prior_knowledge(true)
+        .tcp_keepalive(Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    let http = config.http();
+    assert_eq!(http.pool_max_idle_per_host(), 8);
+    assert_eq!(http.pool_idle_timeout(), Some(Duration::from_secs(30)));
+    assert!(http.http2_prior_knowledge());
+    assert_eq!(http.tcp_keepalive(), Some(Duration::from_secs(60)));
+
+    // 设置会传递给底层客户端，构建客户端不应失败
+    let _client = OpenAI::with_config(config);
+}
+
+#[test]
+fn test_http_config_builder_connection_pool() {
+    let http = HttpConfigBuilder::default()
+        .pool_max_idle_per_host(32)
+        .pool_idle_timeout(Duration::from_secs(10))
+        .http2_prior_knowledge(true)
+        .tcp_keepalive(Duration::from_secs(30))
+        .timeout(Duration::from_secs(60))
+        .build()
+        .unwrap();
+
+    assert_eq!(http.pool_max_idle_per_host(), 32);
+    assert_eq!(http.pool_idle_timeout(), Some(Duration::from_secs(10)));
+    assert!(http.http2_prior_knowledge());
+    assert_eq!(http.tcp_keepalive(), Some(Duration::from_secs(30)));
+    assert_eq!(http.timeout(), Duration::from_secs(60));
+
+    let _client = http.build_reqwest_client();
+}
+
+#[tokio::test]
+async fn test_config_builder_tls() {
+    let pem = std::fs::read("./assets/test_ca.pem").unwrap();
+    let certificate = Certificate::from_pem(&pem).unwrap();
+
+    let config = Config::builder()
+        .api_key("test-key")
+        .base_url("https://gateway.internal/v1")
+        .add_root_certificate(certificate.clone())
+        .add_root_certificate(certificate)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+
+    let http = config.http();
+    assert_eq!(http.root_certificates().len(), 2);
+    assert!(http.danger_accept_invalid_certs());
+
+    // 证书会传递给底层客户端，构建客户端不应失败
+    let _client = http.build_reqwest_client();
+    let _client = OpenAI::with_config(config);
+
+    let default = Config::new("test-key", "https://api.test.com/v1");
+    assert!(default.http().root_certificates().is_empty());
+    assert!(!default.http().danger_accept_invalid_certs());
 }
 
 #[test]
@@ -96,3 +209,151 @@ async fn test_build_openai() {
     assert_eq!(client.api_key(), "test-key");
     assert_eq!(client.base_url(), "https://api.test.com/v1");
 }
+
+#[test]
+fn test_from_env_prefixed() {
+    // SAFETY: 该测试使用唯一的前缀，不会与其他测试读取的环境变量冲突
+    unsafe {
+        std::env::set_var("OPENAI4RS_TEST_PREFIXED_API_KEY", "prefixed-key");
+        std::env::set_var(
+            "OPENAI4RS_TEST_PREFIXED_BASE_URL",
+            "https://prefixed.test.com/v1",
+        );
+        std::env::set_var("OPENAI4RS_TEST_PREFIXED_RETRY_COUNT", "2");
+    }
+
+    let client = OpenAI::from_env_prefixed("OPENAI4RS_TEST_PREFIXED").unwrap();
+    assert_eq!(client.api_key(), "prefixed-key");
+    assert_eq!(client.base_url(), "https://prefixed.test.com/v1");
+    assert_eq!(client.retry_count(), 2);
+
+    let missing = OpenAI::from_env_prefixed("OPENAI4RS_TEST_MISSING");
+    assert!(missing.is_err());
+}
+
+#[test]
+fn test_from_env_missing_api_key() {
+    let error = OpenAI::from_env_prefixed("OPENAI4RS_TEST_NO_KEY")
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        ConfigError::MissingEnvVar {
+            name: "OPENAI4RS_TEST_NO_KEY_API_KEY".to_string()
+        }
+    );
+    assert_eq!(error.env_var(), "OPENAI4RS_TEST_NO_KEY_API_KEY");
+
+    let error: OpenAIError = error.into();
+    assert!(error.is_config_error());
+}
+
+#[test]
+fn test_from_env_invalid_timeout() {
+    // SAFETY: 该测试使用唯一的前缀，不会与其他测试读取的环境变量冲突
+    unsafe {
+        std::env::set_var("OPENAI4RS_TEST_BAD_TIMEOUT_API_KEY", "key");
+        std::env::set_var("OPENAI4RS_TEST_BAD_TIMEOUT_TIMEOUT", "12O");
+    }
+
+    let error = OpenAI::from_env_prefixed("OPENAI4RS_TEST_BAD_TIMEOUT")
+        .err()
+        .unwrap();
+
+    match error {
+        ConfigError::InvalidEnvVar { name, value, .. } => {
+            assert_eq!(name, "OPENAI4RS_TEST_BAD_TIMEOUT_TIMEOUT");
+            assert_eq!(value, "12O");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    // SAFETY: 同上
+    unsafe {
+        std::env::set_var("OPENAI4RS_TEST_BAD_TIMEOUT_TIMEOUT", "30");
+    }
+    let client = OpenAI::from_env_prefixed("OPENAI4RS_TEST_BAD_TIMEOUT").unwrap();
+    assert_eq!(client.timeout(), Duration::from_secs(30));
+}
+
+#[test]
+fn test_from_env_invalid_numeric_values_are_not_ignored() {
+    // SAFETY: 该测试使用唯一的前缀，不会与其他测试读取的环境变量冲突
+    unsafe {
+        std::env::set_var("OPENAI4RS_TEST_BAD_NUMERIC_API_KEY", "key");
+        std::env::set_var("OPENAI4RS_TEST_BAD_NUMERIC_CONNECT_TIMEOUT", "-5");
+        std::env::set_var("OPENAI4RS_TEST_BAD_NUMERIC_RETRY_COUNT", "three");
+    }
+
+    let error = OpenAI::from_env_prefixed("OPENAI4RS_TEST_BAD_NUMERIC")
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.env_var(),
+        "OPENAI4RS_TEST_BAD_NUMERIC_CONNECT_TIMEOUT"
+    );
+
+    // SAFETY: 同上
+    unsafe {
+        std::env::remove_var("OPENAI4RS_TEST_BAD_NUMERIC_CONNECT_TIMEOUT");
+    }
+    let error = OpenAI::from_env_prefixed("OPENAI4RS_TEST_BAD_NUMERIC")
+        .err()
+        .unwrap();
+    match error {
+        ConfigError::InvalidEnvVar { name, value, .. } => {
+            assert_eq!(name, "OPENAI4RS_TEST_BAD_NUMERIC_RETRY_COUNT");
+            assert_eq!(value, "three");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_injected_http_client() {
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(header("user-agent", "InjectedAgent/1.0"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"object": "list", "data": []})),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let http_client = reqwest::Client::builder()
+        .user_agent("InjectedAgent/1.0")
+        .build()
+        .unwrap();
+
+    let config = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .http_client(http_client.clone())
+        .build()
+        .unwrap();
+    assert!(config.http_client().is_some());
+    let client = OpenAI::with_config(config);
+    client
+        .models()
+        .list(openai4rs::ModelsParam::new())
+        .await
+        .unwrap();
+
+    // 更新配置会重新创建内部客户端，注入的客户端应被保留
+    let client = OpenAI::new("test-key", &server.uri()).with_http_client(http_client);
+    client.update_config(|config| {
+        config.with_retry_count(1);
+    });
+    client
+        .models()
+        .list(openai4rs::ModelsParam::new())
+        .await
+        .unwrap();
+}
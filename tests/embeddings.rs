@@ -0,0 +1,315 @@
+use openai4rs::embeddings::EmbeddingsParam;
+use openai4rs::*;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+/// 为每个输入返回一个以其长度为值的一维向量，`index`为批次内的位置。
+fn echo_embeddings(request: &Request) -> ResponseTemplate {
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    let inputs = body["input"].as_array().unwrap();
+    let data: Vec<serde_json::Value> = inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            serde_json::json!({
+                "object": "embedding",
+                "index": index,
+                "embedding": [input.as_str().unwrap().len() as f32],
+            })
+        })
+        .collect();
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "object": "list",
+        "model": "text-embedding-3-small",
+        "data": data,
+        "usage": {"prompt_tokens": inputs.len(), "total_tokens": inputs.len()},
+    }))
+}
+
+#[tokio::test]
+async fn test_embeddings_create_batched() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .respond_with(echo_embeddings)
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let inputs: Vec<String> = (1..=7).map(|len| "x".repeat(len)).collect();
+    let response = client
+        .embeddings()
+        .create_batched(
+            EmbeddingsParam::new("text-embedding-3-small", inputs).dimensions(1),
+            3,
+            2,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 7);
+    for (position, embedding) in response.embeddings().iter().enumerate() {
+        assert_eq!(embedding.index(), position);
+        assert_eq!(embedding.vector(), Some(vec![(position + 1) as f32]));
+    }
+    assert_eq!(response.prompt_tokens(), 7);
+    assert_eq!(response.total_tokens(), 7);
+
+    let requests = server.received_requests().await.unwrap();
+    let mut batch_sizes: Vec<usize> = requests
+        .iter()
+        .map(|request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            assert_eq!(body["dimensions"], 1);
+            body["input"].as_array().unwrap().len()
+        })
+        .collect();
+    batch_sizes.sort_unstable();
+    assert_eq!(batch_sizes, vec![1, 3, 3]);
+}
+
+#[tokio::test]
+async fn test_embeddings_create_batched_small_input() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .respond_with(echo_embeddings)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let response = client
+        .embeddings()
+        .create_batched(
+            EmbeddingsParam::new("text-embedding-3-small", vec!["a", "bb"]),
+            3,
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 2);
+
+    let error = client
+        .embeddings()
+        .create_batched(EmbeddingsParam::new("text-embedding-3-small", "a"), 0, 2)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        OpenAIError::Request(error::RequestError::InvalidParameter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_embeddings_request_header() {
+    use wiremock::matchers::header;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .and(header("x-request-id", "req-42"))
+        .respond_with(echo_embeddings)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let response = client
+        .embeddings()
+        .create(
+            EmbeddingsParam::new("text-embedding-3-small", vec!["hello"])
+                .header("x-request-id", HeaderValue::from_static("req-42")),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 1);
+}
+
+#[tokio::test]
+async fn test_embeddings_user_field() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .respond_with(echo_embeddings)
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    client
+        .embeddings()
+        .create(EmbeddingsParam::new("text-embedding-3-small", vec!["hello"]).user("user-1234"))
+        .await
+        .unwrap();
+    client
+        .embeddings()
+        .create(EmbeddingsParam::new(
+            "text-embedding-3-small",
+            vec!["hello"],
+        ))
+        .await
+        .unwrap();
+
+    let bodies: Vec<serde_json::Value> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect();
+    assert_eq!(bodies[0]["user"], "user-1234");
+    assert!(bodies[1].get("user").is_none());
+}
+
+#[tokio::test]
+async fn test_embeddings_create_batched_progress() {
+    use std::sync::Mutex;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .respond_with(echo_embeddings)
+        .expect(5)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let inputs: Vec<String> = (1..=10).map(|len| "x".repeat(len)).collect();
+    let progress = Mutex::new(Vec::new());
+    let response = client
+        .embeddings()
+        .create_batched_with_progress(
+            EmbeddingsParam::new("text-embedding-3-small", inputs),
+            3,
+            2,
+            |done, total| progress.lock().unwrap().push((done, total)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 10);
+    for (position, embedding) in response.embeddings().iter().enumerate() {
+        assert_eq!(embedding.index(), position);
+    }
+
+    // 批次可能乱序完成，但已完成数量递增且最终等于输入总数
+    let progress = progress.into_inner().unwrap();
+    assert_eq!(progress.len(), 4);
+    assert!(progress.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(progress.last(), Some(&(10, 10)));
+    assert!(progress.iter().all(|&(_, total)| total == 10));
+
+    // 只发送一个请求时报告一次
+    let calls = Mutex::new(Vec::new());
+    client
+        .embeddings()
+        .create_batched_with_progress(
+            EmbeddingsParam::new("text-embedding-3-small", vec!["a", "b"]),
+            3,
+            2,
+            |done, total| calls.lock().unwrap().push((done, total)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(calls.into_inner().unwrap(), vec![(2, 2)]);
+}
+
+#[tokio::test]
+async fn test_embeddings_base64_into_float_vectors() {
+    use base64::Engine;
+    use wiremock::matchers::body_partial_json;
+
+    let vectors = vec![vec![0.25f32, -1.5, 3.0], vec![1e-3, 0.0, -42.125]];
+    let data: Vec<serde_json::Value> = vectors
+        .iter()
+        .enumerate()
+        .map(|(index, vector)| {
+            let bytes: Vec<u8> = vector.iter().flat_map(|f| f.to_le_bytes()).collect();
+            serde_json::json!({
+                "object": "embedding",
+                "index": index,
+                "embedding": base64::engine::general_purpose::STANDARD.encode(bytes),
+            })
+        })
+        .collect();
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .and(body_partial_json(
+            serde_json::json!({"encoding_format": "base64"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": data,
+            "usage": {"prompt_tokens": 2, "total_tokens": 2},
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let response = client
+        .embeddings()
+        .create(
+            EmbeddingsParam::new("text-embedding-3-small", ["first", "second"])
+                .encoding_format(EncodingFormat::Base64),
+        )
+        .await
+        .unwrap();
+
+    assert!(
+        response
+            .embeddings()
+            .iter()
+            .all(|e| e.as_base64().is_some())
+    );
+    assert_eq!(response.into_float_vectors().unwrap(), vectors);
+}
+
+#[tokio::test]
+async fn test_embeddings_invalid_json_keeps_raw_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("{\"object\": \"list\", \"data\": ["),
+        )
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let error = client
+        .embeddings()
+        .create(EmbeddingsParam::new("text-embedding-3-small", "hello"))
+        .await
+        .unwrap_err();
+
+    assert!(error.is_deserialization());
+    assert_eq!(error.status_code(), Some(200));
+    // 错误信息保留serde_json报告的出错位置
+    let message = error.to_string();
+    assert!(message.contains("line 1 column 28"), "{message}");
+    assert!(message.contains("/embeddings"), "{message}");
+
+    match error {
+        OpenAIError::Processing(error::ProcessingError::JsonBody {
+            error,
+            raw,
+            target_type,
+            status_code,
+            url,
+        }) => {
+            assert!(error.is_eof());
+            assert_eq!(raw, "{\"object\": \"list\", \"data\": [");
+            assert!(target_type.contains("EmbeddingResponse"));
+            assert_eq!(status_code, 200);
+            assert!(url.ends_with("/embeddings"));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
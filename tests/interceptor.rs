@@ -0,0 +1,244 @@
+use openai4rs::error::RequestError;
+use openai4rs::*;
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Logging {
+    name: &'static str,
+    log: Log,
+}
+
+impl Interceptor for Logging {
+    fn on_request(&self, _request: &mut Request) -> Result<(), OpenAIError> {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{}:request", self.name));
+        Ok(())
+    }
+
+    fn on_response(&self, response: &reqwest::Response) -> Result<(), OpenAIError> {
+        self.log.lock().unwrap().push(format!(
+            "{}:response:{}",
+            self.name,
+            response.status().as_u16()
+        ));
+        Ok(())
+    }
+
+    fn on_error(&self, error: &OpenAIError) {
+        self.log.lock().unwrap().push(format!(
+            "{}:error:{}",
+            self.name,
+            error.status_code().unwrap_or_default()
+        ));
+    }
+}
+
+struct InjectHeader;
+
+impl Interceptor for InjectHeader {
+    fn on_request(&self, request: &mut Request) -> Result<(), OpenAIError> {
+        request
+            .headers_mut()
+            .insert("x-trace-id", HeaderValue::from_static("trace-123"));
+        Ok(())
+    }
+}
+
+struct Reject;
+
+impl Interceptor for Reject {
+    fn on_request(&self, _request: &mut Request) -> Result<(), OpenAIError> {
+        Err(RequestError::Interceptor("blocked".to_string()).into())
+    }
+}
+
+#[tokio::test]
+async fn test_interceptor_order_and_header_injection() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(header("x-trace-id", "trace-123"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(models::ModelsData::mock(&[]).to_mock_json()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let log = Log::default();
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .interceptor(Logging {
+            name: "outer",
+            log: log.clone(),
+        })
+        .build_openai()
+        .unwrap();
+    client.add_interceptor(InjectHeader);
+    client.add_interceptor(Logging {
+        name: "inner",
+        log: log.clone(),
+    });
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "outer:request",
+            "inner:request",
+            "inner:response:200",
+            "outer:response:200"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_interceptor_on_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "message": "bad request",
+                "type": "invalid_request_error"
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let log = Log::default();
+    let client = OpenAI::new("test-key", &server.uri());
+    client.add_interceptor(Logging {
+        name: "outer",
+        log: log.clone(),
+    });
+    client.add_interceptor(Logging {
+        name: "inner",
+        log: log.clone(),
+    });
+
+    assert!(client.models().list(ModelsParam::new()).await.is_err());
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "outer:request",
+            "inner:request",
+            "inner:error:400",
+            "outer:error:400"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_interceptor_short_circuit() {
+    let server = MockServer::start().await;
+
+    let log = Log::default();
+    let client = OpenAI::new("test-key", &server.uri());
+    client.add_interceptor(Reject);
+    client.add_interceptor(Logging {
+        name: "after",
+        log: log.clone(),
+    });
+
+    let error = client.models().list(ModelsParam::new()).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        OpenAIError::Request(RequestError::Interceptor(_))
+    ));
+    assert!(log.lock().unwrap().is_empty());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_rate_limit_status() {
+    use std::time::Duration;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(models::ModelsData::mock(&[]).to_mock_json())
+                .insert_header("x-ratelimit-remaining-requests", "59")
+                .insert_header("x-ratelimit-reset-requests", "1s"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(models::ModelsData::mock(&[]).to_mock_json())
+                .insert_header("x-ratelimit-remaining-requests", "58")
+                .insert_header("x-ratelimit-remaining-tokens", "1000"),
+        )
+        .mount(&server)
+        .await;
+
+    let log = Log::default();
+    let tracker = RateLimitInterceptor::new();
+    let client = OpenAI::new("test-key", &server.uri())
+        .with_interceptor(Logging {
+            name: "a",
+            log: log.clone(),
+        })
+        .with_interceptor(tracker.clone());
+    assert_eq!(client.rate_limit_status(), None);
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+    let first = client.rate_limit_status().unwrap();
+    assert_eq!(first.info.remaining_requests, Some(59));
+    assert_eq!(first.info.reset_requests, Some(Duration::from_secs(1)));
+    assert_eq!(
+        tracker.snapshot().map(|snapshot| snapshot.info),
+        Some(first.info.clone())
+    );
+    assert!(first.age() < Duration::from_secs(5));
+
+    // 快照整体替换，不会保留上一个响应中的字段
+    client.models().list(ModelsParam::new()).await.unwrap();
+    let status = client.rate_limit_status().unwrap();
+    assert_eq!(status.info.remaining_requests, Some(58));
+    assert_eq!(status.info.remaining_tokens, Some(1000));
+    assert_eq!(status.info.reset_requests, None);
+    assert!(status.received_at() >= first.received_at());
+
+    assert_eq!(log.lock().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_rate_limit_status_without_interceptors() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(models::ModelsData::mock(&[]).to_mock_json())
+                .insert_header("x-ratelimit-remaining-requests", "59"),
+        )
+        .mount(&server)
+        .await;
+
+    // 客户端不依赖配置中的拦截器链记录速率限制信息
+    let config = Config::new("test-key", server.uri());
+    assert!(config.interceptors().is_empty());
+    let client = OpenAI::with_config(config);
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+    let status = client.rate_limit_status().unwrap();
+    assert_eq!(status.info.remaining_requests, Some(59));
+}
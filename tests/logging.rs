@@ -0,0 +1,225 @@
+use openai4rs::*;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "sk-very-secret-key";
+
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Captured {
+    fn output(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Captured {
+    type Writer = Captured;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn capture_debug_logs() -> (Captured, tracing::subscriber::DefaultGuard) {
+    capture_logs(tracing::Level::DEBUG)
+}
+
+fn capture_logs(level: tracing::Level) -> (Captured, tracing::subscriber::DefaultGuard) {
+    let captured = Captured::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(captured.clone())
+        .with_ansi(false)
+        .finish();
+    let guard = tracing::subscriber::set_default(subscriber);
+    (captured, guard)
+}
+
+async fn mock_chat_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(ChatCompletion::mock("pong").to_mock_json()),
+        )
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_logging_redacts_api_key() {
+    let server = mock_chat_server().await;
+    let (captured, _guard) = capture_debug_logs();
+
+    let client = Config::builder()
+        .api_key(API_KEY)
+        .base_url(server.uri())
+        .log_bodies(true)
+        .build_openai()
+        .unwrap();
+    let messages = vec![user!("ping")];
+    let completion = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    assert_eq!(completion.content(), Some("pong"));
+
+    let output = captured.output();
+    assert!(output.contains("openai4rs_request"));
+    assert!(output.contains("POST"));
+    assert!(output.contains("/chat/completions"));
+    assert!(output.contains("status=200"));
+    assert!(output.contains("Bearer [REDACTED]"));
+    assert!(output.contains("ping"));
+    assert!(output.contains("pong"));
+    assert!(!output.contains(API_KEY));
+}
+
+#[tokio::test]
+async fn test_logging_omits_bodies_by_default() {
+    let server = mock_chat_server().await;
+    let (captured, _guard) = capture_debug_logs();
+
+    let client = OpenAI::new(API_KEY, &server.uri());
+    let messages = vec![user!("ping")];
+    client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    let output = captured.output();
+    assert!(output.contains("status=200"));
+    assert!(!output.contains("request body"));
+    assert!(!output.contains("response body"));
+    assert!(!output.contains(API_KEY));
+}
+
+#[tokio::test]
+async fn test_debug_logging_interceptor_redacts_api_key() {
+    let server = mock_chat_server().await;
+    // 只捕获INFO级别，排除内置请求日志的输出
+    let (captured, _guard) = capture_logs(tracing::Level::INFO);
+
+    let client = OpenAI::new(API_KEY, &server.uri());
+    client.add_interceptor(
+        DebugLoggingInterceptor::new()
+            .level(tracing::Level::INFO)
+            .max_body_len(40)
+            .redact_header(HeaderName::from_static("x-gateway-token")),
+    );
+    let messages = vec![user!("a fairly long prompt that will not fit")];
+    client
+        .chat()
+        .create(
+            ChatParam::new("gpt-4o", &messages)
+                .header("x-gateway-token", HeaderValue::from_static("gw-secret")),
+        )
+        .await
+        .unwrap();
+
+    let output = captured.output();
+    assert!(output.contains("INFO"));
+    assert!(output.contains("openai4rs request"));
+    assert!(output.contains("openai4rs response"));
+    assert!(output.contains("Bearer [REDACTED]"));
+    assert!(!output.contains("sending request"));
+    assert!(output.contains(r#"body={"messages":"#));
+    assert!(output.contains("bytes truncated"));
+    assert!(!output.contains("will not fit"));
+    assert!(!output.contains(API_KEY));
+    assert!(!output.contains("gw-secret"));
+}
+
+#[tokio::test]
+async fn test_debug_logging_interceptor_logs_response_body() {
+    let server = mock_chat_server().await;
+    let (captured, _guard) = capture_logs(tracing::Level::INFO);
+
+    let client = OpenAI::new(API_KEY, &server.uri());
+    client.add_interceptor(DebugLoggingInterceptor::new().level(tracing::Level::INFO));
+    let messages = vec![user!("ping")];
+    let completion = client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+    // 读取响应体后仍能正常解析响应
+    assert_eq!(completion.content(), Some("pong"));
+
+    let output = captured.output();
+    assert!(output.contains("openai4rs response body"), "{output}");
+    assert!(output.contains(r#""id":"chatcmpl-mock""#), "{output}");
+    assert!(output.contains(r#""content":"pong""#), "{output}");
+    assert!(!output.contains(API_KEY));
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn test_lint_warns_on_empty_messages() {
+    let server = mock_chat_server().await;
+    let (captured, _guard) = capture_logs(tracing::Level::WARN);
+
+    let client = OpenAI::new(API_KEY, &server.uri());
+    let messages = vec![];
+    client
+        .chat()
+        .create(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    let output = captured.output();
+    assert!(output.contains("WARN"), "{output}");
+    assert!(
+        output.contains("Suspicious chat request: `messages` is empty"),
+        "{output}"
+    );
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+async fn test_lint_warns_on_tools_with_tool_choice_none() {
+    let server = mock_chat_server().await;
+    let (captured, _guard) = capture_logs(tracing::Level::WARN);
+
+    let client = OpenAI::new(API_KEY, &server.uri());
+    let messages = vec![user!("ping")];
+    let tool = ChatCompletionToolParam::function(
+        "get_weather",
+        "Get the weather",
+        Parameters::object().build().unwrap(),
+    );
+    client
+        .chat()
+        .create(
+            ChatParam::new("gpt-4o", &messages)
+                .tools(vec![tool])
+                .tool_choice(ToolChoice::None),
+        )
+        .await
+        .unwrap();
+
+    let output = captured.output();
+    assert!(
+        output
+            .contains("Suspicious chat request: `tools` are provided but `tool_choice` is `none`"),
+        "{output}"
+    );
+}
@@ -0,0 +1,304 @@
+use openai4rs::*;
+use std::time::Duration;
+
+fn mock_client(transport: &MockTransport) -> OpenAI {
+    Config::builder()
+        .api_key("test-key")
+        .base_url("https://api.test.com/v1")
+        .retry_policy(ExponentialBackoff {
+            internal_server_error_base_delay: Duration::from_millis(1),
+            ..ExponentialBackoff::without_jitter()
+        })
+        .transport(transport.clone())
+        .build_openai()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_mock_transport_chat_completion() {
+    let transport = MockTransport::new();
+    transport.push_chat_completion(&ChatCompletion::mock("Hello!").with_usage(5, 2));
+    let client = mock_client(&transport);
+
+    let messages = vec![user!("Hi")];
+    let response = client
+        .chat()
+        .create(ChatParam::new("mock-model", &messages))
+        .await
+        .unwrap();
+
+    assert_eq!(response.content(), Some("Hello!"));
+    assert_eq!(response.usage.unwrap().total_tokens, 7);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].url(),
+        "https://api.test.com/v1/chat/completions"
+    );
+    assert_eq!(
+        requests[0].headers()[header::AUTHORIZATION],
+        "Bearer test-key"
+    );
+    assert_eq!(requests[0].body().unwrap()["model"], "mock-model");
+}
+
+#[tokio::test]
+async fn test_mock_transport_agent_loop() {
+    // 第一轮返回工具调用，第二轮根据工具结果返回最终回复
+    let transport = MockTransport::new();
+    transport
+        .push_chat_completion(&ChatCompletion::mock("").with_tool_call(
+            "call_1",
+            "get_weather",
+            "{\"city\":\"Boston\"}",
+        ))
+        .push_chat_completion(&ChatCompletion::mock("It is sunny in Boston."));
+    let client = mock_client(&transport);
+
+    let mut messages = vec![user!("What's the weather in Boston?")];
+    loop {
+        let response = client
+            .chat()
+            .create(ChatParam::new("mock-model", &messages))
+            .await
+            .unwrap();
+        response.append_to(&mut messages);
+
+        let Some(tool_calls) = response.tool_calls() else {
+            assert_eq!(response.content(), Some("It is sunny in Boston."));
+            break;
+        };
+        for tool_call in tool_calls {
+            assert_eq!(tool_call.function.name, "get_weather");
+            messages.push(tool!(tool_call_id: tool_call.function.id.clone(), content: "sunny"));
+        }
+    }
+
+    assert_eq!(transport.requests().len(), 2);
+    assert_eq!(transport.remaining(), 0);
+    let second_body = transport.requests()[1].body().unwrap().clone();
+    assert_eq!(second_body["messages"].as_array().unwrap().len(), 3);
+    assert_eq!(second_body["messages"][2]["tool_call_id"], "call_1");
+}
+
+#[tokio::test]
+async fn test_chat_param_push_message() {
+    let transport = MockTransport::new();
+    transport
+        .push_chat_completion(&ChatCompletion::mock("Hi!"))
+        .push_chat_completion(&ChatCompletion::mock("Bye!"));
+    let client = mock_client(&transport);
+
+    let messages = vec![system!("Be brief.")];
+    let mut param = ChatParam::new("mock-model", &messages);
+    param.push_message(user!("Hello"));
+    let response = client.chat().create(param.clone()).await.unwrap();
+
+    param
+        .push_message(assistant!(response.content().unwrap()))
+        .extend_messages([user!("Goodbye"), user!("See you")]);
+    client.chat().create(param).await.unwrap();
+
+    let requests = transport.requests();
+    let first = requests[0].body().unwrap()["messages"].clone();
+    assert_eq!(first.as_array().unwrap().len(), 2);
+
+    let second = requests[1].body().unwrap()["messages"].clone();
+    let contents: Vec<_> = second
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|message| {
+            (
+                message["role"].as_str().unwrap(),
+                message["content"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        contents,
+        [
+            ("system", "Be brief."),
+            ("user", "Hello"),
+            ("assistant", "Hi!"),
+            ("user", "Goodbye"),
+            ("user", "See you"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_mock_transport_stream() {
+    let transport = MockTransport::new();
+    transport.push_chat_stream([
+        ChatCompletionChunk::mock("Hel"),
+        ChatCompletionChunk::mock("lo").with_finish_reason(FinishReason::Stop),
+        ChatCompletionChunk::mock_usage(3, 2),
+    ]);
+    let client = mock_client(&transport);
+
+    let messages = vec![user!("Hi")];
+    let completion = client
+        .chat()
+        .create_stream(ChatParam::new("mock-model", &messages))
+        .await
+        .unwrap()
+        .collect_completion()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(completion.content(), Some("Hello"));
+    assert_eq!(completion.usage.unwrap().total_tokens, 5);
+    assert_eq!(
+        transport.requests()[0].headers()[header::ACCEPT],
+        "text/event-stream"
+    );
+}
+
+#[tokio::test]
+async fn test_mock_transport_retry_and_error() {
+    let transport = MockTransport::new();
+    transport
+        .push_error(500, "Internal server error")
+        .push_chat_completion(&ChatCompletion::mock("recovered"))
+        .push_error(400, "Bad request");
+    let client = mock_client(&transport);
+
+    let messages = vec![user!("Hi")];
+    let response = client
+        .chat()
+        .create(ChatParam::new("mock-model", &messages))
+        .await
+        .unwrap();
+    assert_eq!(response.content(), Some("recovered"));
+    assert_eq!(transport.requests().len(), 2);
+
+    let error = client
+        .chat()
+        .create(ChatParam::new("mock-model", &messages))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status_code(), Some(400));
+    assert!(error.to_string().contains("Bad request"));
+    assert_eq!(transport.remaining(), 0);
+}
+
+#[tokio::test]
+async fn test_mock_transport_json() {
+    let transport = MockTransport::new();
+    transport.push_json(
+        200,
+        serde_json::json!({
+            "object": "list",
+            "data": [{"id": "mock-model", "object": "model", "created": 0, "owned_by": "openai"}]
+        }),
+    );
+    let client = mock_client(&transport);
+
+    let models = client.models().list(ModelsParam::new()).await.unwrap();
+    assert_eq!(models.data.len(), 1);
+    assert_eq!(transport.requests()[0].method(), "GET");
+}
+
+#[tokio::test]
+async fn test_mock_transport_client_error_statuses() {
+    let transport = MockTransport::new();
+    transport
+        .push_error(402, "Insufficient balance")
+        .push_error(403, "Access denied")
+        .push_error(404, "The model `unknown` does not exist");
+    let client = mock_client(&transport);
+
+    let mut errors = Vec::new();
+    for _ in 0..3 {
+        errors.push(client.models().list(ModelsParam::new()).await.unwrap_err());
+    }
+
+    assert!(errors[0].is_payment_required());
+    assert!(errors[1].is_forbidden());
+    assert!(errors[2].is_not_found());
+    assert_eq!(
+        errors.iter().map(|e| e.status_code()).collect::<Vec<_>>(),
+        [Some(402), Some(403), Some(404)]
+    );
+    assert!(errors.iter().all(|e| !e.is_retryable()));
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_mock_transport_all_endpoints_share_retry_path() {
+    // 所有接口都经由同一个执行器发送请求，因此对5xx的重试行为一致
+    let transport = MockTransport::new();
+    transport
+        .push_error(500, "Internal server error")
+        .push_json(200, serde_json::json!({"object": "list", "data": []}))
+        .push_error(503, "Service unavailable")
+        .push_json(
+            200,
+            serde_json::json!({
+                "object": "list",
+                "data": [{"object": "embedding", "index": 0, "embedding": [0.1, 0.2]}],
+                "model": "mock-embedding",
+                "usage": {"prompt_tokens": 1, "total_tokens": 1}
+            }),
+        )
+        .push_error(502, "Bad gateway")
+        .push_json(
+            200,
+            serde_json::json!({
+                "id": "cmpl-1",
+                "object": "text_completion",
+                "created": 0,
+                "model": "mock-instruct",
+                "choices": [{"index": 0, "text": "done", "logprobs": null, "finish_reason": "stop"}]
+            }),
+        )
+        .push_error(500, "Internal server error")
+        .push_chat_completion(&ChatCompletion::mock("done"));
+    let client = mock_client(&transport);
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+    client
+        .embeddings()
+        .create(EmbeddingsParam::new("mock-embedding", "hello"))
+        .await
+        .unwrap();
+    client
+        .completions()
+        .create(CompletionsParam::new("mock-instruct", "hello"))
+        .await
+        .unwrap();
+    let messages = vec![user!("hello")];
+    client
+        .chat()
+        .create(ChatParam::new("mock-model", &messages))
+        .await
+        .unwrap();
+
+    let paths: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|request| {
+            request
+                .url()
+                .trim_start_matches("https://api.test.com/v1")
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "/models",
+            "/models",
+            "/embeddings",
+            "/embeddings",
+            "/completions",
+            "/completions",
+            "/chat/completions",
+            "/chat/completions",
+        ]
+    );
+    assert_eq!(transport.remaining(), 0);
+}
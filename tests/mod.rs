@@ -1,3 +1,16 @@
 mod api;
+mod api_key_provider;
+mod azure;
+mod chat;
+mod completions;
 mod config;
+mod embeddings;
+mod interceptor;
+mod logging;
+mod mock_transport;
+mod models;
+mod raw;
+mod retry;
 mod serialization;
+mod stream;
+mod tool_parameters;
@@ -0,0 +1,143 @@
+use openai4rs::*;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_models_retrieve() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models/gpt-4o-mini"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "gpt-4o-mini",
+            "object": "model",
+            "created": 1721172741,
+            "owned_by": "system"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let model = client
+        .models()
+        .retrieve("gpt-4o-mini", ModelsParam::new())
+        .await
+        .unwrap();
+
+    assert_eq!(model.id, "gpt-4o-mini");
+    assert_eq!(model.created, 1721172741);
+    assert_eq!(model.object.as_deref(), Some("model"));
+    assert_eq!(model.owned_by.as_deref(), Some("system"));
+    assert!(model.extra_fields.is_none());
+}
+
+#[tokio::test]
+async fn test_models_retrieve_not_found() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models/missing-model"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": {
+                "message": "The model `missing-model` does not exist",
+                "type": "invalid_request_error",
+                "param": null,
+                "code": "model_not_found"
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let error = client
+        .models()
+        .retrieve("missing-model", ModelsParam::new())
+        .await
+        .unwrap_err();
+
+    assert_eq!(error.status_code(), Some(404));
+}
+
+#[tokio::test]
+async fn test_models_delete() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/models/ft:gpt-4o-mini:acme::abc123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "ft:gpt-4o-mini:acme::abc123",
+            "object": "model",
+            "deleted": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let deletion = client
+        .models()
+        .delete("ft:gpt-4o-mini:acme::abc123", ModelsParam::new())
+        .await
+        .unwrap();
+
+    assert_eq!(deletion.id, "ft:gpt-4o-mini:acme::abc123");
+    assert_eq!(deletion.object, "model");
+    assert!(deletion.deleted);
+}
+
+#[tokio::test]
+async fn test_models_list_request_header() {
+    use wiremock::matchers::header;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(header("x-request-id", "req-42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": []
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let models = client
+        .models()
+        .list(ModelsParam::new().header("x-request-id", HeaderValue::from_static("req-42")))
+        .await
+        .unwrap();
+
+    assert!(models.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_models_list_query() {
+    use wiremock::matchers::query_param;
+
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .and(query_param("limit", "20"))
+        .and(query_param("provider", "anthropic"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [{"id": "claude-sonnet", "object": "model", "created": 0, "owned_by": "anthropic"}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let models = client
+        .models()
+        .list(ModelsParam::new().limit(20).query("provider", "anthropic"))
+        .await
+        .unwrap();
+
+    assert_eq!(models.data.len(), 1);
+    assert_eq!(models.data[0].id, "claude-sonnet");
+}
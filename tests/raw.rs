@@ -0,0 +1,69 @@
+use openai4rs::*;
+use wiremock::matchers::{body_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_raw_post() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/moderations"))
+        .and(header("authorization", "Bearer test-key"))
+        .and(header("x-global", "yes"))
+        .and(body_json(serde_json::json!({"input": "hello"})))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"flagged": false})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .header("x-global", HeaderValue::from_static("yes"))
+        .build_openai()
+        .unwrap();
+
+    let response = client
+        .raw_post("/moderations", serde_json::json!({"input": "hello"}))
+        .await
+        .unwrap();
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["flagged"], false);
+}
+
+#[tokio::test]
+async fn test_raw_get() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/files"))
+        .and(header("authorization", "Bearer test-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"object": "list", "data": []})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let response = client.raw_get("/files").await.unwrap();
+
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_raw_post_rejects_non_object_body() {
+    let server = MockServer::start().await;
+    let client = OpenAI::new("test-key", &server.uri());
+
+    let error = client.raw_post("/moderations", "hello").await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        OpenAIError::Request(openai4rs::error::RequestError::InvalidParameter(_))
+    ));
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
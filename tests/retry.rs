@@ -0,0 +1,247 @@
+use openai4rs::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_on_retry_callback() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "0")
+                .set_body_json(serde_json::json!({
+                    "error": {"message": "rate limited", "type": "rate_limit_error"}
+                })),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"object": "list", "data": []})),
+        )
+        .mount(&server)
+        .await;
+
+    let attempts = Arc::new(Mutex::new(Vec::new()));
+    let recorded = attempts.clone();
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .retry_count(3)
+        .on_retry(move |attempt, max_attempts, error, delay| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((attempt, max_attempts, error.status_code(), delay));
+        })
+        .build_openai()
+        .unwrap();
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 1);
+    let (attempt, max_attempts, status, delay) = attempts[0];
+    assert_eq!((attempt, max_attempts, status), (1, 3, Some(429)));
+    assert!(delay < Duration::from_secs(1));
+}
+
+/// 以固定间隔重试任意错误，最多尝试`max_attempts`次
+struct FixedRetries {
+    delay: Duration,
+    max_attempts: u32,
+}
+
+impl RetryPolicy for FixedRetries {
+    fn delay(
+        &self,
+        attempt: u32,
+        _error: &OpenAIError,
+        _retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        (attempt < self.max_attempts).then_some(self.delay)
+    }
+}
+
+#[tokio::test]
+async fn test_custom_retry_policy() {
+    let server = MockServer::start().await;
+
+    // 400默认不会重试，自定义策略可以覆盖这一行为
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {"message": "bad request", "type": "invalid_request_error"}
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let recorded = delays.clone();
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .retry_count(10)
+        .retry_policy(FixedRetries {
+            delay: Duration::from_millis(5),
+            max_attempts: 2,
+        })
+        .on_retry(move |_, _, _, delay| recorded.lock().unwrap().push(delay))
+        .build_openai()
+        .unwrap();
+
+    let error = client.models().list(ModelsParam::new()).await.unwrap_err();
+    assert!(error.is_bad_request());
+    assert_eq!(*delays.lock().unwrap(), vec![Duration::from_millis(5)]);
+}
+
+#[tokio::test]
+async fn test_default_retry_policy_skips_non_retryable() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {"message": "bad request", "type": "invalid_request_error"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .retry_count(3)
+        .build_openai()
+        .unwrap();
+
+    let error = client.models().list(ModelsParam::new()).await.unwrap_err();
+    assert!(error.is_bad_request());
+}
+
+#[tokio::test]
+async fn test_request_retry_count_overrides_global() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "0")
+                .set_body_json(serde_json::json!({
+                    "error": {"message": "rate limited", "type": "rate_limit_error"}
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .retry_count(3)
+        .retry_policy(ExponentialBackoff {
+            retry_after_jitter: Duration::ZERO,
+            ..Default::default()
+        })
+        .build_openai()
+        .unwrap();
+
+    let mut expected = 0;
+    for (param, attempts) in [
+        (ModelsParam::new().no_retry(), 1),
+        (ModelsParam::new().retry_count(0), 1),
+        (ModelsParam::new().retry_count(2), 2),
+        (ModelsParam::new(), 3),
+    ] {
+        let error = client.models().list(param).await.unwrap_err();
+        assert!(error.is_rate_limit());
+
+        expected += attempts;
+        assert_eq!(server.received_requests().await.unwrap().len(), expected);
+    }
+}
+
+#[tokio::test]
+async fn test_backoff_without_jitter_is_deterministic() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(ResponseTemplate::new(500).set_body_json(serde_json::json!({
+            "error": {"message": "server error", "type": "server_error"}
+        })))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let recorded = delays.clone();
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .retry_count(4)
+        .retry_policy(ExponentialBackoff {
+            internal_server_error_base_delay: Duration::from_millis(1),
+            ..ExponentialBackoff::without_jitter()
+        })
+        .on_retry(move |_, _, _, delay| recorded.lock().unwrap().push(delay))
+        .build_openai()
+        .unwrap();
+
+    let error = client.models().list(ModelsParam::new()).await.unwrap_err();
+    assert!(error.is_server_error());
+    assert_eq!(
+        *delays.lock().unwrap(),
+        [1, 2, 4].map(Duration::from_millis).to_vec()
+    );
+}
+
+#[tokio::test]
+async fn test_retry_after_http_date() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "Tue, 21 Oct 2025 07:28:00 GMT")
+                .set_body_json(serde_json::json!({
+                    "error": {"message": "rate limited", "type": "rate_limit_error"}
+                })),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"object": "list", "data": []})),
+        )
+        .mount(&server)
+        .await;
+
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let recorded = delays.clone();
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .retry_count(2)
+        .retry_policy(ExponentialBackoff::without_jitter())
+        .on_retry(move |_, _, _, delay| recorded.lock().unwrap().push(delay))
+        .build_openai()
+        .unwrap();
+
+    client.models().list(ModelsParam::new()).await.unwrap();
+
+    // 日期已经过去，立即重试而不是回退到指数退避的5秒
+    assert_eq!(*delays.lock().unwrap(), vec![Duration::ZERO]);
+}
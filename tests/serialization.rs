@@ -137,6 +137,8 @@ fn test_chat_completion_helpers() {
     // 验证数据
     assert_eq!(chat_completion.content(), Some("Hello, world!"));
     assert!(chat_completion.has_tool_calls());
+    assert!(!chat_completion.has_refusal());
+    assert_eq!(chat_completion.refusal(), None);
 
     let tool_calls = chat_completion.tool_calls().unwrap();
     assert_eq!(tool_calls.len(), 1);
@@ -188,6 +190,55 @@ fn test_chat_completion_chunk_helpers() {
     assert_eq!(tool_calls[0].function.name, "get_current_weather");
     let deltas: Vec<&ChoiceDelta> = chat_completion_chunk.deltas().collect();
     assert_eq!(deltas.len(), 1);
+    assert!(!chat_completion_chunk.has_refusal());
+    assert_eq!(chat_completion_chunk.refusal(), None);
+}
+
+#[test]
+fn test_chat_completion_refusal_helpers() {
+    let json = serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1700000000,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "refusal": "I can't help with that."
+            },
+            "finish_reason": "stop"
+        }]
+    });
+    let chat_completion: ChatCompletion = serde_json::from_value(json).unwrap();
+    assert!(chat_completion.has_refusal());
+    assert_eq!(chat_completion.refusal(), Some("I can't help with that."));
+    assert!(!chat_completion.has_content());
+
+    let chunk = |refusal: &str| {
+        serde_json::from_value::<ChatCompletionChunk>(serde_json::json!({
+            "id": "chatcmpl-123",
+            "object": "chat.completion.chunk",
+            "created": 1700000000,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "delta": {"refusal": refusal},
+                "finish_reason": null
+            }]
+        }))
+        .unwrap()
+    };
+    let first = chunk("I can't ");
+    assert!(first.has_refusal());
+    assert_eq!(first.refusal(), Some("I can't "));
+    assert!(!chunk("").has_refusal());
+
+    // 拒绝说明分多个增量发送时应拼接
+    let mut choice = first.choices[0].clone();
+    choice.merge(chunk("help with that.").choices[0].clone());
+    assert_eq!(choice.delta.refusal(), Some("I can't help with that."));
 }
 
 #[test]
@@ -241,3 +292,916 @@ fn test_chat_completion_missing_id() {
     assert_eq!(choice.message.role, "assistant");
     assert_eq!(choice.message.content.as_deref(), None);
 }
+
+#[test]
+fn test_tool_choice_serialize() {
+    assert_eq!(
+        serde_json::to_value(ToolChoice::Auto).unwrap(),
+        serde_json::json!("auto")
+    );
+    assert_eq!(
+        serde_json::to_value(ToolChoice::None).unwrap(),
+        serde_json::json!("none")
+    );
+    assert_eq!(
+        serde_json::to_value(ToolChoice::Required).unwrap(),
+        serde_json::json!("required")
+    );
+
+    let left = serde_json::to_value(ToolChoice::function("get_current_weather")).unwrap();
+    let right = serde_json::json!({
+        "type": "function",
+        "function": {
+            "name": "get_current_weather"
+        }
+    });
+    assert_eq!(left, right);
+}
+
+#[test]
+fn test_chat_completion_to_assistant_message() {
+    let json = serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1699896916,
+        "model": "gpt-4o-mini",
+        "choices": [
+            {
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [
+                        {
+                            "id": "call_abc123",
+                            "type": "function",
+                            "function": {
+                                "name": "get_current_weather",
+                                "arguments": "{\"location\": \"Boston, MA\"}"
+                            }
+                        },
+                        {
+                            "id": "call_def456",
+                            "type": "function",
+                            "function": {
+                                "name": "get_current_time",
+                                "arguments": "{}"
+                            }
+                        }
+                    ]
+                },
+                "logprobs": null,
+                "finish_reason": "tool_calls"
+            }
+        ]
+    });
+
+    let chat_completion: ChatCompletion = serde_json::from_value(json).unwrap();
+    let assistant = chat_completion.to_assistant_message().unwrap();
+
+    let left = serde_json::to_value(&assistant).unwrap();
+    let right = serde_json::json!({
+        "role": "assistant",
+        "tool_calls": [
+            {
+                "id": "call_abc123",
+                "type": "function",
+                "function": {
+                    "name": "get_current_weather",
+                    "arguments": "{\"location\": \"Boston, MA\"}"
+                }
+            },
+            {
+                "id": "call_def456",
+                "type": "function",
+                "function": {
+                    "name": "get_current_time",
+                    "arguments": "{}"
+                }
+            }
+        ]
+    });
+    assert_eq!(left, right);
+}
+
+#[test]
+fn test_chat_completion_append_to() {
+    let completion = ChatCompletion::mock("Hello!").with_tool_call("call_1", "get_weather", "{}");
+    let mut messages = vec![user!("Hi")];
+    completion.append_to(&mut messages);
+    assert_eq!(messages.len(), 2);
+
+    let expected = ChatCompletionMessageParam::from_completion(&completion).unwrap();
+    assert_eq!(
+        serde_json::to_value(&messages[1]).unwrap(),
+        serde_json::to_value(&expected).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(&messages[1]).unwrap(),
+        serde_json::json!({
+            "role": "assistant",
+            "content": "Hello!",
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{}"}
+            }]
+        })
+    );
+
+    let mut empty = ChatCompletion::mock("unused");
+    empty.choices.clear();
+    empty.append_to(&mut messages);
+    assert_eq!(messages.len(), 2);
+    assert!(ChatCompletionMessageParam::from_completion(&empty).is_none());
+}
+
+#[test]
+fn test_developer_serialize() {
+    let developer = developer!("content");
+    let left = serde_json::to_value(&developer).unwrap();
+    let right: serde_json::Value = serde_json::json!({
+        "content": "content",
+        "role": "developer"
+    });
+    assert_eq!(left, right);
+
+    let developer = developer!(content = "content", name = "name");
+    let left = serde_json::to_value(&developer).unwrap();
+    let right: serde_json::Value = serde_json::json!({
+        "content": "content",
+        "name": "name",
+        "role": "developer"
+    });
+    assert_eq!(left, right);
+}
+
+#[test]
+fn test_chat_completion_mock() {
+    let completion = ChatCompletion::mock("Hello, world!")
+        .with_tool_call("call_1", "get_current_weather", "{\"city\":\"Paris\"}")
+        .with_usage(10, 5);
+
+    assert_eq!(completion.content(), Some("Hello, world!"));
+    assert!(matches!(
+        completion.choices[0].finish_reason,
+        FinishReason::ToolCalls
+    ));
+    assert_eq!(completion.tool_calls().unwrap()[0].function.id, "call_1");
+    assert_eq!(completion.usage.unwrap().total_tokens, 15);
+
+    let chunk = ChatCompletionChunk::mock("Hel").with_reasoning("thinking");
+    assert_eq!(chunk.content(), Some("Hel"));
+    assert_eq!(chunk.reasoning(), Some("thinking"));
+
+    let usage_chunk = ChatCompletionChunk::mock_usage(3, 4);
+    assert!(usage_chunk.choices.is_empty());
+    assert_eq!(usage_chunk.usage.unwrap().total_tokens, 7);
+}
+
+#[test]
+fn test_chat_completion_logprobs() {
+    let json = fs::read_to_string("./assets/chatcompletion_logprobs.json").unwrap();
+    let chatcompletion: ChatCompletion = serde_json::from_str(&json).unwrap();
+
+    let logprobs = chatcompletion.logprobs().unwrap();
+    assert!(logprobs.refusal.is_none());
+    let tokens = logprobs.content.as_ref().unwrap();
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].token, "Hi");
+    assert_eq!(tokens[0].bytes.as_deref(), Some(&b"Hi"[..]));
+    assert!((tokens[0].prob() - 0.9999).abs() < 1e-4);
+
+    let top = tokens[0].top_logprobs.as_ref().unwrap();
+    assert_eq!(top[1].token, "Hello");
+    assert!(top[1].prob() < 1e-4);
+
+    assert!(ChatCompletion::mock("Hi").logprobs().is_none());
+}
+
+#[test]
+fn test_chat_completion_token_logprobs() {
+    let json = serde_json::json!({
+        "id": "chatcmpl-logprobs",
+        "object": "chat.completion",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hello world"},
+            "logprobs": {
+                "content": [
+                    {"token": "Hello", "logprob": -0.25, "bytes": null, "top_logprobs": []},
+                    {"token": " world", "logprob": -0.75, "bytes": null, "top_logprobs": []}
+                ],
+                "refusal": null
+            },
+            "finish_reason": "stop"
+        }]
+    });
+    let chatcompletion: ChatCompletion = serde_json::from_value(json).unwrap();
+
+    let pairs: Vec<(&str, f64)> = chatcompletion
+        .token_logprobs()
+        .unwrap()
+        .iter()
+        .map(|t| (t.token.as_str(), t.logprob))
+        .collect();
+    assert_eq!(pairs, vec![("Hello", -0.25), (" world", -0.75)]);
+
+    let tokens = chatcompletion.choices[0].token_logprobs().unwrap();
+    let perplexity = (-tokens.iter().map(|t| t.logprob).sum::<f64>() / tokens.len() as f64).exp();
+    assert!((perplexity - 0.5f64.exp()).abs() < 1e-12);
+
+    assert!(ChatCompletion::mock("Hi").token_logprobs().is_none());
+}
+
+#[test]
+fn test_prompt_serialize() {
+    let single = serde_json::to_value(Prompt::from("Say hello")).unwrap();
+    assert_eq!(single, serde_json::json!("Say hello"));
+
+    let multi = serde_json::to_value(Prompt::from(["Say hello", "Say goodbye"])).unwrap();
+    assert_eq!(multi, serde_json::json!(["Say hello", "Say goodbye"]));
+
+    assert_eq!(
+        Prompt::from(vec!["a".to_string()]),
+        Prompt::Multi(vec!["a".to_string()])
+    );
+}
+
+#[test]
+fn test_completion_batched_prompts() {
+    let json = serde_json::json!({
+        "id": "cmpl-abc123",
+        "object": "text_completion",
+        "created": 1700000000,
+        "model": "gpt-3.5-turbo-instruct",
+        "choices": [
+            {"index": 1, "text": "Goodbye!", "finish_reason": "stop", "logprobs": null},
+            {"index": 0, "text": "Hello!", "finish_reason": "stop", "logprobs": null}
+        ]
+    });
+    let completion: completions::Completion = serde_json::from_value(json).unwrap();
+    assert_eq!(completion.choices.len(), 2);
+
+    let first = completion.choices_for_prompt(0, 1);
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].index, 0);
+    assert_eq!(first[0].text, "Hello!");
+
+    let second = completion.choices_for_prompt(1, 1);
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].index, 1);
+    assert_eq!(second[0].text, "Goodbye!");
+
+    assert_eq!(completion.choices[0].prompt_index(2), 0);
+    assert!(completion.choices_for_prompt(2, 1).is_empty());
+}
+
+#[test]
+fn test_chat_completion_all_choices() {
+    let json = serde_json::json!({
+        "id": "chatcmpl-multi",
+        "object": "chat.completion",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "choices": [
+            {
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_a",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{\"location\":\"Boston\"}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            },
+            {
+                "index": 1,
+                "message": {
+                    "role": "assistant",
+                    "content": "Let me check both.",
+                    "tool_calls": [
+                        {
+                            "id": "call_b",
+                            "type": "function",
+                            "function": {"name": "get_weather", "arguments": "{\"location\":\"Paris\"}"}
+                        },
+                        {
+                            "id": "call_c",
+                            "type": "function",
+                            "function": {"name": "get_time", "arguments": "{}"}
+                        }
+                    ]
+                },
+                "finish_reason": "tool_calls"
+            }
+        ]
+    });
+    let chatcompletion: ChatCompletion = serde_json::from_value(json).unwrap();
+
+    let tool_calls: Vec<(usize, &str)> = chatcompletion
+        .all_tool_calls()
+        .into_iter()
+        .map(|(index, call)| (index, call.function.id.as_str()))
+        .collect();
+    assert_eq!(
+        tool_calls,
+        vec![(0, "call_a"), (1, "call_b"), (1, "call_c")]
+    );
+    assert_eq!(chatcompletion.tool_calls().unwrap().len(), 1);
+
+    assert_eq!(
+        chatcompletion.all_content(),
+        vec![(1, "Let me check both.")]
+    );
+}
+
+#[test]
+fn test_function_parse_arguments() {
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct WeatherArgs {
+        location: String,
+    }
+
+    let function = Function::new("call_1", "get_weather", r#"{"location":"Boston, MA"}"#);
+    let args: WeatherArgs = function.parse_arguments().unwrap();
+    assert_eq!(
+        args,
+        WeatherArgs {
+            location: "Boston, MA".to_string()
+        }
+    );
+    assert_eq!(
+        function.arguments_value().unwrap()["location"],
+        "Boston, MA"
+    );
+
+    // 流被截断时参数不是完整的JSON
+    let truncated = Function::new("call_1", "get_weather", r#"{"location":"Bos"#);
+    let error = truncated.parse_arguments::<WeatherArgs>().unwrap_err();
+    assert!(error.is_eof());
+    assert!(truncated.arguments_value().is_err());
+}
+
+#[test]
+fn test_tool_call_parse_arguments() {
+    use openai4rs::error::ProcessingError;
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct WeatherArgs {
+        location: String,
+    }
+
+    let completion = ChatCompletion::mock("")
+        .with_tool_call("call_1", "get_time", r#"{"timezone":"UTC"}"#)
+        .with_tool_call("call_2", "get_weather", r#"{"location":"Boston, MA"}"#);
+
+    let tool_call = &completion.tool_calls().unwrap()[1];
+    let args: WeatherArgs = tool_call.parse_arguments().unwrap();
+    assert_eq!(args.location, "Boston, MA");
+
+    let args: WeatherArgs = completion.tool_arguments("get_weather").unwrap().unwrap();
+    assert_eq!(args.location, "Boston, MA");
+    assert!(
+        completion
+            .tool_arguments::<WeatherArgs>("get_stock_price")
+            .is_none()
+    );
+
+    // 参数结构不匹配
+    let error = completion
+        .tool_arguments::<WeatherArgs>("get_time")
+        .unwrap()
+        .unwrap_err();
+    assert!(error.is_processing_error());
+    assert!(error.to_string().contains("get_time"));
+
+    // 模型生成的JSON不完整
+    let malformed =
+        ChatCompletion::mock("").with_tool_call("call_1", "get_weather", r#"{"location":"Bos"#);
+    let error = malformed
+        .tool_arguments::<WeatherArgs>("get_weather")
+        .unwrap()
+        .unwrap_err();
+    match error {
+        OpenAIError::Processing(ProcessingError::ToolArguments {
+            error,
+            name,
+            target_type,
+        }) => {
+            assert!(error.is_eof());
+            assert_eq!(name, "get_weather");
+            assert!(target_type.ends_with("WeatherArgs"));
+        }
+        other => panic!("expected a tool arguments error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_tool_result_message() {
+    let json = serde_json::json!({
+        "role": "assistant",
+        "content": null,
+        "tool_calls": [{
+            "id": "call_abc123",
+            "type": "function",
+            "function": {"name": "get_weather", "arguments": "{\"location\":\"Boston\"}"}
+        }]
+    });
+    let message: ChatCompletionMessage = serde_json::from_value(json).unwrap();
+    let tool_call = &message.tool_calls().unwrap()[0];
+
+    let from_tool_call = ChatCompletionMessageParam::tool_result(tool_call, "72°F");
+    let from_function = ChatCompletionMessageParam::tool_result(
+        &tool_call.function,
+        serde_json::json!({"temp": 72}),
+    );
+
+    match (&from_tool_call, &from_function) {
+        (ChatCompletionMessageParam::Tool(a), ChatCompletionMessageParam::Tool(b)) => {
+            assert_eq!(a.tool_call_id, "call_abc123");
+            assert_eq!(b.tool_call_id, "call_abc123");
+            assert!(matches!(&a.content, Content::Text(text) if text == "72°F"));
+            assert!(matches!(&b.content, Content::Object(_)));
+        }
+        other => panic!("unexpected messages: {other:?}"),
+    }
+
+    let expected = tool!(tool_call_id: "call_abc123", content: "72°F");
+    assert_eq!(
+        serde_json::to_value(&from_tool_call).unwrap(),
+        serde_json::to_value(&expected).unwrap()
+    );
+}
+
+#[test]
+fn test_tool_message_from_struct() {
+    #[derive(serde::Serialize)]
+    struct Weather {
+        temperature: i32,
+        unit: &'static str,
+    }
+
+    let weather = Weather {
+        temperature: 72,
+        unit: "F",
+    };
+    let expected = serde_json::json!({
+        "role": "tool",
+        "tool_call_id": "call_abc123",
+        "content": r#"{"temperature":72,"unit":"F"}"#
+    });
+
+    let from_macro = tool!(tool_call_id: "call_abc123", content: &weather);
+    assert_eq!(serde_json::to_value(&from_macro).unwrap(), expected);
+
+    let function = Function::new("call_abc123", "get_weather", "{}");
+    let from_helper = ChatCompletionMessageParam::tool_result_json(&function, &weather).unwrap();
+    assert_eq!(serde_json::to_value(&from_helper).unwrap(), expected);
+}
+
+#[test]
+fn test_content_as_text() {
+    assert_eq!(Content::from("hello").as_text(), Some("hello"));
+    assert_eq!(
+        Content::from(serde_json::json!({"temp": 72})).as_text(),
+        None
+    );
+}
+
+#[test]
+fn test_content_into_text() {
+    assert_eq!(
+        Content::from("hello").into_text(),
+        Some("hello".to_string())
+    );
+    assert_eq!(Content::from(serde_json::json!([1, 2])).into_text(), None);
+}
+
+#[test]
+fn test_content_display() {
+    assert_eq!(Content::from("hello").to_string(), "hello");
+    assert_eq!(
+        Content::from(serde_json::json!({"temp": 72, "unit": "F"})).to_string(),
+        r#"{"temp":72,"unit":"F"}"#
+    );
+}
+
+fn assert_message_round_trip(message: &ChatCompletionMessageParam) {
+    let json = serde_json::to_value(message).unwrap();
+    let restored: ChatCompletionMessageParam = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+}
+
+#[test]
+fn test_message_param_round_trip() {
+    let system = system!("You are a helpful assistant.");
+    let developer = developer!(content: "Answer in French.", name: "dev");
+    let user = user!(content: serde_json::json!([{"type": "text", "text": "hi"}]));
+    let assistant = assistant!(
+        content = "Let me check.",
+        name = "bot",
+        tool_calls = vec![ChatCompletionMessageToolCallParam::function(
+            "call_abc123",
+            "get_weather",
+            "{\"location\":\"Boston\"}",
+        )]
+    );
+    let tool = tool!(tool_call_id: "call_abc123", content: "72°F");
+
+    for message in [&system, &developer, &user, &assistant, &tool] {
+        assert_message_round_trip(message);
+    }
+
+    let restored: ChatCompletionMessageParam =
+        serde_json::from_value(serde_json::to_value(&assistant).unwrap()).unwrap();
+    match restored {
+        ChatCompletionMessageParam::Assistant(inner) => {
+            let tool_calls = inner.tool_calls.unwrap();
+            let ChatCompletionMessageToolCallParam::Function(function) = &tool_calls[0];
+            assert_eq!(function.id, "call_abc123");
+            assert_eq!(function.name, "get_weather");
+        }
+        other => panic!("unexpected message: {other:?}"),
+    }
+}
+
+#[test]
+fn test_message_param_deserialize_conversation() {
+    let json = serde_json::json!([
+        {"role": "system", "content": "Be brief."},
+        {"role": "user", "content": "Weather in Boston?"},
+        {"role": "assistant", "content": null, "tool_calls": [{
+            "id": "call_abc123",
+            "type": "function",
+            "function": {"name": "get_weather", "arguments": "{}"}
+        }]},
+        {"role": "tool", "tool_call_id": "call_abc123", "content": "72°F"}
+    ]);
+    let messages: Vec<ChatCompletionMessageParam> = serde_json::from_value(json).unwrap();
+    assert_eq!(messages.len(), 4);
+    assert!(matches!(messages[0], ChatCompletionMessageParam::System(_)));
+    assert!(matches!(messages[1], ChatCompletionMessageParam::User(_)));
+    assert!(matches!(
+        &messages[2],
+        ChatCompletionMessageParam::Assistant(inner) if inner.content.is_none()
+    ));
+    assert!(matches!(messages[3], ChatCompletionMessageParam::Tool(_)));
+
+    let unknown = serde_json::json!({"role": "narrator", "content": "..."});
+    assert!(serde_json::from_value::<ChatCompletionMessageParam>(unknown).is_err());
+}
+
+#[test]
+fn test_deserialize_models_list() {
+    let openai = serde_json::json!({
+        "object": "list",
+        "data": [
+            {"id": "gpt-4o-mini", "object": "model", "created": 1721172741, "owned_by": "system"},
+            {"id": "ft:gpt-4o-mini:acme::abc123", "object": "model", "created": 1730000000, "owned_by": "acme"}
+        ]
+    });
+    let models: models::ModelsData = serde_json::from_value(openai).unwrap();
+    assert_eq!(models.data.len(), 2);
+    assert_eq!(models.data[0].created, 1721172741);
+    assert!(models.data[0].is_owned_by("system"));
+    assert!(models.data[1].is_owned_by("ACME"));
+    assert!(!models.data[1].is_owned_by("system"));
+    assert!(models.data[0].extra_fields.is_none());
+    assert!(models.data[0].context_length.is_none());
+    assert!(models.data[0].pricing.is_none());
+    assert!(models.data[0].architecture.is_none());
+
+    let openrouter = serde_json::json!({
+        "data": [{
+            "id": "openai/gpt-4o",
+            "canonical_slug": "openai/gpt-4o",
+            "name": "OpenAI: GPT-4o",
+            "created": 1715367049,
+            "description": "GPT-4o is OpenAI's latest flagship model.",
+            "context_length": 128000,
+            "architecture": {
+                "modality": "text+image->text",
+                "input_modalities": ["text", "image", "file"],
+                "output_modalities": ["text"],
+                "tokenizer": "GPT",
+                "instruct_type": null
+            },
+            "pricing": {
+                "prompt": "0.0000025",
+                "completion": "0.00001",
+                "request": "0",
+                "image": "0.003613",
+                "web_search": "0",
+                "input_cache_read": "0.00000125"
+            },
+            "top_provider": {"context_length": 128000, "is_moderated": true}
+        }]
+    });
+    let models: models::ModelsData = serde_json::from_value(openrouter).unwrap();
+    let model = &models.data[0];
+    assert_eq!(model.id, "openai/gpt-4o");
+    assert_eq!(model.created, 1715367049);
+    assert!(model.owned_by.is_none());
+    assert!(!model.is_owned_by("openai"));
+    assert_eq!(model.context_length, Some(128000));
+
+    let pricing = model.pricing.as_ref().unwrap();
+    assert_eq!(pricing.prompt.as_deref(), Some("0.0000025"));
+    assert_eq!(pricing.completion.as_deref(), Some("0.00001"));
+    assert_eq!(pricing.image.as_deref(), Some("0.003613"));
+    assert_eq!(
+        pricing.extra_fields.get("input_cache_read"),
+        Some(&serde_json::json!("0.00000125"))
+    );
+
+    let architecture = model.architecture.as_ref().unwrap();
+    assert_eq!(architecture.modality.as_deref(), Some("text+image->text"));
+    assert_eq!(architecture.output_modalities, vec!["text"]);
+    assert_eq!(architecture.tokenizer.as_deref(), Some("GPT"));
+    assert!(architecture.instruct_type.is_none());
+    assert!(model.supports_input_modality("IMAGE"));
+    assert!(!model.supports_input_modality("audio"));
+
+    assert_eq!(
+        model.extra_field("context_length"),
+        Some(&serde_json::json!(128000))
+    );
+    assert_eq!(
+        model
+            .extra_field("pricing")
+            .and_then(|pricing| pricing.get("prompt")),
+        Some(&serde_json::json!("0.0000025"))
+    );
+    assert!(model.extra_field("architecture").is_some());
+    assert_eq!(
+        model.extra_field("top_provider"),
+        Some(&serde_json::json!({"context_length": 128000, "is_moderated": true}))
+    );
+    assert_eq!(
+        model.extra_field("name"),
+        Some(&serde_json::json!("OpenAI: GPT-4o"))
+    );
+    assert_eq!(model.extra_field("missing"), None);
+
+    // 格式不符合预期的提供商字段保留在`extra_fields`中
+    let unusual = serde_json::json!({
+        "id": "local-model",
+        "created": 0,
+        "context_length": "unknown",
+        "pricing": "free"
+    });
+    let model: models::Model = serde_json::from_value(unusual).unwrap();
+    assert!(model.context_length.is_none());
+    assert!(model.pricing.is_none());
+    assert_eq!(
+        model.extra_field("context_length"),
+        Some(&serde_json::json!("unknown"))
+    );
+    assert_eq!(
+        model.extra_field("pricing"),
+        Some(&serde_json::json!("free"))
+    );
+}
+
+#[test]
+fn test_deserialize_reasoning_usage() {
+    let json = serde_json::json!({
+        "prompt_tokens": 1117,
+        "completion_tokens": 46,
+        "total_tokens": 1163,
+        "prompt_tokens_details": {
+            "cached_tokens": 1024,
+            "audio_tokens": 0
+        },
+        "completion_tokens_details": {
+            "reasoning_tokens": 32,
+            "audio_tokens": 0,
+            "accepted_prediction_tokens": 0,
+            "rejected_prediction_tokens": 0
+        },
+        "cost": 0.0012
+    });
+
+    let usage: common::types::CompletionUsage = serde_json::from_value(json).unwrap();
+    assert_eq!(usage.total_tokens, 1163);
+    assert_eq!(usage.reasoning_tokens(), Some(32));
+    assert_eq!(usage.cached_tokens(), Some(1024));
+    let details = usage.completion_tokens_details.as_ref().unwrap();
+    assert_eq!(details.accepted_prediction_tokens, Some(0));
+    assert_eq!(
+        usage.extra_fields.as_ref().unwrap()["cost"],
+        serde_json::json!(0.0012)
+    );
+
+    // 未返回明细的提供商
+    let usage: common::types::CompletionUsage = serde_json::from_value(serde_json::json!({
+        "prompt_tokens": 5,
+        "completion_tokens": 7,
+        "total_tokens": 12,
+        "prompt_tokens_details": null
+    }))
+    .unwrap();
+    assert_eq!(usage.reasoning_tokens(), None);
+    assert_eq!(usage.cached_tokens(), None);
+    assert!(usage.extra_fields.is_none());
+}
+
+#[test]
+fn test_deserialize_message_annotations() {
+    let json = serde_json::json!({
+        "id": "chatcmpl-search",
+        "object": "chat.completion",
+        "created": 1741891428,
+        "model": "gpt-4o-search-preview",
+        "choices": [
+            {
+                "index": 0,
+                "finish_reason": "stop",
+                "message": {
+                    "role": "assistant",
+                    "content": "Rust 1.85 stabilized async closures [Rust Blog].",
+                    "refusal": null,
+                    "annotations": [
+                        {
+                            "type": "url_citation",
+                            "url_citation": {
+                                "end_index": 47,
+                                "start_index": 36,
+                                "title": "Announcing Rust 1.85.0",
+                                "url": "https://blog.rust-lang.org/2025/02/20/Rust-1.85.0.html"
+                            }
+                        },
+                        {
+                            "type": "url_citation",
+                            "url_citation": {
+                                "end_index": 47,
+                                "start_index": 36,
+                                "title": "Rust Release Notes",
+                                "url": "https://doc.rust-lang.org/releases.html"
+                            }
+                        }
+                    ]
+                }
+            }
+        ]
+    });
+
+    let completion: ChatCompletion = serde_json::from_value(json).unwrap();
+    assert_eq!(completion.annotations().unwrap().len(), 2);
+    assert_eq!(completion.annotations().unwrap()[0].r#type, "url_citation");
+
+    let citations = completion.choices[0].message.citations();
+    let links: Vec<(&str, &str)> = citations
+        .iter()
+        .map(|citation| (citation.title.as_str(), citation.url.as_str()))
+        .collect();
+    assert_eq!(
+        links,
+        vec![
+            (
+                "Announcing Rust 1.85.0",
+                "https://blog.rust-lang.org/2025/02/20/Rust-1.85.0.html"
+            ),
+            (
+                "Rust Release Notes",
+                "https://doc.rust-lang.org/releases.html"
+            ),
+        ]
+    );
+    let content = completion.content().unwrap();
+    let cited = &content[citations[0].start_index as usize..citations[0].end_index as usize];
+    assert_eq!(cited, "[Rust Blog]");
+
+    // 没有注释的消息
+    let completion = ChatCompletion::mock("Hello!");
+    assert!(completion.annotations().is_none());
+    assert!(completion.choices[0].message.citations().is_empty());
+}
+
+#[test]
+fn test_deserialize_unknown_finish_reason() {
+    let json = serde_json::json!({
+        "id": "chatcmpl-eos",
+        "object": "chat.completion",
+        "created": 1699896916,
+        "model": "local-model",
+        "choices": [
+            {
+                "index": 0,
+                "message": {"role": "assistant", "content": "done"},
+                "finish_reason": "eos"
+            },
+            {
+                "index": 1,
+                "message": {"role": "assistant", "content": "calling"},
+                "finish_reason": "tool_calls"
+            }
+        ]
+    });
+
+    let completion: ChatCompletion = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        completion.choices[0].finish_reason,
+        FinishReason::Unknown("eos".to_string())
+    );
+    assert_eq!(completion.choices[0].finish_reason.as_str(), "eos");
+    assert_eq!(completion.choices[1].finish_reason, FinishReason::ToolCalls);
+
+    let chunk: ChatCompletionChunk = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-eos",
+        "object": "chat.completion.chunk",
+        "created": 1699896916,
+        "model": "local-model",
+        "choices": [{"index": 0, "delta": {}, "finish_reason": "tool_use"}]
+    }))
+    .unwrap();
+    assert_eq!(
+        chunk.choices[0].finish_reason,
+        Some(FinishReason::Unknown("tool_use".to_string()))
+    );
+}
+
+#[test]
+fn test_content_macro_parts() {
+    let image_url = "https://example.com/cat.png";
+    let content = content!(text: "What is in this image?", image_url: image_url, text: format!("Answer in {} words.", 5));
+    assert_eq!(
+        serde_json::to_value(&content).unwrap(),
+        serde_json::json!([
+            {"type": "text", "text": "What is in this image?"},
+            {"type": "image_url", "image_url": {"url": "https://example.com/cat.png"}},
+            {"type": "text", "text": "Answer in 5 words."}
+        ])
+    );
+
+    let message = user!(content: content!(image_url: image_url));
+    assert_eq!(
+        serde_json::to_value(&message).unwrap()["content"],
+        serde_json::json!([{"type": "image_url", "image_url": {"url": image_url}}])
+    );
+
+    // 非键值形式仍按JSON表达式处理
+    let text = "hello";
+    assert_eq!(content!(text).as_text(), Some("hello"));
+    assert!(matches!(
+        content!(serde_json::json!([1])),
+        Content::Object(_)
+    ));
+}
+
+#[test]
+fn test_chat_types_resolve_to_single_definition() {
+    // 各个公开路径必须指向同一个类型，否则这些函数无法编译
+    fn completion(value: openai4rs::chat::ChatCompletion) -> openai4rs::ChatCompletion {
+        value
+    }
+    fn delta(value: openai4rs::ChoiceDelta) -> openai4rs::modules::chat::types::ChoiceDelta {
+        value
+    }
+    fn chunk(value: openai4rs::chat::types::ChatCompletionChunk) -> ChatCompletionChunk {
+        value
+    }
+
+    let chunk = chunk(ChatCompletionChunk::mock("hi"));
+    let delta = delta(chunk.choices[0].delta.clone());
+    assert_eq!(delta.content(), Some("hi"));
+    assert_eq!(completion(ChatCompletion::mock("hi")).content(), Some("hi"));
+}
+
+#[test]
+fn test_deserialize_chunk_index_and_extra_fields() {
+    let chunk: ChatCompletionChunk = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "provider": "gateway",
+        "choices": [{
+            "index": 1,
+            "delta": {"content": "hi", "annotations": []},
+            "finish_reason": null
+        }]
+    }))
+    .unwrap();
+
+    let index: usize = chunk.choices[0].index;
+    assert_eq!(index, 1);
+    assert_eq!(
+        chunk.extra_fields.as_ref().unwrap()["provider"],
+        serde_json::json!("gateway")
+    );
+    assert!(
+        chunk.choices[0]
+            .delta
+            .extra_fields
+            .as_ref()
+            .unwrap()
+            .contains_key("annotations")
+    );
+}
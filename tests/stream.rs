@@ -0,0 +1,752 @@
+use futures::StreamExt;
+use openai4rs::*;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+const CHUNK: &str = r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1699896916,"model":"gpt-4o","choices":[{"index":0,"delta":{"role":"assistant","content":"Hello"},"finish_reason":null}]}"#;
+
+/// 启动一个只发送一个SSE事件且不会主动结束响应的服务器。
+///
+/// 当客户端关闭连接时，通过返回的通道发出通知。
+async fn start_hanging_sse_server() -> (String, oneshot::Receiver<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (closed_tx, closed_rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 16 * 1024];
+        let _ = socket.read(&mut buf).await.unwrap();
+
+        let event = format!("data: {CHUNK}\n\n");
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n{:x}\r\n{}\r\n",
+            event.len(),
+            event
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+
+        // 保持连接打开，直到客户端断开
+        loop {
+            match socket.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => continue,
+            }
+        }
+        let _ = closed_tx.send(());
+    });
+
+    (format!("http://{addr}"), closed_rx)
+}
+
+#[tokio::test]
+async fn test_stream_cancellation_token() {
+    let (base_url, closed_rx) = start_hanging_sse_server().await;
+    let client = OpenAI::new("test-key", &base_url);
+    let token = CancellationToken::new();
+
+    let messages = vec![user!("hello")];
+    let mut stream = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages).cancellation_token(token.clone()))
+        .await
+        .unwrap();
+
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.content(), Some("Hello"));
+
+    token.cancel();
+
+    // 后台任务退出后发送端被丢弃，流随之结束
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("stream did not terminate after cancellation");
+    assert!(next.is_none());
+
+    // 底层连接被关闭
+    tokio::time::timeout(Duration::from_secs(5), closed_rx)
+        .await
+        .expect("connection was not closed after cancellation")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_stream_drop_closes_connection() {
+    let (base_url, closed_rx) = start_hanging_sse_server().await;
+    let client = OpenAI::new("test-key", &base_url);
+
+    let messages = vec![user!("hello")];
+    let mut stream = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    assert!(stream.next().await.unwrap().is_ok());
+    drop(stream);
+
+    tokio::time::timeout(Duration::from_secs(5), closed_rx)
+        .await
+        .expect("connection was not closed after dropping the stream")
+        .unwrap();
+}
+
+fn sse_body(chunks: &[serde_json::Value]) -> String {
+    let mut body = String::new();
+    for chunk in chunks {
+        body.push_str(&format!("data: {chunk}\n\n"));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+fn choice_chunk(index: usize, content: &str, finish_reason: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "id": "chatcmpl-n",
+        "object": "chat.completion.chunk",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": index,
+            "delta": {"role": "assistant", "content": content},
+            "finish_reason": finish_reason
+        }]
+    })
+}
+
+#[tokio::test]
+async fn test_stream_multiple_choices() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = sse_body(&[
+        choice_chunk(0, "Hel", None),
+        choice_chunk(1, "Bon", None),
+        choice_chunk(0, "lo", None),
+        choice_chunk(1, "jour", None),
+        choice_chunk(1, "", Some("stop")),
+        choice_chunk(0, "", Some("length")),
+    ]);
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(
+            serde_json::json!({"n": 2, "stream": true}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("greet")];
+    let mut stream = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages).n(2))
+        .await
+        .unwrap();
+
+    let mut choices: Vec<StreamChoice> = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        for choice in chunk.unwrap().choices {
+            match choices.iter_mut().find(|c| c.index == choice.index) {
+                Some(existing) => existing.merge(choice),
+                None => choices.push(choice),
+            }
+        }
+    }
+    choices.sort_by_key(|c| c.index);
+
+    assert_eq!(choices.len(), 2);
+    assert_eq!(choices[0].delta.content.as_deref(), Some("Hello"));
+    assert!(matches!(
+        choices[0].finish_reason,
+        Some(FinishReason::Length)
+    ));
+    assert_eq!(choices[1].index, 1);
+    assert_eq!(choices[1].delta.content.as_deref(), Some("Bonjour"));
+    assert!(matches!(choices[1].finish_reason, Some(FinishReason::Stop)));
+}
+
+#[tokio::test]
+async fn test_stream_collect_completion_multiple_choices() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = sse_body(&[
+        choice_chunk(1, "Bon", None),
+        choice_chunk(0, "Hel", None),
+        choice_chunk(1, "jour", None),
+        choice_chunk(0, "lo", None),
+        choice_chunk(0, "", Some("stop")),
+        choice_chunk(1, "", Some("length")),
+    ]);
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("greet")];
+    let completion = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages).n(2))
+        .await
+        .unwrap()
+        .collect_completion()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(completion.id, "chatcmpl-n");
+    assert_eq!(completion.object, "chat.completion");
+    assert_eq!(completion.all_content(), vec![(0, "Hello"), (1, "Bonjour")]);
+    assert!(matches!(
+        completion.choices[0].finish_reason,
+        FinishReason::Stop
+    ));
+    assert!(matches!(
+        completion.choices[1].finish_reason,
+        FinishReason::Length
+    ));
+}
+
+#[tokio::test]
+async fn test_chat_rejects_invalid_n() {
+    // 参数校验在发送前完成，不会访问该地址
+    let client = OpenAI::new("test-key", "http://127.0.0.1:1");
+    let messages = vec![user!("hello")];
+
+    let error = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages).n(0))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        OpenAIError::Request(openai4rs::error::RequestError::Validation { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_stream_raw_events() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    // 包含一个空事件和`[DONE]`之后的数据，二者都不应出现在流中
+    let body = format!(
+        "data: {CHUNK}\n\nevent: ping\ndata: \n\ndata: not json\n\ndata: [DONE]\n\ndata: {CHUNK}\n\n"
+    );
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(serde_json::json!({"stream": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hi")];
+    let stream = client
+        .chat()
+        .create_stream_raw(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    let events: Vec<String> = stream.map(Result::unwrap).collect().await;
+    assert_eq!(events, vec![CHUNK.to_string(), "not json".to_string()]);
+}
+
+#[tokio::test]
+async fn test_stream_skips_keepalive_events() {
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = format!(
+        ": keepalive\n\ndata: {}\n\nevent: ping\ndata: {{\"type\": \"ping\"}}\n\n: \n\nevent: keepalive\ndata: \n\ndata: {}\n\ndata: [DONE]\n\n",
+        choice_chunk(0, "Hel", None),
+        choice_chunk(0, "lo", Some("stop")),
+    );
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let keepalives = Arc::new(Mutex::new(Vec::new()));
+    let recorded = keepalives.clone();
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url(server.uri())
+        .on_keepalive(move |event_type| recorded.lock().unwrap().push(event_type.to_string()))
+        .build_openai()
+        .unwrap();
+    let messages = vec![user!("hi")];
+    let chunks: Vec<ChatCompletionChunk> = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let contents: Vec<_> = chunks
+        .iter()
+        .map(|chunk| chunk.choices[0].delta.content.as_deref())
+        .collect();
+    assert_eq!(contents, vec![Some("Hel"), Some("lo")]);
+    // 注释行在解析阶段被丢弃，不会触发回调
+    assert_eq!(*keepalives.lock().unwrap(), vec!["ping", "keepalive"]);
+}
+
+fn tool_call_chunk(
+    index: usize,
+    id: Option<&str>,
+    name: &str,
+    arguments: &str,
+) -> ChatCompletionChunk {
+    let mut tool_call = serde_json::json!({
+        "index": index,
+        "type": "function",
+        "function": {"name": name, "arguments": arguments}
+    });
+    if let Some(id) = id {
+        tool_call["id"] = serde_json::json!(id);
+    }
+    serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-tools",
+        "object": "chat.completion.chunk",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "delta": {"role": "assistant", "tool_calls": [tool_call]},
+            "finish_reason": null
+        }]
+    }))
+    .unwrap()
+}
+
+fn merge_tool_call_chunks(
+    chunks: Vec<ChatCompletionChunk>,
+) -> Vec<(usize, String, String, String)> {
+    let mut accumulator = ChatCompletionAccumulator::new();
+    for chunk in chunks {
+        accumulator.push(chunk);
+    }
+    let completion = accumulator.finish().unwrap();
+    completion
+        .tool_calls()
+        .unwrap()
+        .iter()
+        .map(|tool_call| {
+            (
+                tool_call.index,
+                tool_call.function.id.clone(),
+                tool_call.function.name.clone(),
+                tool_call.function.arguments.clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_merge_interleaved_tool_calls_in_one_chunk() {
+    // 标准格式：并发的工具调用按`index`交错，同一个块中包含多个工具调用增量
+    let arguments_chunk = |first: &str, second: &str| -> ChatCompletionChunk {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-tools",
+            "object": "chat.completion.chunk",
+            "created": 1699896916,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "delta": {"tool_calls": [
+                    {"index": 0, "type": "function", "function": {"arguments": first}},
+                    {"index": 1, "type": "function", "function": {"arguments": second}}
+                ]},
+                "finish_reason": null
+            }]
+        }))
+        .unwrap()
+    };
+
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, Some("call_a"), "get_weather", ""),
+        tool_call_chunk(1, Some("call_b"), "get_time", ""),
+        arguments_chunk("{\"city\":", "{\"tz\":"),
+        arguments_chunk("\"Boston\"}", "\"UTC\"}"),
+    ]);
+
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[0].1, "call_a");
+    assert_eq!(tool_calls[0].2, "get_weather");
+    assert_eq!(tool_calls[0].3, "{\"city\":\"Boston\"}");
+    assert_eq!(tool_calls[1].1, "call_b");
+    assert_eq!(tool_calls[1].2, "get_time");
+    assert_eq!(tool_calls[1].3, "{\"tz\":\"UTC\"}");
+}
+
+#[test]
+fn test_merge_interleaved_concurrent_tool_calls() {
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, Some("call_a"), "get_weather", ""),
+        tool_call_chunk(1, Some("call_b"), "get_time", ""),
+        tool_call_chunk(0, None, "", "{\"city\":"),
+        tool_call_chunk(1, None, "", "{\"tz\":"),
+        tool_call_chunk(0, None, "", "\"Boston\"}"),
+        tool_call_chunk(1, None, "", "\"UTC\"}"),
+    ]);
+
+    assert_eq!(
+        tool_calls,
+        vec![
+            (
+                0,
+                "call_a".to_string(),
+                "get_weather".to_string(),
+                "{\"city\":\"Boston\"}".to_string()
+            ),
+            (
+                1,
+                "call_b".to_string(),
+                "get_time".to_string(),
+                "{\"tz\":\"UTC\"}".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_merge_sequential_index_zero_tool_calls() {
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, Some("call_a"), "get_weather", ""),
+        tool_call_chunk(0, None, "", "{\"city\":\"Boston\"}"),
+        tool_call_chunk(0, Some("call_b"), "get_time", ""),
+        tool_call_chunk(0, None, "", "{\"tz\":\"UTC\"}"),
+    ]);
+
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[0].1, "call_a");
+    assert_eq!(tool_calls[0].3, "{\"city\":\"Boston\"}");
+    assert_eq!(tool_calls[1].1, "call_b");
+    assert_eq!(tool_calls[1].2, "get_time");
+    assert_eq!(tool_calls[1].3, "{\"tz\":\"UTC\"}");
+}
+
+#[tokio::test]
+async fn test_stream_sequential_tool_calls_without_parallel_calls() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // 禁用并行工具调用后，每个工具调用都以`index`为0依次发送，服务端在每个数据块中回显该设置
+    let chunk = |id: Option<&str>, name: &str, arguments: &str| {
+        let mut tool_call = serde_json::json!({
+            "index": 0,
+            "type": "function",
+            "function": {"name": name, "arguments": arguments}
+        });
+        if let Some(id) = id {
+            tool_call["id"] = serde_json::json!(id);
+        }
+        serde_json::json!({
+            "id": "chatcmpl-tools",
+            "object": "chat.completion.chunk",
+            "created": 1699896916,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "delta": {"tool_calls": [tool_call]},
+                "finish_reason": null
+            }],
+            "parallel_tool_calls": false
+        })
+    };
+    let body = sse_body(&[
+        chunk(Some("call_a"), "get_weather", ""),
+        chunk(None, "", "{\"city\":\"Boston\"}"),
+        chunk(Some("call_b"), "get_weather", ""),
+        chunk(None, "", "{\"city\":\"Paris\"}"),
+    ]);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(body_partial_json(
+            serde_json::json!({"parallel_tool_calls": false}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("weather in Boston and Paris")];
+    let completion = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages).parallel_tool_calls(false))
+        .await
+        .unwrap()
+        .collect_completion()
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(completion.parallel_tool_calls(), Some(false));
+    let tool_calls = completion.tool_calls().unwrap();
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[0].function.id, "call_a");
+    assert_eq!(tool_calls[0].function.arguments, "{\"city\":\"Boston\"}");
+    assert_eq!(tool_calls[1].function.id, "call_b");
+    assert_eq!(tool_calls[1].function.arguments, "{\"city\":\"Paris\"}");
+}
+
+#[test]
+fn test_merge_index_zero_argument_continuation() {
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, Some("call_a"), "get_weather", ""),
+        tool_call_chunk(0, None, "", "{\"city\":"),
+        tool_call_chunk(0, None, "", "\"Boston\"}"),
+    ]);
+
+    assert_eq!(tool_calls.len(), 1);
+    assert_eq!(tool_calls[0].1, "call_a");
+    assert_eq!(tool_calls[0].2, "get_weather");
+    assert_eq!(tool_calls[0].3, "{\"city\":\"Boston\"}");
+}
+
+#[test]
+fn test_merge_index_zero_new_call_without_id() {
+    // 新的工具调用未携带`id`，但带有函数名
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, None, "get_weather", ""),
+        tool_call_chunk(0, None, "", "{\"city\":\"Boston\"}"),
+        tool_call_chunk(0, None, "get_time", ""),
+        tool_call_chunk(0, None, "", "{\"tz\":\"UTC\"}"),
+    ]);
+
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[0].2, "get_weather");
+    assert_eq!(tool_calls[0].3, "{\"city\":\"Boston\"}");
+    assert_eq!(tool_calls[1].2, "get_time");
+    assert_eq!(tool_calls[1].3, "{\"tz\":\"UTC\"}");
+}
+
+#[test]
+fn test_merge_name_after_id_only_delta() {
+    // 首个增量只携带`id`，函数名在随后的增量中到达
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, Some("call_a"), "", ""),
+        tool_call_chunk(0, None, "get_weather", ""),
+        tool_call_chunk(0, None, "", "{}"),
+    ]);
+
+    assert_eq!(tool_calls.len(), 1);
+    assert_eq!(tool_calls[0].1, "call_a");
+    assert_eq!(tool_calls[0].2, "get_weather");
+    assert_eq!(tool_calls[0].3, "{}");
+}
+
+#[test]
+fn test_merge_tool_call_with_repeated_id() {
+    let tool_calls = merge_tool_call_chunks(vec![
+        tool_call_chunk(0, Some("call_a"), "get_weather", "{\"city\":"),
+        tool_call_chunk(0, Some("call_a"), "", "\"Boston\"}"),
+    ]);
+
+    assert_eq!(tool_calls.len(), 1);
+    assert_eq!(tool_calls[0].1, "call_a");
+    assert_eq!(tool_calls[0].3, "{\"city\":\"Boston\"}");
+}
+
+#[test]
+fn test_merge_chunk_with_trailing_usage() {
+    let mut completion: ChatCompletionChunk = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "system_fingerprint": "fp_1",
+        "choices": [{
+            "index": 0,
+            "delta": {"role": "assistant", "content": "Hello"},
+            "finish_reason": null
+        }]
+    }))
+    .unwrap();
+    let usage_only: ChatCompletionChunk = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 1699896916,
+        "model": "gpt-4o",
+        "choices": [],
+        "usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+    }))
+    .unwrap();
+
+    completion.merge(ChatCompletionChunk::mock(", world").with_finish_reason(FinishReason::Stop));
+    completion.merge(usage_only);
+
+    assert_eq!(completion.choices.len(), 1);
+    assert_eq!(completion.content(), Some("Hello, world"));
+    assert_eq!(
+        completion.choices[0].finish_reason,
+        Some(FinishReason::Stop)
+    );
+    assert_eq!(completion.system_fingerprint.as_deref(), Some("fp_1"));
+    assert_eq!(completion.usage.unwrap().total_tokens, 7);
+}
+
+#[tokio::test]
+async fn test_stream_reasoning_then_content() {
+    let transport = MockTransport::new();
+    transport.push_chat_stream([
+        ChatCompletionChunk::mock("").with_reasoning("9.8 is"),
+        ChatCompletionChunk::mock("").with_reasoning(" larger"),
+        ChatCompletionChunk::mock("9.8").with_reasoning(""),
+        ChatCompletionChunk::mock(" is greater.").with_finish_reason(FinishReason::Stop),
+        ChatCompletionChunk::mock_usage(10, 5),
+    ]);
+    let client = Config::builder()
+        .api_key("test-key")
+        .base_url("https://api.test.com/v1")
+        .transport(transport)
+        .build_openai()
+        .unwrap();
+
+    let messages = vec![user!("9.11 and 9.8, which is greater?")];
+    let events: Vec<ReasoningEvent> = client
+        .chat()
+        .create_stream(ChatParam::new("mock-model", &messages))
+        .await
+        .unwrap()
+        .reasoning_then_content()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(
+        events,
+        [
+            ReasoningEvent::Reasoning("9.8 is".to_string()),
+            ReasoningEvent::Reasoning(" larger".to_string()),
+            ReasoningEvent::ReasoningDone,
+            ReasoningEvent::Content("9.8".to_string()),
+            ReasoningEvent::Content(" is greater.".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_stream_error_event_surfaces_api_error() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let error = serde_json::json!({
+        "error": {
+            "message": "Rate limit reached for requests",
+            "type": "rate_limit_error",
+            "param": null,
+            "code": "rate_limit_exceeded"
+        }
+    });
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(sse_body(&[error]), "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let messages = vec![user!("hi")];
+    let mut stream = client
+        .chat()
+        .create_stream(ChatParam::new("gpt-4o", &messages))
+        .await
+        .unwrap();
+
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(error.is_rate_limit());
+    match error {
+        OpenAIError::Api(api_error) => {
+            assert_eq!(api_error.status, 429);
+            assert_eq!(api_error.message, "Rate limit reached for requests");
+            assert_eq!(api_error.code(), Some("rate_limit_exceeded"));
+        }
+        other => panic!("expected an API error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_completions_create_stream() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let chunk = |text: &str, finish_reason: Option<&str>| {
+        serde_json::json!({
+            "id": "cmpl-stream",
+            "object": "text_completion",
+            "created": 1700000000,
+            "model": "gpt-3.5-turbo-instruct",
+            "choices": [{
+                "index": 0,
+                "text": text,
+                "logprobs": null,
+                "finish_reason": finish_reason
+            }]
+        })
+    };
+    let body = sse_body(&[
+        chunk("Once", None),
+        chunk(" upon", None),
+        chunk(" a time", Some("length")),
+    ]);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/completions"))
+        .and(body_partial_json(serde_json::json!({"stream": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = OpenAI::new("test-key", &server.uri());
+    let chunks: Vec<_> = client
+        .completions()
+        .create_stream(
+            CompletionsParam::new("gpt-3.5-turbo-instruct", "Tell a story").max_tokens(3),
+        )
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(chunks.len(), 3);
+    let chunks: Vec<_> = chunks.into_iter().map(Result::unwrap).collect();
+    let text: String = chunks
+        .iter()
+        .map(|chunk| chunk.choices[0].text.as_str())
+        .collect();
+    assert_eq!(text, "Once upon a time");
+    assert!(chunks.iter().all(|chunk| chunk.id == "cmpl-stream"));
+    assert!(chunks[0].choices[0].finish_reason.is_none());
+    assert!(matches!(
+        chunks[2].choices[0].finish_reason,
+        Some(completions::types::FinishReason::Length)
+    ));
+}
@@ -0,0 +1,151 @@
+use openai4rs::*;
+use serde::Deserialize;
+use serde_json::json;
+
+/// 温度单位
+#[derive(ToolParameters, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(ToolParameters, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+enum Detail {
+    #[default]
+    Brief,
+    FullReport,
+    #[serde(rename = "raw")]
+    RawData,
+}
+
+/// 地理坐标
+#[derive(ToolParameters, Deserialize)]
+#[allow(dead_code)]
+struct Coordinates {
+    /// 纬度
+    lat: f64,
+    /// 经度
+    lon: f64,
+}
+
+/// 获取天气的参数
+#[derive(ToolParameters, Deserialize)]
+#[allow(dead_code)]
+struct GetWeather {
+    /// 城市和州，例如：旧金山，加利福尼亚州
+    location: String,
+    unit: Option<Unit>,
+    /// 预报天数
+    days: i64,
+    include_hourly: bool,
+    tags: Vec<String>,
+    coordinates: Option<Coordinates>,
+    #[serde(default)]
+    detail: Detail,
+    #[serde(skip)]
+    cache_key: String,
+}
+
+#[test]
+fn test_derive_tool_parameters_struct() {
+    let schema = serde_json::to_value(GetWeather::parameters()).unwrap();
+
+    let mut required = schema["required"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap())
+        .collect::<Vec<_>>();
+    required.sort();
+    assert_eq!(required, vec!["days", "include_hourly", "location", "tags"]);
+
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["description"], "获取天气的参数");
+    assert_eq!(
+        schema["properties"]["location"],
+        json!({"type": "string", "description": "城市和州，例如：旧金山，加利福尼亚州"})
+    );
+    assert_eq!(
+        schema["properties"]["unit"],
+        json!({"type": "string", "description": "温度单位", "enum": ["celsius", "fahrenheit"]})
+    );
+    assert_eq!(
+        schema["properties"]["days"],
+        json!({"type": "integer", "description": "预报天数"})
+    );
+    assert_eq!(
+        schema["properties"]["include_hourly"],
+        json!({"type": "boolean"})
+    );
+    assert_eq!(
+        schema["properties"]["tags"],
+        json!({"type": "array", "items": {"type": "string"}})
+    );
+    assert_eq!(
+        schema["properties"]["detail"]["enum"],
+        json!(["brief", "full_report", "raw"])
+    );
+    assert!(schema["properties"].get("cache_key").is_none());
+}
+
+#[test]
+fn test_derive_tool_parameters_nested() {
+    let schema = serde_json::to_value(GetWeather::parameters()).unwrap();
+    assert_eq!(
+        schema["properties"]["coordinates"],
+        json!({
+            "type": "object",
+            "description": "地理坐标",
+            "properties": {
+                "lat": {"type": "number", "description": "纬度"},
+                "lon": {"type": "number", "description": "经度"}
+            },
+            "required": ["lat", "lon"]
+        })
+    );
+}
+
+#[test]
+fn test_derive_tool_parameters_rename() {
+    #[derive(ToolParameters)]
+    #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    struct Search {
+        search_query: String,
+        #[serde(rename = "max")]
+        max_results: Option<u32>,
+        r#type: String,
+    }
+
+    let schema = serde_json::to_value(Search::parameters()).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    let mut names: Vec<_> = properties.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, vec!["max", "searchQuery", "type"]);
+    assert!(schema.get("description").is_none());
+}
+
+#[test]
+fn test_derived_schema_matches_arguments() {
+    let tool = ChatCompletionToolParam::function(
+        "get_weather",
+        "获取指定地点的天气",
+        GetWeather::parameters(),
+    );
+    let ChatCompletionToolParam::Function(definition) = tool;
+    assert_eq!(definition.name, "get_weather");
+
+    let function = Function::new(
+        "call_1",
+        "get_weather",
+        r#"{"location":"Boston, MA","unit":"fahrenheit","days":3,"include_hourly":false,"tags":[],"detail":"full_report"}"#,
+    );
+    let args: GetWeather = function.parse_arguments().unwrap();
+    assert_eq!(args.location, "Boston, MA");
+    assert!(matches!(args.unit, Some(Unit::Fahrenheit)));
+    assert!(matches!(args.detail, Detail::FullReport));
+}